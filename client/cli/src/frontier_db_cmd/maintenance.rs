@@ -0,0 +1,239 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+	fs,
+	io::{self, BufWriter, Read, Write},
+	ops::RangeInclusive,
+	sync::Arc,
+};
+
+use ethereum_types::H256;
+use serde::{Deserialize, Serialize};
+use serde_json::Deserializer;
+// Substrate
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+// Frontier
+use fc_storage::StorageOverride;
+use fp_rpc::EthereumRuntimeRPCApi;
+
+use super::FrontierDbCmd;
+
+/// A single line of a mapping dump.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MappingEntry<H> {
+	pub substrate_block_hash: H,
+	/// `None` when the block was synced but carries no ethereum block.
+	pub ethereum_block_hash: Option<H256>,
+	pub ethereum_transaction_hashes: Vec<H256>,
+}
+
+/// Range based maintenance operations on the mapping columns.
+pub struct Maintenance<'a, B: BlockT, C> {
+	cmd: &'a FrontierDbCmd,
+	client: Arc<C>,
+	backend: Arc<fc_db::kv::Backend<B, C>>,
+	storage_override: Arc<dyn StorageOverride<B>>,
+}
+
+impl<'a, B, C> Maintenance<'a, B, C>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+{
+	pub fn new(
+		cmd: &'a FrontierDbCmd,
+		client: Arc<C>,
+		backend: Arc<fc_db::kv::Backend<B, C>>,
+		storage_override: Arc<dyn StorageOverride<B>>,
+	) -> Self {
+		Self {
+			cmd,
+			client,
+			backend,
+			storage_override,
+		}
+	}
+
+	/// Rebuild the mapping of every canonical block in the range, regardless of its sync status.
+	pub fn reindex(&self) -> sc_cli::Result<()> {
+		let range = self.range()?;
+		let (from, to) = (*range.start(), *range.end());
+		for number in range {
+			let hash = self.canonical_hash(number)?;
+			let header = self
+				.client
+				.header(hash)?
+				.ok_or_else(|| format!("Header of block #{} not found", number))?;
+			fc_mapping_sync::kv::sync_header(
+				self.client.as_ref(),
+				self.storage_override.clone(),
+				self.backend.as_ref(),
+				&header,
+			)?;
+		}
+		println!("Reindexed blocks #{} to #{}", from, to);
+		Ok(())
+	}
+
+	/// Check that the block and transaction columns agree with `pallet_ethereum::CurrentBlock`
+	/// for every canonical block in the range.
+	pub fn verify(&self) -> sc_cli::Result<()> {
+		let mut mismatches = 0u32;
+		for number in self.range()? {
+			let hash = self.canonical_hash(number)?;
+			if !self.backend.mapping().is_synced(&hash)? {
+				println!("#{} ({:?}): not synced", number, hash);
+				mismatches += 1;
+				continue;
+			}
+			let Some(block) = self.storage_override.current_block(hash) else {
+				continue;
+			};
+			let ethereum_block_hash = block.header.hash();
+			let mapped = self.backend.mapping().block_hash(&ethereum_block_hash)?;
+			if !mapped.map_or(false, |hashes| hashes.contains(&hash)) {
+				println!(
+					"#{} ({:?}): ethereum block {:?} is not mapped",
+					number, hash, ethereum_block_hash
+				);
+				mismatches += 1;
+			}
+			for (index, transaction) in block.transactions.iter().enumerate() {
+				let transaction_hash = transaction.hash();
+				let found = self
+					.backend
+					.mapping()
+					.transaction_metadata(&transaction_hash)?
+					.iter()
+					.any(|metadata| {
+						metadata.substrate_block_hash == hash
+							&& metadata.ethereum_block_hash == ethereum_block_hash
+							&& metadata.ethereum_index == index as u32
+					});
+				if !found {
+					println!(
+						"#{} ({:?}): transaction {:?} at index {} is not mapped",
+						number, hash, transaction_hash, index
+					);
+					mismatches += 1;
+				}
+			}
+		}
+		if mismatches > 0 {
+			return Err(format!("Found {} mapping inconsistencies", mismatches).into());
+		}
+		println!("Mapping is consistent");
+		Ok(())
+	}
+
+	/// Write the mapping of every synced canonical block in the range as JSON lines.
+	pub fn export(&self) -> sc_cli::Result<()> {
+		let mut output: Box<dyn Write> = match &self.cmd.value {
+			Some(path) => Box::new(BufWriter::new(fs::File::create(path)?)),
+			None => Box::new(io::stdout()),
+		};
+		for number in self.range()? {
+			let hash = self.canonical_hash(number)?;
+			if !self.backend.mapping().is_synced(&hash)? {
+				continue;
+			}
+			let entry = match self.storage_override.current_block(hash) {
+				Some(block) => {
+					let ethereum_block_hash = block.header.hash();
+					let mapped = self.backend.mapping().block_hash(&ethereum_block_hash)?;
+					if !mapped.map_or(false, |hashes| hashes.contains(&hash)) {
+						continue;
+					}
+					MappingEntry {
+						substrate_block_hash: hash,
+						ethereum_block_hash: Some(ethereum_block_hash),
+						ethereum_transaction_hashes: block
+							.transactions
+							.iter()
+							.map(|transaction| transaction.hash())
+							.collect(),
+					}
+				}
+				None => MappingEntry {
+					substrate_block_hash: hash,
+					ethereum_block_hash: None,
+					ethereum_transaction_hashes: Vec::new(),
+				},
+			};
+			let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+			writeln!(output, "{}", line)?;
+		}
+		output.flush()?;
+		Ok(())
+	}
+
+	/// Load a dump produced by `export` into the mapping columns.
+	pub fn import(&self) -> sc_cli::Result<()> {
+		let imported = match &self.cmd.value {
+			Some(path) => self.import_from(fs::File::open(path)?)?,
+			None => self.import_from(io::stdin())?,
+		};
+		println!("Imported {} mapping entries", imported);
+		Ok(())
+	}
+
+	fn import_from<I: Read>(&self, input: I) -> sc_cli::Result<usize> {
+		let mut imported = 0;
+		for entry in Deserializer::from_reader(input).into_iter::<MappingEntry<B::Hash>>() {
+			let entry = entry.map_err(|e| format!("Failed to deserialize mapping entry: {}", e))?;
+			match entry.ethereum_block_hash {
+				Some(ethereum_block_hash) => {
+					self.backend
+						.mapping()
+						.write_hashes(fc_db::kv::MappingCommitment::<B> {
+							block_hash: entry.substrate_block_hash,
+							ethereum_block_hash,
+							ethereum_transaction_hashes: entry.ethereum_transaction_hashes,
+						})?
+				}
+				None => self
+					.backend
+					.mapping()
+					.write_none(entry.substrate_block_hash)?,
+			}
+			imported += 1;
+		}
+		Ok(imported)
+	}
+
+	fn range(&self) -> sc_cli::Result<RangeInclusive<u32>> {
+		let best: u32 = self.client.info().best_number.unique_saturated_into();
+		let from = self.cmd.from.unwrap_or(0);
+		let to = self.cmd.to.unwrap_or(best).min(best);
+		if from > to {
+			return Err(format!("Invalid block range #{} to #{}", from, to).into());
+		}
+		Ok(from..=to)
+	}
+
+	fn canonical_hash(&self, number: u32) -> sc_cli::Result<B::Hash> {
+		Ok(self
+			.client
+			.hash(number.into())?
+			.ok_or_else(|| format!("Block #{} not found", number))?)
+	}
+}
@@ -171,6 +171,7 @@ where
 					.to_string()
 					.into())
 			}
+			_ => return Err(self.operation_error(&self.cmd.operation)),
 		}
 		Ok(())
 	}
@@ -147,6 +147,7 @@ impl<'a, B: BlockT, C: HeaderBackend<B>> MetaDb<'a, B, C> {
 					self.backend.meta().write_ethereum_schema(vec![])?;
 				}
 			},
+			_ => return Err(self.operation_error(&self.cmd.operation)),
 		}
 		Ok(())
 	}
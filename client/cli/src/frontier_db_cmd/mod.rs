@@ -16,6 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+mod maintenance;
 mod mapping_db;
 mod meta_db;
 #[cfg(test)]
@@ -32,8 +33,11 @@ use sc_cli::{PruningParams, SharedParams};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_storage::StorageOverride;
 
 use self::{
+	maintenance::Maintenance,
 	mapping_db::{MappingDb, MappingKey, MappingValue},
 	meta_db::{MetaDb, MetaKey, MetaValue},
 };
@@ -43,19 +47,37 @@ use self::{
 pub struct FrontierDbCmd {
 	/// Specify the operation to perform.
 	///
-	/// Can be one of `create | read | update | delete`.
+	/// Can be one of `create | read | update | delete | reindex | verify | export | import`.
 	#[arg(value_enum, ignore_case = true, required = true)]
 	pub operation: Operation,
 
 	/// Specify the column to query.
 	///
-	/// Can be one of `meta | block | transaction`.
-	#[arg(value_enum, ignore_case = true, required = true)]
-	pub column: Column,
+	/// Can be one of `meta | block | transaction`. Only used by the CRUD operations.
+	#[arg(
+		value_enum,
+		ignore_case = true,
+		required_if_eq_any([
+			("operation", "create"),
+			("operation", "read"),
+			("operation", "update"),
+			("operation", "delete"),
+		])
+	)]
+	pub column: Option<Column>,
 
 	/// Specify the key to either read or write.
-	#[arg(short('k'), long, required = true)]
-	pub key: String,
+	#[arg(
+		short('k'),
+		long,
+		required_if_eq_any([
+			("operation", "create"),
+			("operation", "read"),
+			("operation", "update"),
+			("operation", "delete"),
+		])
+	)]
+	pub key: Option<String>,
 
 	/// Specify the value to write.
 	///
@@ -63,9 +85,24 @@ pub struct FrontierDbCmd {
 	/// - When `None`, read from stdin.
 	///
 	/// In any case, payload must be serializable to a known type.
+	///
+	/// For `export` and `import` this is the dump file, falling back to stdout and
+	/// stdin respectively.
 	#[arg(long)]
 	pub value: Option<PathBuf>,
 
+	/// First block number of the range processed by `reindex | verify | export`.
+	///
+	/// Defaults to the genesis block.
+	#[arg(long)]
+	pub from: Option<u32>,
+
+	/// Last block number (inclusive) of the range processed by `reindex | verify | export`.
+	///
+	/// Defaults to the best block.
+	#[arg(long)]
+	pub to: Option<u32>,
+
 	/// Shared parameters
 	#[command(flatten)]
 	pub shared_params: SharedParams,
@@ -81,6 +118,14 @@ pub enum Operation {
 	Read,
 	Update,
 	Delete,
+	/// Rebuild the mapping of a block range from the chain.
+	Reindex,
+	/// Cross-check the mapping of a block range against the on-chain ethereum blocks.
+	Verify,
+	/// Dump the mapping of a block range as JSON lines.
+	Export,
+	/// Load a mapping dump produced by `export`.
+	Import,
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -98,22 +143,46 @@ pub enum DbValue<H> {
 }
 
 impl FrontierDbCmd {
+	/// Run the command against `backend`. Only the key-value backend is supported, the columns of
+	/// the SQL backend are not handled by this command.
 	pub fn run<B, C>(
 		&self,
 		client: Arc<C>,
-		backend: Arc<fc_db::kv::Backend<B, C>>,
+		backend: fc_db::Backend<B, C>,
+		storage_override: Arc<dyn StorageOverride<B>>,
 	) -> sc_cli::Result<()>
 	where
 		B: BlockT,
 		C: HeaderBackend<B> + ProvideRuntimeApi<B>,
 		C::Api: fp_rpc::EthereumRuntimeRPCApi<B>,
 	{
-		match self.column {
+		let backend = match backend {
+			fc_db::Backend::KeyValue(backend) => backend,
+			#[allow(unreachable_patterns)]
+			_ => return Err("`frontier-db` only supports the key-value backend".into()),
+		};
+
+		if let Operation::Reindex | Operation::Verify | Operation::Export | Operation::Import =
+			self.operation
+		{
+			let maintenance = Maintenance::new(self, client, backend, storage_override);
+			return match self.operation {
+				Operation::Reindex => maintenance.reindex(),
+				Operation::Verify => maintenance.verify(),
+				Operation::Export => maintenance.export(),
+				_ => maintenance.import(),
+			};
+		}
+
+		let (Some(column), Some(key)) = (&self.column, &self.key) else {
+			return Err(format!("`{:?}` requires a column and a key", self.operation).into());
+		};
+		match column {
 			Column::Meta => {
 				// New meta db handler
 				let meta_db = MetaDb::new(self, backend);
 				// Maybe get a MetaKey
-				let key = MetaKey::from_str(&self.key)?;
+				let key = MetaKey::from_str(key)?;
 				// Maybe get a MetaValue
 				let value = match utils::maybe_deserialize_value::<B>(
 					&self.operation,
@@ -131,7 +200,7 @@ impl FrontierDbCmd {
 				let mapping_db = MappingDb::new(self, client, backend);
 				// Maybe get a MappingKey
 				let key = MappingKey::EthBlockOrTransactionHash(
-					H256::from_str(&self.key).expect("H256 provided key"),
+					H256::from_str(key).expect("H256 provided key"),
 				);
 				// Maybe get a MappingValue
 				let value = match utils::maybe_deserialize_value::<B>(
@@ -143,7 +212,7 @@ impl FrontierDbCmd {
 					_ => return Err(format!("Unexpected `{:?}` value", self.value).into()),
 				};
 				// Run the query
-				mapping_db.query(&self.column, &key, &value)?
+				mapping_db.query(column, &key, &value)?
 			}
 		}
 		Ok(())
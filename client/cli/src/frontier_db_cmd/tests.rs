@@ -18,7 +18,7 @@
 
 use std::{collections::HashMap, path::PathBuf, str::FromStr, sync::Arc};
 
-use ethereum_types::H256;
+use ethereum_types::{Address, H256, U256};
 use futures::executor;
 use codec::Encode;
 use serde::Serialize;
@@ -32,12 +32,14 @@ use sp_io::hashing::twox_128;
 use sp_runtime::{
 	generic::{Block, Header},
 	traits::{BlakeTwo256, Block as BlockT},
+	Permill,
 };
 use substrate_test_runtime_client::{
 	BlockBuilderExt, ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt,
 	TestClientBuilder,
 };
 // Frontier
+use fc_storage::StorageOverride;
 use fp_storage::{constants::*, EthereumStorageSchema};
 use argochain_runtime::RuntimeApi;
 
@@ -61,6 +63,37 @@ pub fn open_frontier_backend<Block: BlockT, C: HeaderBackend<Block>>(
 	)?))
 }
 
+/// Storage override for a chain without any ethereum block.
+struct EmptyStorageOverride;
+
+impl StorageOverride<OpaqueBlock> for EmptyStorageOverride {
+	fn account_code_at(&self, _at: H256, _address: Address) -> Option<Vec<u8>> {
+		None
+	}
+	fn account_storage_at(&self, _at: H256, _address: Address, _index: U256) -> Option<H256> {
+		None
+	}
+	fn current_block(&self, _at: H256) -> Option<ethereum::BlockV2> {
+		None
+	}
+	fn current_receipts(&self, _at: H256) -> Option<Vec<ethereum::ReceiptV3>> {
+		None
+	}
+	fn current_transaction_statuses(&self, _at: H256) -> Option<Vec<fp_rpc::TransactionStatus>> {
+		None
+	}
+	fn elasticity(&self, _at: H256) -> Option<Permill> {
+		None
+	}
+	fn is_eip1559(&self, _at: H256) -> bool {
+		false
+	}
+}
+
+fn storage_override() -> Arc<dyn StorageOverride<OpaqueBlock>> {
+	Arc::new(EmptyStorageOverride)
+}
+
 fn storage_prefix_build(module: &[u8], storage: &[u8]) -> Vec<u8> {
 	[twox_128(module), twox_128(storage)].concat().to_vec()
 }
@@ -76,9 +109,11 @@ enum TestValue {
 fn cmd(key: String, value: Option<PathBuf>, operation: Operation, column: Column) -> FrontierDbCmd {
 	FrontierDbCmd {
 		operation,
-		column,
-		key,
+		column: Some(column),
+		key: Some(key),
 		value,
+		from: None,
+		to: None,
 		shared_params: sc_cli::SharedParams {
 			chain: None,
 			dev: true,
@@ -139,7 +174,11 @@ fn schema_create_success_if_value_is_empty() {
 		Operation::Create,
 		Column::Meta
 	)
-	.run(client, backend.clone())
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	assert_eq!(
@@ -175,7 +214,11 @@ fn schema_create_fails_if_value_is_not_empty() {
 		Operation::Create,
 		Column::Meta
 	)
-	.run(client, backend.clone())
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_err());
 
 	let data_after = backend.meta().ethereum_schema().unwrap().unwrap();
@@ -208,7 +251,11 @@ fn schema_read_works() {
 		Operation::Read,
 		Column::Meta
 	)
-	.run(client, backend)
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend),
+		storage_override()
+	)
 	.is_ok());
 }
 
@@ -232,7 +279,11 @@ fn schema_update_works() {
 		Operation::Update,
 		Column::Meta
 	)
-	.run(client, backend.clone())
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	assert_eq!(
@@ -264,7 +315,11 @@ fn schema_delete_works() {
 		Operation::Delete,
 		Column::Meta
 	)
-	.run(client, backend.clone())
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	assert_eq!(backend.meta().ethereum_schema(), Ok(Some(vec![])));
@@ -290,7 +345,11 @@ fn tips_create_success_if_value_is_empty() {
 		Operation::Create,
 		Column::Meta
 	)
-	.run(client, backend.clone())
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	assert_eq!(
@@ -324,7 +383,11 @@ fn tips_create_fails_if_value_is_not_empty() {
 		Operation::Create,
 		Column::Meta
 	)
-	.run(client, backend.clone())
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_err());
 
 	let data_after = backend.meta().current_syncing_tips().unwrap();
@@ -356,7 +419,11 @@ fn tips_read_works() {
 		Operation::Read,
 		Column::Meta
 	)
-	.run(client, backend)
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend),
+		storage_override()
+	)
 	.is_ok());
 }
 
@@ -380,7 +447,11 @@ fn tips_update_works() {
 		Operation::Update,
 		Column::Meta
 	)
-	.run(client, backend.clone())
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	assert_eq!(
@@ -412,7 +483,11 @@ fn tips_delete_works() {
 		Operation::Delete,
 		Column::Meta
 	)
-	.run(client, backend.clone())
+	.run(
+		client,
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	assert_eq!(backend.meta().current_syncing_tips(), Ok(vec![]));
@@ -445,7 +520,11 @@ fn non_existent_meta_static_keys_are_no_op() {
 		Operation::Create,
 		Column::Meta
 	)
-	.run(Arc::clone(&client), backend.clone())
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_err());
 
 	assert_eq!(
@@ -455,7 +534,11 @@ fn non_existent_meta_static_keys_are_no_op() {
 
 	// Run the Read command
 	assert!(cmd(":foo".to_string(), None, Operation::Read, Column::Meta)
-		.run(Arc::clone(&client), backend.clone())
+		.run(
+			Arc::clone(&client),
+			fc_db::Backend::KeyValue(backend.clone()),
+			storage_override()
+		)
 		.is_err());
 
 	// Run the Update command
@@ -465,7 +548,11 @@ fn non_existent_meta_static_keys_are_no_op() {
 		Operation::Update,
 		Column::Meta
 	)
-	.run(Arc::clone(&client), backend.clone())
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_err());
 
 	assert_eq!(
@@ -476,7 +563,11 @@ fn non_existent_meta_static_keys_are_no_op() {
 	// Run the Delete command
 	assert!(
 		cmd(":foo".to_string(), None, Operation::Delete, Column::Meta)
-			.run(Arc::clone(&client), backend.clone())
+			.run(
+				Arc::clone(&client),
+				fc_db::Backend::KeyValue(backend.clone()),
+				storage_override()
+			)
 			.is_err()
 	);
 
@@ -512,7 +603,11 @@ fn not_deserializable_input_value_is_no_op() {
 		Operation::Create,
 		Column::Meta
 	)
-	.run(Arc::clone(&client), backend.clone())
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_err());
 
 	assert_eq!(backend.meta().ethereum_schema(), Ok(None));
@@ -524,7 +619,11 @@ fn not_deserializable_input_value_is_no_op() {
 		Operation::Update,
 		Column::Meta
 	)
-	.run(Arc::clone(&client), backend.clone())
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_err());
 
 	assert_eq!(backend.meta().ethereum_schema(), Ok(None));
@@ -574,7 +673,11 @@ fn commitment_create() {
 		Operation::Create,
 		Column::Block
 	)
-	.run(Arc::clone(&client), backend.clone())
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	// Expect the ethereum and substrate block hashes to be mapped.
@@ -601,7 +704,11 @@ fn commitment_create() {
 		Operation::Create,
 		Column::Block
 	)
-	.run(Arc::clone(&client), backend)
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend),
+		storage_override()
+	)
 	.is_err());
 }
 
@@ -660,7 +767,11 @@ fn commitment_update() {
 		Operation::Create,
 		Column::Block
 	)
-	.run(Arc::clone(&client), backend.clone())
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	// Expect the ethereum and substrate block hashes to be mapped.
@@ -708,7 +819,11 @@ fn commitment_update() {
 		Operation::Update,
 		Column::Block
 	)
-	.run(Arc::clone(&client), backend.clone())
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	// Expect the ethereum and substrate block hashes to be mapped.
@@ -785,7 +900,11 @@ fn mapping_read_works() {
 		Operation::Create,
 		Column::Block,
 	)
-	.run(Arc::clone(&client), backend.clone())
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	// Read block command.
@@ -795,7 +914,11 @@ fn mapping_read_works() {
 		Operation::Read,
 		Column::Block
 	)
-	.run(Arc::clone(&client), backend.clone())
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend.clone()),
+		storage_override()
+	)
 	.is_ok());
 
 	// Read transaction command.
@@ -805,6 +928,164 @@ fn mapping_read_works() {
 		Operation::Read,
 		Column::Transaction
 	)
-	.run(Arc::clone(&client), backend)
+	.run(
+		Arc::clone(&client),
+		fc_db::Backend::KeyValue(backend),
+		storage_override()
+	)
 	.is_ok());
 }
+
+fn maintenance_cmd(operation: Operation, value: Option<PathBuf>) -> FrontierDbCmd {
+	FrontierDbCmd {
+		column: None,
+		key: None,
+		..cmd(String::new(), value, operation, Column::Meta)
+	}
+}
+
+#[test]
+fn verify_reports_unsynced_blocks() {
+	let tmp = tempdir().expect("create a temporary directory");
+
+	// Test client.
+	let (client, _) = TestClientBuilder::new().build_with_native_executor::<RuntimeApi, _>(None);
+	let client = Arc::new(client);
+	// Create a temporary frontier secondary DB.
+	let backend = open_frontier_backend::<OpaqueBlock, _>(client.clone(), tmp.into_path())
+		.expect("a temporary db was created");
+
+	// The genesis block was never synced.
+	assert!(maintenance_cmd(Operation::Verify, None)
+		.run(
+			Arc::clone(&client),
+			fc_db::Backend::KeyValue(backend.clone()),
+			storage_override()
+		)
+		.is_err());
+
+	backend
+		.mapping()
+		.write_none(client.info().genesis_hash)
+		.expect("write genesis mapping");
+
+	assert!(maintenance_cmd(Operation::Verify, None)
+		.run(
+			client,
+			fc_db::Backend::KeyValue(backend),
+			storage_override()
+		)
+		.is_ok());
+}
+
+#[test]
+fn reindex_syncs_the_block_range() {
+	let tmp = tempdir().expect("create a temporary directory");
+
+	// Test client with two blocks on top of the genesis block.
+	let (c, _) = TestClientBuilder::new().build_with_native_executor::<RuntimeApi, _>(None);
+	let mut client = Arc::new(c);
+	let mut hashes = vec![client.info().genesis_hash];
+	for _ in 0..2 {
+		let chain = client.chain_info();
+		let block = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(chain.best_hash)
+			.with_parent_block_number(chain.best_number)
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block;
+		hashes.push(block.header.hash());
+		executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+	}
+	// Create a temporary frontier secondary DB.
+	let backend = open_frontier_backend::<OpaqueBlock, _>(client.clone(), tmp.into_path())
+		.expect("a temporary db was created");
+	let range_cmd = |operation, from| FrontierDbCmd {
+		from,
+		..maintenance_cmd(operation, None)
+	};
+
+	// Only the blocks of the range are reindexed.
+	assert!(range_cmd(Operation::Reindex, Some(1))
+		.run(
+			Arc::clone(&client),
+			fc_db::Backend::KeyValue(backend.clone()),
+			storage_override()
+		)
+		.is_ok());
+	assert_eq!(backend.mapping().is_synced(&hashes[0]), Ok(false));
+	assert_eq!(backend.mapping().is_synced(&hashes[1]), Ok(true));
+	assert_eq!(backend.mapping().is_synced(&hashes[2]), Ok(true));
+	assert!(range_cmd(Operation::Verify, Some(1))
+		.run(
+			Arc::clone(&client),
+			fc_db::Backend::KeyValue(backend.clone()),
+			storage_override()
+		)
+		.is_ok());
+	assert!(range_cmd(Operation::Verify, None)
+		.run(
+			Arc::clone(&client),
+			fc_db::Backend::KeyValue(backend.clone()),
+			storage_override()
+		)
+		.is_err());
+
+	// The genesis block is synced like the mapping sync worker does.
+	assert!(range_cmd(Operation::Reindex, None)
+		.run(
+			Arc::clone(&client),
+			fc_db::Backend::KeyValue(backend.clone()),
+			storage_override()
+		)
+		.is_ok());
+	assert_eq!(backend.mapping().is_synced(&hashes[0]), Ok(true));
+	assert!(range_cmd(Operation::Verify, None)
+		.run(
+			client,
+			fc_db::Backend::KeyValue(backend),
+			storage_override()
+		)
+		.is_ok());
+}
+
+#[test]
+fn export_import_roundtrip() {
+	let tmp = tempdir().expect("create a temporary directory");
+	let dump_path = tmp.path().join("mapping.jsonl");
+
+	// Test client.
+	let (client, _) = TestClientBuilder::new().build_with_native_executor::<RuntimeApi, _>(None);
+	let client = Arc::new(client);
+	let genesis_hash = client.info().genesis_hash;
+	// Create two temporary frontier secondary DBs.
+	let source = open_frontier_backend::<OpaqueBlock, _>(client.clone(), tmp.path().join("source"))
+		.expect("a temporary db was created");
+	let target = open_frontier_backend::<OpaqueBlock, _>(client.clone(), tmp.path().join("target"))
+		.expect("a temporary db was created");
+
+	source
+		.mapping()
+		.write_none(genesis_hash)
+		.expect("write genesis mapping");
+
+	assert!(maintenance_cmd(Operation::Export, Some(dump_path.clone()))
+		.run(
+			Arc::clone(&client),
+			fc_db::Backend::KeyValue(source),
+			storage_override()
+		)
+		.is_ok());
+	assert_eq!(target.mapping().is_synced(&genesis_hash), Ok(false));
+
+	assert!(maintenance_cmd(Operation::Import, Some(dump_path))
+		.run(
+			client,
+			fc_db::Backend::KeyValue(target.clone()),
+			storage_override()
+		)
+		.is_ok());
+	assert_eq!(target.mapping().is_synced(&genesis_hash), Ok(true));
+}
@@ -95,6 +95,10 @@ pub trait FrontierDbMessage {
 		"One-to-many operation not allowed".into()
 	}
 
+	fn operation_error(&self, operation: &Operation) -> sc_cli::Error {
+		format!("Operation `{:?}` does not apply to a single key", operation).into()
+	}
+
 	#[cfg(not(test))]
 	fn confirmation_prompt<K: core::fmt::Debug, V: core::fmt::Debug>(
 		&self,
@@ -0,0 +1,431 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Sectioned bloom-bits log index.
+//!
+//! The chain is split into sections of [`SECTION_SIZE`] blocks. For every finalized section the
+//! bloom filters of its blocks are rotated into 2048 bit vectors, one per bloom bit, so that
+//! answering "which blocks may contain this address or topic" only reads three vectors per
+//! section instead of every block header.
+
+use std::{collections::HashSet, marker::PhantomData, sync::Arc};
+
+use codec::{Decode, Encode};
+use ethereum_types::{Bloom, BloomInput};
+// Substrate
+use sp_blockchain::HeaderBackend;
+use sp_core::{H160, H256};
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+// Frontier
+use fc_api::FilteredLog;
+use fc_storage::StorageOverride;
+use fp_storage::EthereumStorageSchema;
+
+use super::{columns, Database, DbHash};
+
+/// Number of blocks in a bloom-bits section.
+pub const SECTION_SIZE: u64 = 4096;
+/// Number of bits in an ethereum logs bloom.
+const BLOOM_BITS: usize = 2048;
+/// Size in bytes of the bit vector of a single bloom bit over a section.
+const SECTION_BYTES: usize = SECTION_SIZE as usize / 8;
+/// Maximum number of topics a log can hold.
+const MAX_TOPIC_COUNT: usize = 4;
+
+mod static_keys {
+	pub const BLOOM_BITS_SECTIONS: &[u8] = b"BLOOM_BITS_SECTIONS";
+	pub const BLOOM_BITS_PREFIX: &[u8] = b"BLOOM_BITS";
+}
+
+fn bits_key(section: u64, bit: u16) -> Vec<u8> {
+	(static_keys::BLOOM_BITS_PREFIX, section, bit).encode()
+}
+
+/// Positions of the bits set in `bloom`.
+fn set_bits(bloom: &Bloom) -> Vec<u16> {
+	bloom
+		.as_bytes()
+		.iter()
+		.enumerate()
+		.flat_map(|(byte, value)| {
+			(0..8u16)
+				.filter(move |bit| value & (1 << bit) != 0)
+				.map(move |bit| byte as u16 * 8 + bit)
+		})
+		.collect()
+}
+
+fn has_bit(bloom: &Bloom, bit: u16) -> bool {
+	bloom.as_bytes()[bit as usize / 8] & (1 << (bit % 8)) != 0
+}
+
+/// Bloom bits of every value accepted at one filter position (the address or a topic).
+type BloomGroup = Vec<Vec<u16>>;
+
+/// Bloom-bits columns, stored in the meta column of the KV database.
+pub struct BloomBitsDb<Block> {
+	db: Arc<dyn Database<DbHash>>,
+	_marker: PhantomData<Block>,
+}
+
+impl<Block: BlockT> BloomBitsDb<Block> {
+	pub(crate) fn new(db: Arc<dyn Database<DbHash>>) -> Self {
+		Self {
+			db,
+			_marker: PhantomData,
+		}
+	}
+
+	/// Number of sections indexed so far. Sections are always indexed in order.
+	pub fn indexed_sections(&self) -> Result<u64, String> {
+		match self.db.get(columns::META, static_keys::BLOOM_BITS_SECTIONS) {
+			Some(raw) => Ok(u64::decode(&mut &raw[..]).map_err(|e| format!("{:?}", e))?),
+			None => Ok(0),
+		}
+	}
+
+	/// Bit vector of `bit` over the blocks of `section`.
+	pub fn section_bits(&self, section: u64, bit: u16) -> Option<Vec<u8>> {
+		self.db.get(columns::META, &bits_key(section, bit))
+	}
+
+	/// Index the next section, given the logs bloom of each of its blocks.
+	pub fn write_section(&self, section: u64, blooms: &[Bloom]) -> Result<(), String> {
+		if section != self.indexed_sections()? {
+			return Err(format!("Bloom bits section {} is out of order", section));
+		}
+		if blooms.len() != SECTION_SIZE as usize {
+			return Err(format!(
+				"Bloom bits section {} expects {} blooms, got {}",
+				section,
+				SECTION_SIZE,
+				blooms.len()
+			));
+		}
+
+		let mut vectors = vec![vec![0u8; SECTION_BYTES]; BLOOM_BITS];
+		for (index, bloom) in blooms.iter().enumerate() {
+			for bit in set_bits(bloom) {
+				vectors[bit as usize][index / 8] |= 1 << (index % 8);
+			}
+		}
+
+		let mut transaction = sp_database::Transaction::new();
+		// Vectors without any set bit are not stored.
+		for (bit, vector) in vectors.into_iter().enumerate() {
+			if vector.iter().any(|byte| *byte != 0) {
+				transaction.set(columns::META, &bits_key(section, bit as u16), &vector);
+			}
+		}
+		transaction.set(
+			columns::META,
+			static_keys::BLOOM_BITS_SECTIONS,
+			&(section + 1).encode(),
+		);
+
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
+
+	/// Bit vector of the blocks of `section` whose bloom matches every group.
+	fn section_matches(&self, section: u64, groups: &[BloomGroup]) -> Vec<u8> {
+		let mut matches = vec![u8::MAX; SECTION_BYTES];
+		for group in groups {
+			let mut group_matches = vec![0u8; SECTION_BYTES];
+			for bits in group {
+				let mut value_matches = vec![u8::MAX; SECTION_BYTES];
+				for bit in bits {
+					match self.section_bits(section, *bit) {
+						Some(vector) => value_matches
+							.iter_mut()
+							.zip(vector)
+							.for_each(|(byte, other)| *byte &= other),
+						None => value_matches.fill(0),
+					}
+				}
+				group_matches
+					.iter_mut()
+					.zip(value_matches)
+					.for_each(|(byte, other)| *byte |= other);
+			}
+			matches
+				.iter_mut()
+				.zip(group_matches)
+				.for_each(|(byte, other)| *byte &= other);
+		}
+		matches
+	}
+}
+
+/// Log filter criteria, as accepted by [`fc_api::LogIndexerBackend::filter_logs`].
+struct LogFilter {
+	addresses: Vec<H160>,
+	topics: [HashSet<H256>; MAX_TOPIC_COUNT],
+	groups: Vec<BloomGroup>,
+}
+
+impl LogFilter {
+	fn new(addresses: Vec<H160>, topics: Vec<Vec<Option<H256>>>) -> Result<Self, String> {
+		let mut unique_topics: [HashSet<H256>; MAX_TOPIC_COUNT] = Default::default();
+		for topic_combination in topics.into_iter() {
+			for (topic_index, topic) in topic_combination.into_iter().enumerate() {
+				if topic_index == MAX_TOPIC_COUNT {
+					return Err("Invalid topic input. Maximum length is 4.".to_string());
+				}
+				if let Some(topic) = topic {
+					unique_topics[topic_index].insert(topic);
+				}
+			}
+		}
+
+		let value_bits = |value: &[u8]| set_bits(&Bloom::from(BloomInput::Raw(value)));
+		let mut groups = Vec::new();
+		if !addresses.is_empty() {
+			groups.push(
+				addresses
+					.iter()
+					.map(|address| value_bits(address.as_bytes()))
+					.collect(),
+			);
+		}
+		for topics in unique_topics.iter().filter(|topics| !topics.is_empty()) {
+			groups.push(
+				topics
+					.iter()
+					.map(|topic| value_bits(topic.as_bytes()))
+					.collect(),
+			);
+		}
+
+		Ok(Self {
+			addresses,
+			topics: unique_topics,
+			groups,
+		})
+	}
+
+	fn matches_bloom(&self, bloom: &Bloom) -> bool {
+		self.groups.iter().all(|group| {
+			group
+				.iter()
+				.any(|bits| bits.iter().all(|bit| has_bit(bloom, *bit)))
+		})
+	}
+
+	fn matches_log(&self, address: &H160, topics: &[H256]) -> bool {
+		(self.addresses.is_empty() || self.addresses.contains(address))
+			&& self.topics.iter().enumerate().all(|(index, accepted)| {
+				accepted.is_empty()
+					|| topics
+						.get(index)
+						.is_some_and(|topic| accepted.contains(topic))
+			})
+	}
+}
+
+/// Log indexer of the KV backend, answering log queries from the bloom-bits index.
+///
+/// Only enabled when a storage override is provided, as candidate blocks are resolved to
+/// exact logs from the chain state.
+pub struct LogIndexerBackend<Block, C> {
+	client: Arc<C>,
+	bloom_bits: Arc<BloomBitsDb<Block>>,
+	storage_override: Option<Arc<dyn StorageOverride<Block>>>,
+}
+
+impl<Block, C> Clone for LogIndexerBackend<Block, C> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			bloom_bits: self.bloom_bits.clone(),
+			storage_override: self.storage_override.clone(),
+		}
+	}
+}
+
+impl<Block: BlockT, C: HeaderBackend<Block>> LogIndexerBackend<Block, C> {
+	pub(crate) fn new(
+		client: Arc<C>,
+		bloom_bits: Arc<BloomBitsDb<Block>>,
+		storage_override: Option<Arc<dyn StorageOverride<Block>>>,
+	) -> Self {
+		Self {
+			client,
+			bloom_bits,
+			storage_override,
+		}
+	}
+
+	/// Append the logs of canonical block `number` matching `filter` to `logs`.
+	fn block_logs(
+		&self,
+		storage_override: &dyn StorageOverride<Block>,
+		number: u64,
+		filter: &LogFilter,
+		check_bloom: bool,
+		logs: &mut Vec<FilteredLog<Block>>,
+	) -> Result<(), String> {
+		let Some(substrate_block_hash) = self
+			.client
+			.hash(number.unique_saturated_into())
+			.map_err(|e| format!("{:?}", e))?
+		else {
+			return Ok(());
+		};
+		let Some(block) = storage_override.current_block(substrate_block_hash) else {
+			return Ok(());
+		};
+		if check_bloom && !filter.matches_bloom(&block.header.logs_bloom) {
+			return Ok(());
+		}
+		let Some(statuses) = storage_override.current_transaction_statuses(substrate_block_hash)
+		else {
+			return Ok(());
+		};
+
+		let ethereum_block_hash = block.header.hash();
+		for status in statuses {
+			for (log_index, log) in status.logs.iter().enumerate() {
+				if filter.matches_log(&log.address, &log.topics) {
+					logs.push(FilteredLog {
+						substrate_block_hash,
+						ethereum_block_hash,
+						block_number: number as u32,
+						// The KV backend does not track schema migrations, the storage
+						// override resolves the actual schema when the block is read.
+						ethereum_storage_schema: EthereumStorageSchema::V3,
+						transaction_index: status.transaction_index,
+						log_index: log_index as u32,
+					});
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block: BlockT, C: HeaderBackend<Block>> fc_api::LogIndexerBackend<Block>
+	for LogIndexerBackend<Block, C>
+{
+	fn is_indexed(&self) -> bool {
+		self.storage_override.is_some()
+	}
+
+	async fn filter_logs(
+		&self,
+		from_block: u64,
+		to_block: u64,
+		addresses: Vec<H160>,
+		topics: Vec<Vec<Option<H256>>>,
+	) -> Result<Vec<FilteredLog<Block>>, String> {
+		let storage_override = self
+			.storage_override
+			.as_deref()
+			.ok_or_else(|| "KeyValue db log index is disabled".to_string())?;
+		let filter = LogFilter::new(addresses, topics)?;
+		let indexed_sections = self.bloom_bits.indexed_sections()?;
+
+		let mut logs = Vec::new();
+		let mut number = from_block;
+		while number <= to_block {
+			let section = number / SECTION_SIZE;
+			let section_start = section * SECTION_SIZE;
+			let section_end = (section_start + SECTION_SIZE - 1).min(to_block);
+
+			if section < indexed_sections {
+				let matches = self.bloom_bits.section_matches(section, &filter.groups);
+				for candidate in number..=section_end {
+					let index = (candidate - section_start) as usize;
+					if matches[index / 8] & (1 << (index % 8)) != 0 {
+						self.block_logs(storage_override, candidate, &filter, false, &mut logs)?;
+					}
+				}
+			} else {
+				// Not indexed yet, fall back to the bloom of each block.
+				for candidate in number..=section_end {
+					self.block_logs(storage_override, candidate, &filter, true, &mut logs)?;
+				}
+			}
+
+			number = match section_end.checked_add(1) {
+				Some(next) => next,
+				None => break,
+			};
+		}
+		Ok(logs)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bloom_bits_round_trip() {
+		let address = H160::repeat_byte(0x11);
+		let topic = H256::repeat_byte(0x22);
+		let mut bloom = Bloom::default();
+		bloom.accrue(BloomInput::Raw(address.as_bytes()));
+		bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+
+		let bits = set_bits(&bloom);
+		assert!(!bits.is_empty() && bits.len() <= 6);
+		assert!(bits.iter().all(|bit| has_bit(&bloom, *bit)));
+
+		let filter = LogFilter::new(vec![address], vec![vec![Some(topic)]]).unwrap();
+		assert!(filter.matches_bloom(&bloom));
+		assert!(filter.matches_log(&address, &[topic]));
+		assert!(!filter.matches_log(&address, &[]));
+
+		let other = LogFilter::new(vec![H160::repeat_byte(0x33)], vec![]).unwrap();
+		assert!(!other.matches_log(&address, &[topic]));
+	}
+
+	#[test]
+	fn section_matches_blocks_containing_the_value() {
+		type OpaqueBlock = sp_runtime::generic::Block<
+			sp_runtime::generic::Header<u64, sp_runtime::traits::BlakeTwo256>,
+			sp_runtime::OpaqueExtrinsic,
+		>;
+		let bloom_bits = BloomBitsDb::<OpaqueBlock>::new(Arc::new(sp_database::MemDb::default()));
+
+		let address = H160::repeat_byte(0x11);
+		let mut blooms = vec![Bloom::default(); SECTION_SIZE as usize];
+		blooms[7].accrue(BloomInput::Raw(address.as_bytes()));
+		blooms[4000].accrue(BloomInput::Raw(address.as_bytes()));
+
+		// Sections must be indexed in order.
+		assert!(bloom_bits.write_section(1, &blooms).is_err());
+		assert!(bloom_bits.write_section(0, &blooms).is_ok());
+		assert_eq!(bloom_bits.indexed_sections(), Ok(1));
+
+		let filter = LogFilter::new(vec![address], vec![]).unwrap();
+		let matches = bloom_bits.section_matches(0, &filter.groups);
+		let candidates = (0..SECTION_SIZE as usize)
+			.filter(|index| matches[index / 8] & (1 << (index % 8)) != 0)
+			.collect::<Vec<_>>();
+		assert_eq!(candidates, vec![7, 4000]);
+	}
+
+	#[test]
+	fn too_many_topics_are_rejected() {
+		assert!(LogFilter::new(vec![], vec![vec![None; 5]]).is_err());
+	}
+}
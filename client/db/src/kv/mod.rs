@@ -16,10 +16,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+mod log_indexer;
 mod parity_db_adapter;
 mod upgrade;
 mod utils;
 
+pub use self::log_indexer::{BloomBitsDb, LogIndexerBackend, SECTION_SIZE};
+
 use std::{
 	marker::PhantomData,
 	path::{Path, PathBuf},
@@ -31,11 +34,12 @@ use codec::{Decode, Encode};
 // Substrate
 pub use sc_client_db::DatabaseSource;
 use sp_blockchain::HeaderBackend;
-use sp_core::{H160, H256};
+use sp_core::H256;
 pub use sp_database::Database;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
-use fc_api::{FilteredLog, TransactionMetadata};
+use fc_api::TransactionMetadata;
+use fc_storage::StorageOverride;
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA_CACHE};
 
 const DB_HASH_LEN: usize = 32;
@@ -66,7 +70,8 @@ pub struct Backend<Block, C> {
 	client: Arc<C>,
 	meta: Arc<MetaDb<Block>>,
 	mapping: Arc<MappingDb<Block>>,
-	log_indexer: LogIndexerBackend<Block>,
+	bloom_bits: Arc<BloomBitsDb<Block>>,
+	log_indexer: LogIndexerBackend<Block, C>,
 }
 
 #[async_trait::async_trait]
@@ -95,26 +100,6 @@ impl<Block: BlockT, C: HeaderBackend<Block>> fc_api::Backend<Block> for Backend<
 	}
 }
 
-#[derive(Clone, Default)]
-pub struct LogIndexerBackend<Block>(PhantomData<Block>);
-
-#[async_trait::async_trait]
-impl<Block: BlockT> fc_api::LogIndexerBackend<Block> for LogIndexerBackend<Block> {
-	fn is_indexed(&self) -> bool {
-		false
-	}
-
-	async fn filter_logs(
-		&self,
-		_from_block: u64,
-		_to_block: u64,
-		_addresses: Vec<H160>,
-		_topics: Vec<Vec<Option<H256>>>,
-	) -> Result<Vec<FilteredLog<Block>>, String> {
-		Err("KeyValue db does not index logs".into())
-	}
-}
-
 /// Returns the frontier database directory.
 pub fn frontier_database_dir(db_config_dir: &Path, db_path: &str) -> PathBuf {
 	db_config_dir.join("frontier").join(db_path)
@@ -156,9 +141,10 @@ impl<Block: BlockT, C: HeaderBackend<Block>> Backend<Block, C> {
 
 	pub fn new(client: Arc<C>, config: &DatabaseSettings) -> Result<Self, String> {
 		let db = utils::open_database::<Block, C>(client.clone(), config)?;
+		let bloom_bits = Arc::new(BloomBitsDb::new(db.clone()));
 
 		Ok(Self {
-			client,
+			client: client.clone(),
 			mapping: Arc::new(MappingDb {
 				db: db.clone(),
 				write_lock: Arc::new(Mutex::new(())),
@@ -168,14 +154,30 @@ impl<Block: BlockT, C: HeaderBackend<Block>> Backend<Block, C> {
 				db: db.clone(),
 				_marker: PhantomData,
 			}),
-			log_indexer: LogIndexerBackend(PhantomData),
+			bloom_bits: bloom_bits.clone(),
+			log_indexer: LogIndexerBackend::new(client, bloom_bits, None),
 		})
 	}
 
+	/// Answer log queries from the bloom-bits index, resolving matching blocks through
+	/// `storage_override`.
+	pub fn with_log_indexer(mut self, storage_override: Arc<dyn StorageOverride<Block>>) -> Self {
+		self.log_indexer = LogIndexerBackend::new(
+			self.client.clone(),
+			self.bloom_bits.clone(),
+			Some(storage_override),
+		);
+		self
+	}
+
 	pub fn mapping(&self) -> &Arc<MappingDb<Block>> {
 		&self.mapping
 	}
 
+	pub fn bloom_bits(&self) -> &Arc<BloomBitsDb<Block>> {
+		&self.bloom_bits
+	}
+
 	pub fn meta(&self) -> &Arc<MetaDb<Block>> {
 		&self.meta
 	}
@@ -340,11 +342,15 @@ impl<Block: BlockT> MappingDb<Block> {
 			.enumerate()
 		{
 			let mut metadata = self.transaction_metadata(&ethereum_transaction_hash)?;
-			metadata.push(TransactionMetadata::<Block> {
+			let entry = TransactionMetadata::<Block> {
 				substrate_block_hash: commitment.block_hash,
 				ethereum_block_hash: commitment.ethereum_block_hash,
 				ethereum_index: i as u32,
-			});
+			};
+			// Re-writing an already synced block (e.g. on reindex) must not duplicate entries.
+			if !metadata.contains(&entry) {
+				metadata.push(entry);
+			}
 			transaction.set(
 				columns::TRANSACTION_MAPPING,
 				&ethereum_transaction_hash.encode(),
@@ -22,20 +22,20 @@ mod worker;
 
 pub use worker::MappingSyncWorker;
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::{Backend as _, HeaderBackend};
 use sp_consensus::SyncOracle;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Zero};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto, Zero};
 // Frontier
 use fc_storage::StorageOverride;
 use fp_consensus::{FindLogError, Hashes, Log, PostLog, PreLog};
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::{EthereumBlockNotification, EthereumBlockNotificationSinks, SyncStrategy};
+use crate::{EthereumBlockNotification, EthereumBlockNotificationSinks, ReorgInfo, SyncStrategy};
 
 pub fn sync_block<Block: BlockT, C: HeaderBackend<Block>>(
 	storage_override: Arc<dyn StorageOverride<Block>>,
@@ -144,6 +144,24 @@ where
 	Ok(())
 }
 
+/// Write the mapping of the block of `header`, whether it is the genesis block or not.
+pub fn sync_header<Block: BlockT, C>(
+	client: &C,
+	storage_override: Arc<dyn StorageOverride<Block>>,
+	backend: &fc_db::kv::Backend<Block, C>,
+	header: &Block::Header,
+) -> Result<(), String>
+where
+	C: HeaderBackend<Block> + ProvideRuntimeApi<Block>,
+	C::Api: EthereumRuntimeRPCApi<Block>,
+{
+	if header.number() == &Zero::zero() {
+		sync_genesis_block(client, backend, header)
+	} else {
+		sync_block(storage_override, backend, header)
+	}
+}
+
 pub fn sync_one_block<Block: BlockT, C, BE>(
 	client: &C,
 	substrate_backend: &BE,
@@ -155,6 +173,7 @@ pub fn sync_one_block<Block: BlockT, C, BE>(
 	pubsub_notification_sinks: Arc<
 		EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>,
 	>,
+	pending_reorgs: &mut HashMap<Block::Hash, Arc<ReorgInfo<Block::Hash>>>,
 ) -> Result<bool, String>
 where
 	C: ProvideRuntimeApi<Block>,
@@ -197,34 +216,37 @@ where
 		}
 	};
 
-	if operating_header.number() == &Zero::zero() {
-		sync_genesis_block(client, frontier_backend, &operating_header)?;
-
-		frontier_backend
-			.meta()
-			.write_current_syncing_tips(current_syncing_tips)?;
-	} else {
+	if operating_header.number() != &Zero::zero() {
 		if SyncStrategy::Parachain == strategy
 			&& operating_header.number() > &client.info().best_number
 		{
 			return Ok(false);
 		}
-		sync_block(storage_override, frontier_backend, &operating_header)?;
-
 		current_syncing_tips.push(*operating_header.parent_hash());
-		frontier_backend
-			.meta()
-			.write_current_syncing_tips(current_syncing_tips)?;
 	}
+	sync_header(
+		client,
+		storage_override,
+		frontier_backend,
+		&operating_header,
+	)?;
+	frontier_backend
+		.meta()
+		.write_current_syncing_tips(current_syncing_tips)?;
 	// Notify on import and remove closed channels.
 	// Only notify when the node is node in major syncing.
+	let hash = operating_header.hash();
+	let is_new_best = client.info().best_hash == hash;
+	let reorg_info = pending_reorgs.remove(&hash).filter(|_| is_new_best);
 	let sinks = &mut pubsub_notification_sinks.lock();
 	sinks.retain(|sink| {
 		if !sync_oracle.is_major_syncing() {
-			let hash = operating_header.hash();
-			let is_new_best = client.info().best_hash == hash;
-			sink.unbounded_send(EthereumBlockNotification { is_new_best, hash })
-				.is_ok()
+			sink.unbounded_send(EthereumBlockNotification {
+				is_new_best,
+				hash,
+				reorg_info: reorg_info.clone(),
+			})
+			.is_ok()
 		} else {
 			// Remove from the pool if in major syncing.
 			false
@@ -245,6 +267,7 @@ pub fn sync_blocks<Block: BlockT, C, BE>(
 	pubsub_notification_sinks: Arc<
 		EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>,
 	>,
+	pending_reorgs: &mut HashMap<Block::Hash, Arc<ReorgInfo<Block::Hash>>>,
 ) -> Result<bool, String>
 where
 	C: ProvideRuntimeApi<Block>,
@@ -265,12 +288,54 @@ where
 				strategy,
 				sync_oracle.clone(),
 				pubsub_notification_sinks.clone(),
+				pending_reorgs,
 			)?;
 	}
 
 	Ok(synced_any)
 }
 
+/// Index the next bloom-bits section once all of its blocks are finalized.
+///
+/// Returns `true` if a following section is ready to be indexed as well.
+pub fn index_bloom_bits<Block: BlockT, C>(
+	client: &C,
+	storage_override: &dyn StorageOverride<Block>,
+	frontier_backend: &fc_db::kv::Backend<Block, C>,
+) -> Result<bool, String>
+where
+	C: HeaderBackend<Block>,
+{
+	let finalized_number: u64 = client.info().finalized_number.unique_saturated_into();
+	let is_ready = |section: u64| (section + 1) * fc_db::kv::SECTION_SIZE <= finalized_number + 1;
+
+	let section = frontier_backend.bloom_bits().indexed_sections()?;
+	if !is_ready(section) {
+		return Ok(false);
+	}
+
+	let section_start = section * fc_db::kv::SECTION_SIZE;
+	let mut blooms = Vec::with_capacity(fc_db::kv::SECTION_SIZE as usize);
+	for number in section_start..section_start + fc_db::kv::SECTION_SIZE {
+		let hash = client
+			.hash(number.unique_saturated_into())
+			.map_err(|e| format!("{:?}", e))?
+			.ok_or_else(|| format!("Block #{} not found", number))?;
+		blooms.push(
+			storage_override
+				.current_block(hash)
+				.map(|block| block.header.logs_bloom)
+				.unwrap_or_default(),
+		);
+	}
+	frontier_backend
+		.bloom_bits()
+		.write_section(section, &blooms)?;
+	log::debug!(target: "mapping-sync", "Indexed bloom bits section {}", section);
+
+	Ok(is_ready(section + 1))
+}
+
 pub fn fetch_header<Block: BlockT, C, BE>(
 	substrate_backend: &BE,
 	frontier_backend: &fc_db::kv::Backend<Block, C>,
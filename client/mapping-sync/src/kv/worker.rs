@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{pin::Pin, sync::Arc, time::Duration};
+use std::{collections::HashMap, pin::Pin, sync::Arc, time::Duration};
 
 use futures::{
 	prelude::*,
@@ -32,12 +32,13 @@ use sc_client_api::{
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::SyncOracle;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
 // Frontier
+use fc_api::Backend as _;
 use fc_storage::StorageOverride;
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::SyncStrategy;
+use crate::{metrics::MappingSyncMetrics, ReorgInfo, SyncStrategy};
 
 pub struct MappingSyncWorker<Block: BlockT, C, BE> {
 	import_notifications: ImportNotifications<Block>,
@@ -57,6 +58,9 @@ pub struct MappingSyncWorker<Block: BlockT, C, BE> {
 	sync_oracle: Arc<dyn SyncOracle + Send + Sync + 'static>,
 	pubsub_notification_sinks:
 		Arc<crate::EthereumBlockNotificationSinks<crate::EthereumBlockNotification<Block>>>,
+	/// Re-orgs reported by import notifications, until their new best block is synced.
+	pending_reorgs: HashMap<Block::Hash, Arc<ReorgInfo<Block::Hash>>>,
+	metrics: Option<MappingSyncMetrics>,
 }
 
 impl<Block: BlockT, C, BE> Unpin for MappingSyncWorker<Block, C, BE> {}
@@ -76,6 +80,7 @@ impl<Block: BlockT, C, BE> MappingSyncWorker<Block, C, BE> {
 		pubsub_notification_sinks: Arc<
 			crate::EthereumBlockNotificationSinks<crate::EthereumBlockNotification<Block>>,
 		>,
+		prometheus_registry: Option<prometheus_endpoint::Registry>,
 	) -> Self {
 		Self {
 			import_notifications,
@@ -94,6 +99,8 @@ impl<Block: BlockT, C, BE> MappingSyncWorker<Block, C, BE> {
 
 			sync_oracle,
 			pubsub_notification_sinks,
+			pending_reorgs: HashMap::new(),
+			metrics: MappingSyncMetrics::new(prometheus_registry.as_ref()),
 		}
 	}
 }
@@ -114,8 +121,17 @@ where
 		loop {
 			match Stream::poll_next(Pin::new(&mut self.import_notifications), cx) {
 				Poll::Pending => break,
-				Poll::Ready(Some(_)) => {
+				Poll::Ready(Some(notification)) => {
 					fire = true;
+					// Remember re-orgs so that they are reported along with their new best block.
+					if notification.is_new_best && !self.sync_oracle.is_major_syncing() {
+						if let Some(tree_route) = notification.tree_route {
+							self.pending_reorgs.insert(
+								notification.hash,
+								Arc::new(ReorgInfo::from_tree_route(&tree_route)),
+							);
+						}
+					}
 				}
 				Poll::Ready(None) => return Poll::Ready(None),
 			}
@@ -138,7 +154,8 @@ where
 		if fire {
 			self.inner_delay = None;
 
-			match crate::kv::sync_blocks(
+			let mut pending_reorgs = std::mem::take(&mut self.pending_reorgs);
+			let result = crate::kv::sync_blocks(
 				self.client.as_ref(),
 				self.substrate_backend.as_ref(),
 				self.storage_override.clone(),
@@ -148,9 +165,44 @@ where
 				self.strategy,
 				self.sync_oracle.clone(),
 				self.pubsub_notification_sinks.clone(),
-			) {
+				&mut pending_reorgs,
+			);
+			self.pending_reorgs = pending_reorgs;
+
+			// Keep the bloom-bits log index up to date with finality.
+			let index_next = if self.frontier_backend.is_indexed() {
+				crate::kv::index_bloom_bits(
+					self.client.as_ref(),
+					self.storage_override.as_ref(),
+					self.frontier_backend.as_ref(),
+				)
+				.unwrap_or_else(|e| {
+					debug!(target: "mapping-sync", "Bloom bits indexing failed with error {:?}.", e);
+					false
+				})
+			} else {
+				false
+			};
+
+			if let Some(metrics) = &self.metrics {
+				match &result {
+					Ok(_) => {
+						let info = self.client.info();
+						let best_number = info.best_number.unique_saturated_into();
+						let is_synced = self
+							.frontier_backend
+							.mapping()
+							.is_synced(&info.best_hash)
+							.unwrap_or(false);
+						metrics.report_best(best_number, is_synced.then_some(best_number));
+					}
+					Err(_) => metrics.report_error(),
+				}
+			}
+
+			match result {
 				Ok(have_next) => {
-					self.have_next = have_next;
+					self.have_next = have_next || index_next;
 					Poll::Ready(Some(()))
 				}
 				Err(e) => {
@@ -286,6 +338,7 @@ mod tests {
 				SyncStrategy::Normal,
 				Arc::new(test_sync_oracle),
 				pubsub_notification_sinks_inner,
+				None,
 			)
 			.for_each(|()| future::ready(()))
 			.await
@@ -428,6 +481,7 @@ mod tests {
 				SyncStrategy::Normal,
 				Arc::new(test_sync_oracle),
 				pubsub_notification_sinks_inner,
+				None,
 			)
 			.for_each(|()| future::ready(()))
 			.await
@@ -467,4 +521,114 @@ mod tests {
 			assert_eq!(sinks.len(), 0);
 		}
 	}
+
+	#[tokio::test]
+	async fn reorg_notification_reports_retracted_and_enacted_blocks() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		let test_sync_oracle = TestSyncOracleNotSyncing {};
+		// Backend
+		let backend = builder.backend();
+		// Client
+		let (client, _) =
+			builder.build_with_native_executor::<argochain_runtime::RuntimeApi, _>(None);
+		let mut client = Arc::new(client);
+		// Overrides
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
+
+		let frontier_backend = Arc::new(
+			fc_db::kv::Backend::<OpaqueBlock, _>::new(
+				client.clone(),
+				&fc_db::kv::DatabaseSettings {
+					source: sc_client_db::DatabaseSource::RocksDb {
+						path: tmp.path().to_path_buf(),
+						cache_size: 0,
+					},
+				},
+			)
+			.expect("frontier backend"),
+		);
+
+		let notification_stream = client.clone().import_notification_stream();
+		let client_inner = client.clone();
+
+		let pubsub_notification_sinks: EthereumBlockNotificationSinks<
+			EthereumBlockNotification<OpaqueBlock>,
+		> = Default::default();
+		let pubsub_notification_sinks = Arc::new(pubsub_notification_sinks);
+
+		let pubsub_notification_sinks_inner = pubsub_notification_sinks.clone();
+
+		tokio::task::spawn(async move {
+			MappingSyncWorker::new(
+				notification_stream,
+				Duration::new(6, 0),
+				client_inner,
+				backend,
+				storage_override.clone(),
+				frontier_backend,
+				3,
+				0,
+				SyncStrategy::Normal,
+				Arc::new(test_sync_oracle),
+				pubsub_notification_sinks_inner,
+				None,
+			)
+			.for_each(|()| future::ready(()))
+			.await
+		});
+
+		let (inner_sink, mut block_notification_stream) =
+			sc_utils::mpsc::tracing_unbounded("pubsub_notification_stream", 100_000);
+		pubsub_notification_sinks.lock().push(inner_sink);
+
+		let genesis = client.chain_info().best_hash;
+		let builder_client = client.clone();
+		let build_block = |parent_hash, parent_number| {
+			BlockBuilderBuilder::new(&*builder_client)
+				.on_parent_block(parent_hash)
+				.with_parent_block_number(parent_number)
+				.with_inherent_digests(ethereum_digest())
+				.build()
+				.unwrap()
+				.build()
+				.unwrap()
+				.block
+		};
+
+		// Import a first block, then a competing fork of the same height.
+		let a1 = build_block(genesis, 0);
+		let a1_hash = a1.header.hash();
+		let _res = client.import(BlockOrigin::Own, a1).await;
+		let notification = block_notification_stream.next().await.expect("a message");
+		assert_eq!(notification.hash, a1_hash);
+		assert!(notification.is_new_best);
+		assert_eq!(notification.reorg_info, None);
+
+		let b1 = build_block(genesis, 0);
+		let b1_hash = b1.header.hash();
+		let _res = client.import(BlockOrigin::Own, b1).await;
+		let notification = block_notification_stream.next().await.expect("a message");
+		assert_eq!(notification.hash, b1_hash);
+		assert!(!notification.is_new_best);
+
+		// Extending the fork makes it the canonical chain.
+		let b2 = build_block(b1_hash, 1);
+		let b2_hash = b2.header.hash();
+		let _res = client.import(BlockOrigin::Own, b2).await;
+		let notification = block_notification_stream.next().await.expect("a message");
+		assert_eq!(notification.hash, b2_hash);
+		assert!(notification.is_new_best);
+		assert_eq!(
+			notification.reorg_info.as_deref(),
+			Some(&ReorgInfo {
+				common_ancestor: genesis,
+				retracted: vec![a1_hash],
+				enacted: vec![b1_hash],
+			})
+		);
+	}
 }
@@ -20,9 +20,14 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod kv;
+mod metrics;
 #[cfg(feature = "sql")]
 pub mod sql;
 
+use std::sync::Arc;
+
+// Substrate
+use sp_blockchain::TreeRoute;
 use sp_runtime::traits::Block as BlockT;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -34,8 +39,43 @@ pub enum SyncStrategy {
 pub type EthereumBlockNotificationSinks<T> =
 	parking_lot::Mutex<Vec<sc_utils::mpsc::TracingUnboundedSender<T>>>;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EthereumBlockNotification<Block: BlockT> {
 	pub is_new_best: bool,
 	pub hash: Block::Hash,
+	/// Re-org that made this block the new best block, if any.
+	pub reorg_info: Option<Arc<ReorgInfo<Block::Hash>>>,
+}
+
+/// Blocks moved in and out of the canonical chain by a re-org.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReorgInfo<Hash> {
+	/// Last block shared by the old and the new canonical chains.
+	pub common_ancestor: Hash,
+	/// Blocks removed from the canonical chain, from the old best block down to the common
+	/// ancestor.
+	pub retracted: Vec<Hash>,
+	/// Blocks added to the canonical chain, from the common ancestor up to the parent of the new
+	/// best block.
+	pub enacted: Vec<Hash>,
+}
+
+impl<Hash: Copy> ReorgInfo<Hash> {
+	/// Re-org described by the tree route from the old best block to the parent of the new one,
+	/// as computed on block import.
+	pub fn from_tree_route<Block: BlockT<Hash = Hash>>(tree_route: &TreeRoute<Block>) -> Self {
+		Self {
+			common_ancestor: tree_route.common_block().hash,
+			retracted: tree_route
+				.retracted()
+				.iter()
+				.map(|hash_and_number| hash_and_number.hash)
+				.collect(),
+			enacted: tree_route
+				.enacted()
+				.iter()
+				.map(|hash_and_number| hash_and_number.hash)
+				.collect(),
+		}
+	}
 }
@@ -0,0 +1,82 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, U64};
+
+/// Prometheus metrics of the mapping-sync workers.
+pub(crate) struct MappingSyncMetrics {
+	/// Number of the highest best block found synced in the mapping database.
+	best_synced_block: Gauge<U64>,
+	/// Number of blocks the mapping database trails the best block by.
+	lag_blocks: Gauge<U64>,
+	/// Number of failed sync attempts.
+	errors: Counter<U64>,
+}
+
+impl MappingSyncMetrics {
+	pub(crate) fn new(prometheus_registry: Option<&Registry>) -> Option<Self> {
+		let registry = prometheus_registry?;
+		match Self::register(registry) {
+			Ok(metrics) => Some(metrics),
+			Err(e) => {
+				log::error!(target: "mapping-sync", "Failed to register metrics: {:?}", e);
+				None
+			}
+		}
+	}
+
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			best_synced_block: register(
+				Gauge::new(
+					"frontier_mapping_sync_best_synced_block",
+					"Number of the highest best block synced in the mapping database.",
+				)?,
+				registry,
+			)?,
+			lag_blocks: register(
+				Gauge::new(
+					"frontier_mapping_sync_lag_blocks",
+					"Number of blocks the mapping database trails the best block by.",
+				)?,
+				registry,
+			)?,
+			errors: register(
+				Counter::new(
+					"frontier_mapping_sync_errors_total",
+					"Number of failed mapping sync attempts.",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Records the current best block, along with the number of the latest best block known to
+	/// be synced, if any.
+	pub(crate) fn report_best(&self, best_number: u64, synced_number: Option<u64>) {
+		if let Some(synced_number) = synced_number {
+			self.best_synced_block.set(synced_number);
+		}
+		self.lag_blocks
+			.set(best_number.saturating_sub(self.best_synced_block.get()));
+	}
+
+	pub(crate) fn report_error(&self) {
+		self.errors.inc();
+	}
+}
@@ -29,7 +29,10 @@ use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto
 // Frontier
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::{EthereumBlockNotification, EthereumBlockNotificationSinks, SyncStrategy};
+use crate::{
+	metrics::MappingSyncMetrics, EthereumBlockNotification, EthereumBlockNotificationSinks,
+	ReorgInfo, SyncStrategy,
+};
 
 /// Defines the commands for the sync worker.
 #[derive(Debug)]
@@ -39,7 +42,11 @@ pub enum WorkerCommand {
 	/// Index leaves.
 	IndexLeaves(Vec<H256>),
 	/// Index the best block known so far via import notifications.
-	IndexBestBlock(H256),
+	IndexBestBlock {
+		block_hash: H256,
+		/// Re-org that made this block the new best block, if any.
+		reorg_info: Option<Arc<ReorgInfo<H256>>>,
+	},
 	/// Canonicalize the enacted and retracted blocks reported via import notifications.
 	Canonicalize {
 		common: H256,
@@ -80,7 +87,9 @@ where
 		pubsub_notification_sinks: Arc<
 			EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>,
 		>,
+		prometheus_registry: Option<prometheus_endpoint::Registry>,
 	) -> tokio::sync::mpsc::Sender<WorkerCommand> {
+		let metrics = MappingSyncMetrics::new(prometheus_registry.as_ref());
 		let (tx, mut rx) = tokio::sync::mpsc::channel(100);
 		tokio::task::spawn(async move {
 			while let Some(cmd) = rx.recv().await {
@@ -122,7 +131,10 @@ where
 							.await;
 						}
 					}
-					WorkerCommand::IndexBestBlock(block_hash) => {
+					WorkerCommand::IndexBestBlock {
+						block_hash,
+						reorg_info,
+					} => {
 						index_canonical_block_and_ancestors(
 							client.clone(),
 							substrate_backend.clone(),
@@ -130,11 +142,28 @@ where
 							block_hash,
 						)
 						.await;
+						if let Some(metrics) = &metrics {
+							let synced_number =
+								if indexer_backend.is_block_indexed(block_hash).await {
+									client
+										.number(block_hash)
+										.ok()
+										.flatten()
+										.map(|number| number.unique_saturated_into())
+								} else {
+									None
+								};
+							metrics.report_best(
+								client.info().best_number.unique_saturated_into(),
+								synced_number,
+							);
+						}
 						let sinks = &mut pubsub_notification_sinks.lock();
 						for sink in sinks.iter() {
 							let _ = sink.unbounded_send(EthereumBlockNotification {
 								is_new_best: true,
 								hash: block_hash,
+								reorg_info: reorg_info.clone(),
 							});
 						}
 					}
@@ -182,12 +211,14 @@ where
 		pubsub_notification_sinks: Arc<
 			EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>,
 		>,
+		prometheus_registry: Option<prometheus_endpoint::Registry>,
 	) {
 		let tx = Self::spawn_worker(
 			client.clone(),
 			substrate_backend.clone(),
 			indexer_backend.clone(),
 			pubsub_notification_sinks.clone(),
+			prometheus_registry,
 		)
 		.await;
 
@@ -229,32 +260,26 @@ where
 						notification.is_new_best,
 					);
 					if notification.is_new_best {
-						if let Some(tree_route) = notification.tree_route {
+						let reorg_info = notification.tree_route.map(|tree_route| {
 							log::debug!(
 								target: "frontier-sql",
 								"🔀  Re-org happened at new best {}, proceeding to canonicalize db",
 								notification.hash
 							);
-							let retracted = tree_route
-								.retracted()
-								.iter()
-								.map(|hash_and_number| hash_and_number.hash)
-								.collect::<Vec<_>>();
-							let enacted = tree_route
-								.enacted()
-								.iter()
-								.map(|hash_and_number| hash_and_number.hash)
-								.collect::<Vec<_>>();
-
-							let common = tree_route.common_block().hash;
+							Arc::new(ReorgInfo::from_tree_route(&tree_route))
+						});
+						if let Some(reorg_info) = &reorg_info {
 							tx.send(WorkerCommand::Canonicalize {
-								common,
-								enacted,
-								retracted,
+								common: reorg_info.common_ancestor,
+								enacted: reorg_info.enacted.clone(),
+								retracted: reorg_info.retracted.clone(),
 							}).await.ok();
 						}
 
-						tx.send(WorkerCommand::IndexBestBlock(notification.hash)).await.ok();
+						tx.send(WorkerCommand::IndexBestBlock {
+							block_hash: notification.hash,
+							reorg_info,
+						}).await.ok();
 					}
 				}
 			}
@@ -678,6 +703,7 @@ mod test {
 				SyncStrategy::Parachain,
 				Arc::new(test_sync_oracle),
 				pubsub_notification_sinks_inner,
+				None,
 			)
 			.await
 		});
@@ -800,6 +826,7 @@ mod test {
 				SyncStrategy::Parachain,
 				Arc::new(test_sync_oracle),
 				pubsub_notification_sinks_inner,
+				None,
 			)
 			.await
 		});
@@ -1003,6 +1030,7 @@ mod test {
 				SyncStrategy::Parachain,
 				Arc::new(test_sync_oracle),
 				pubsub_notification_sinks_inner,
+				None,
 			)
 			.await
 		});
@@ -1206,6 +1234,7 @@ mod test {
 				SyncStrategy::Parachain,
 				Arc::new(test_sync_oracle),
 				pubsub_notification_sinks_inner,
+				None,
 			)
 			.await
 		});
@@ -1310,6 +1339,7 @@ mod test {
 				SyncStrategy::Normal,
 				Arc::new(sync_oracle),
 				pubsub_notification_sinks.clone(),
+				None,
 			)
 			.await
 		});
@@ -1411,6 +1441,7 @@ mod test {
 				SyncStrategy::Normal,
 				Arc::new(sync_oracle),
 				pubsub_notification_sinks.clone(),
+				None,
 			)
 			.await
 		});
@@ -1526,6 +1557,7 @@ mod test {
 				SyncStrategy::Parachain,
 				Arc::new(sync_oracle),
 				pubsub_notification_sinks.clone(),
+				None,
 			)
 			.await
 		});
@@ -1627,6 +1659,7 @@ mod test {
 				SyncStrategy::Parachain,
 				Arc::new(sync_oracle),
 				pubsub_notification_sinks.clone(),
+				None,
 			)
 			.await
 		});
@@ -1742,6 +1775,7 @@ mod test {
 				SyncStrategy::Normal,
 				Arc::new(sync_oracle),
 				pubsub_notification_sinks.clone(),
+				None,
 			)
 			.await
 		});
@@ -1843,6 +1877,7 @@ mod test {
 				SyncStrategy::Parachain,
 				Arc::new(sync_oracle),
 				pubsub_notification_sinks.clone(),
+				None,
 			)
 			.await
 		});
@@ -181,6 +181,16 @@ pub trait EthApi {
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<Bytes>;
 
+	/// Returns the account and the given storage slots of an address, along with the Substrate
+	/// read-proofs of their state entries.
+	#[method(name = "eth_getProof")]
+	async fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<EthAccount>;
+
 	// ########################################################################
 	// Execute
 	// ########################################################################
@@ -202,6 +212,22 @@ pub trait EthApi {
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<U256>;
 
+	/// Generate an EIP-2930 access list for the given call, along with the gas it uses with it.
+	#[method(name = "eth_createAccessList")]
+	async fn create_access_list(
+		&self,
+		request: TransactionRequest,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<AccessListResult>;
+
+	/// Simulate a sequence of blocks of calls, with block and state overrides.
+	#[method(name = "eth_simulateV1")]
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>>;
+
 	// ########################################################################
 	// Fee
 	// ########################################################################
@@ -266,15 +292,15 @@ pub trait EthApi {
 #[rpc(server)]
 pub trait EthFilterApi {
 	/// Returns id of new filter.
-	#[method(name = "eth_newFilter")]
+	#[method(name = "eth_newFilter", with_extensions)]
 	fn new_filter(&self, filter: Filter) -> RpcResult<U256>;
 
 	/// Returns id of new block filter.
-	#[method(name = "eth_newBlockFilter")]
+	#[method(name = "eth_newBlockFilter", with_extensions)]
 	fn new_block_filter(&self) -> RpcResult<U256>;
 
 	/// Returns id of new block filter.
-	#[method(name = "eth_newPendingTransactionFilter")]
+	#[method(name = "eth_newPendingTransactionFilter", with_extensions)]
 	fn new_pending_transaction_filter(&self) -> RpcResult<U256>;
 
 	/// Returns filter changes since last poll.
@@ -34,20 +34,31 @@ pub struct AccountInfo {
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageProof {
+	/// Storage slot.
 	pub key: U256,
+	/// Storage value.
 	pub value: U256,
+	/// Substrate trie nodes proving the `EVM::AccountStorages` entry of the slot.
 	pub proof: Vec<Bytes>,
 }
 
-/// Account information.
+/// Account information, as returned by `eth_getProof`.
+///
+/// The proofs are Substrate read-proofs rather than Ethereum Merkle-Patricia proofs, see
+/// `fp-state-proof` for their verification.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EthAccount {
 	pub address: H160,
 	pub balance: U256,
 	pub nonce: U256,
+	/// Keccak-256 hash of the account code.
 	pub code_hash: H256,
+	/// Substrate state root all proofs are checked against.
 	pub storage_hash: H256,
+	/// Substrate trie nodes proving the `EVM::AccountCodes` entry of the account and, when the
+	/// node knows the address mapping of its runtime, the `System::Account` entry of the mapped
+	/// account.
 	pub account_proof: Vec<Bytes>,
 	pub storage_proof: Vec<StorageProof>,
 }
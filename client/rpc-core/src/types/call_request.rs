@@ -18,8 +18,9 @@
 
 use std::collections::BTreeMap;
 
+use ethereum::AccessListItem;
 use ethereum_types::{H256, U256};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::types::Bytes;
 
@@ -41,3 +42,16 @@ pub struct CallStateOverride {
 	/// executing the call.
 	pub state_diff: Option<BTreeMap<H256, H256>>,
 }
+
+/// Result of `eth_createAccessList`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+	/// Accounts and storage slots accessed by the call.
+	pub access_list: Vec<AccessListItem>,
+	/// Gas used by the call when sent with `access_list`.
+	pub gas_used: U256,
+	/// Execution error, if the call failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
@@ -19,6 +19,7 @@
 use std::{
 	collections::{BTreeMap, HashSet},
 	sync::{Arc, Mutex},
+	time::Instant,
 };
 
 use ethereum_types::{Bloom, BloomInput, H160, H256, U256};
@@ -481,6 +482,11 @@ pub struct FilterPoolItem {
 	pub filter_type: FilterType,
 	pub at_block: u64,
 	pub pending_transaction_hashes: HashSet<H256>,
+	/// Connection which installed the filter, counted against its filter quota. Only meaningful
+	/// for WebSocket connections, each HTTP request being given a new one.
+	pub connection_id: Option<usize>,
+	/// When the filter was installed or last polled, its time-to-live starts from there.
+	pub last_activity: Instant,
 }
 
 /// On-memory stored filters created through the `eth_newFilter` RPC.
@@ -28,6 +28,7 @@ mod filter;
 mod index;
 mod log;
 mod receipt;
+mod simulate;
 mod sync;
 mod transaction;
 mod transaction_request;
@@ -47,7 +48,7 @@ pub use self::{
 	block::{Block, BlockTransactions, Header, Rich, RichBlock, RichHeader},
 	block_number::BlockNumberOrHash,
 	bytes::Bytes,
-	call_request::CallStateOverride,
+	call_request::{AccessListResult, CallStateOverride},
 	fee::{FeeHistory, FeeHistoryCache, FeeHistoryCacheItem, FeeHistoryCacheLimit},
 	filter::{
 		Filter, FilterAddress, FilterChanges, FilterPool, FilterPoolItem, FilterType,
@@ -56,6 +57,10 @@ pub use self::{
 	index::Index,
 	log::Log,
 	receipt::Receipt,
+	simulate::{
+		SimulateBlock, SimulateBlockOverrides, SimulateCallError, SimulatePayload, SimulatedBlock,
+		SimulatedCall,
+	},
 	sync::{
 		ChainStatus, EthProtocolInfo, PeerCount, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
 		Peers, PipProtocolInfo, SyncInfo, SyncStatus, TransactionStats,
@@ -23,13 +23,16 @@ use std::collections::BTreeMap;
 use ethereum::{
 	BlockV2 as EthereumBlock, ReceiptV3 as EthereumReceipt, TransactionV2 as EthereumTransaction,
 };
-use ethereum_types::{H256, U256};
+use ethereum_types::{H160, H256, U256};
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{from_value, Value};
 // Substrate
 use sp_crypto_hashing::keccak_256;
 
-use crate::types::{Bytes, Filter, FilteredParams, Header, Log, Rich, RichHeader};
+use crate::types::{
+	BuildFrom, Bytes, Filter, FilterAddress, FilteredParams, Header, Log, Rich, RichHeader,
+	Transaction, VariadicValue,
+};
 
 /// Subscription kind.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
@@ -38,6 +41,8 @@ use crate::types::{Bytes, Filter, FilteredParams, Header, Log, Rich, RichHeader}
 pub enum Kind {
 	/// New block headers subscription.
 	NewHeads,
+	/// Finalized block headers subscription, following GRANDPA finality.
+	FinalizedHeads,
 	/// Logs subscription.
 	Logs,
 	/// New Pending Transactions subscription.
@@ -54,6 +59,10 @@ pub enum Params {
 	None,
 	/// Log parameters.
 	Logs(Filter),
+	/// Whether `newPendingTransactions` streams full transactions instead of hashes.
+	Bool(bool),
+	/// Filter of `newPendingTransactions`.
+	PendingTransactions(PendingTransactionFilter),
 }
 
 impl<'a> Deserialize<'a> for Params {
@@ -67,9 +76,58 @@ impl<'a> Deserialize<'a> for Params {
 			return Ok(Params::None);
 		}
 
-		from_value(v)
-			.map(Params::Logs)
-			.map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e)))
+		if let Value::Bool(full_transactions) = v {
+			return Ok(Params::Bool(full_transactions));
+		}
+
+		from_value(v.clone()).map(Params::Logs).or_else(|e| {
+			from_value(v)
+				.map(Params::PendingTransactions)
+				.map_err(|_| D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e)))
+		})
+	}
+}
+
+/// Filter of a `newPendingTransactions` subscription.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTransactionFilter {
+	/// Only transactions sent by these addresses.
+	pub from_address: Option<FilterAddress>,
+	/// Only transactions sent to these addresses.
+	pub to_address: Option<FilterAddress>,
+	/// Stream transaction hashes instead of full transactions.
+	#[serde(default)]
+	pub hashes_only: bool,
+}
+
+impl PendingTransactionFilter {
+	/// Filter streaming the hashes of all pending transactions.
+	pub fn hashes() -> Self {
+		Self {
+			hashes_only: true,
+			..Default::default()
+		}
+	}
+
+	/// Whether the sender of transactions must be recovered to apply the filter.
+	pub fn requires_sender(&self) -> bool {
+		!self.hashes_only || self.from_address.is_some()
+	}
+
+	/// Whether a transaction from `from` to `to` matches the filter.
+	pub fn matches(&self, from: &H160, to: Option<&H160>) -> bool {
+		fn contains(addresses: &Option<FilterAddress>, address: Option<&H160>) -> bool {
+			match (addresses, address) {
+				(None | Some(VariadicValue::Null), _) => true,
+				(Some(VariadicValue::Single(a)), Some(address)) => a == address,
+				(Some(VariadicValue::Multiple(a)), Some(address)) => a.contains(address),
+				(_, None) => false,
+			}
+		}
+
+		contains(&self.from_address, Some(from)) && contains(&self.to_address, to)
 	}
 }
 
@@ -82,6 +140,8 @@ pub enum PubSubResult {
 	Log(Box<Log>),
 	/// Transaction hash
 	TransactionHash(H256),
+	/// Full transaction
+	Transaction(Box<Transaction>),
 	/// SyncStatus
 	SyncingStatus(PubSubSyncing),
 }
@@ -112,10 +172,12 @@ impl PubSubResult {
 		}))
 	}
 
+	/// Logs of a block matching `params`. `removed` marks logs of a block retracted by a re-org.
 	pub fn logs(
 		block: EthereumBlock,
 		receipts: Vec<EthereumReceipt>,
 		params: &FilteredParams,
+		removed: bool,
 	) -> impl Iterator<Item = Self> {
 		let block_number = block.header.number;
 		let block_hash = block.header.hash();
@@ -148,7 +210,7 @@ impl PubSubResult {
 						transaction_index: Some(U256::from(receipt_index)),
 						log_index: Some(U256::from(log_index)),
 						transaction_log_index: Some(U256::from(transaction_log_index)),
-						removed: false,
+						removed,
 					});
 				}
 				transaction_log_index += 1;
@@ -161,6 +223,10 @@ impl PubSubResult {
 	pub fn transaction_hash(tx: &EthereumTransaction) -> Self {
 		Self::TransactionHash(tx.hash())
 	}
+
+	pub fn transaction(from: H160, tx: &EthereumTransaction) -> Self {
+		Self::Transaction(Box::new(Transaction::build_from(from, tx)))
+	}
 }
 
 impl Serialize for PubSubResult {
@@ -172,6 +238,7 @@ impl Serialize for PubSubResult {
 			Self::Header(ref header) => header.serialize(serializer),
 			Self::Log(ref log) => log.serialize(serializer),
 			Self::TransactionHash(ref hash) => hash.serialize(serializer),
+			Self::Transaction(ref transaction) => transaction.serialize(serializer),
 			Self::SyncingStatus(ref sync) => sync.serialize(serializer),
 		}
 	}
@@ -193,3 +260,65 @@ pub struct SyncingStatus {
 	#[serde(default = "Default::default", skip_serializing_if = "Option::is_none")]
 	pub highest_block: Option<u64>,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	#[test]
+	fn deserialize_params() {
+		let address = H160::from_str("1000000000000000000000000000000000000000").unwrap();
+
+		let params: Params = serde_json::from_str("null").unwrap();
+		assert_eq!(params, Params::None);
+
+		let params: Params = serde_json::from_str("true").unwrap();
+		assert_eq!(params, Params::Bool(true));
+
+		let params: Params =
+			serde_json::from_str(r#"{"address":"0x1000000000000000000000000000000000000000"}"#)
+				.unwrap();
+		assert!(matches!(params, Params::Logs(_)));
+
+		let params: Params = serde_json::from_str(
+			r#"{"toAddress":["0x1000000000000000000000000000000000000000"],"hashesOnly":true}"#,
+		)
+		.unwrap();
+		assert_eq!(
+			params,
+			Params::PendingTransactions(PendingTransactionFilter {
+				from_address: None,
+				to_address: Some(VariadicValue::Multiple(vec![address])),
+				hashes_only: true,
+			})
+		);
+
+		assert!(serde_json::from_str::<Params>(r#"{"unknown":1}"#).is_err());
+	}
+
+	#[test]
+	fn pending_transaction_filter_matches() {
+		let alice = H160::repeat_byte(0x01);
+		let bob = H160::repeat_byte(0x02);
+
+		let filter = PendingTransactionFilter::default();
+		assert!(filter.matches(&alice, Some(&bob)));
+		assert!(filter.matches(&alice, None));
+
+		let filter = PendingTransactionFilter {
+			from_address: Some(VariadicValue::Single(alice)),
+			..Default::default()
+		};
+		assert!(filter.matches(&alice, Some(&bob)));
+		assert!(!filter.matches(&bob, Some(&alice)));
+
+		let filter = PendingTransactionFilter {
+			to_address: Some(VariadicValue::Multiple(vec![alice, bob])),
+			..Default::default()
+		};
+		assert!(filter.matches(&alice, Some(&bob)));
+		// Contract creations have no recipient.
+		assert!(!filter.matches(&alice, None));
+	}
+}
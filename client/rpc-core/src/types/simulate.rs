@@ -0,0 +1,108 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use ethereum_types::{H160, U256, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Bytes, CallStateOverride, Log, TransactionRequest};
+
+/// `eth_simulateV1` request.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+	/// Blocks to simulate, in order.
+	pub block_state_calls: Vec<SimulateBlock>,
+	/// Emit a synthetic `Transfer` log for every native value transfer.
+	#[serde(default)]
+	pub trace_transfers: bool,
+	/// Check calls as transactions would be, signatures excepted.
+	#[serde(default)]
+	pub validation: bool,
+}
+
+/// A simulated block.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+	/// Block environment overrides.
+	pub block_overrides: Option<SimulateBlockOverrides>,
+	/// State overrides applied before the block's calls.
+	pub state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+	/// Calls executed in the block, in order.
+	#[serde(default)]
+	pub calls: Vec<TransactionRequest>,
+}
+
+/// Block environment overrides of a simulated block.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlockOverrides {
+	/// Block number.
+	pub number: Option<U256>,
+	/// Block timestamp, in seconds.
+	pub time: Option<U64>,
+	/// Base fee per gas.
+	pub base_fee_per_gas: Option<U256>,
+}
+
+/// `eth_simulateV1` result for a simulated block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+	/// Block number.
+	pub number: U256,
+	/// Block timestamp, in seconds.
+	pub timestamp: U256,
+	/// Block gas limit.
+	pub gas_limit: U256,
+	/// Gas used by all the calls of the block.
+	pub gas_used: U256,
+	/// Base fee per gas.
+	pub base_fee_per_gas: U256,
+	/// Results of the block's calls.
+	pub calls: Vec<SimulatedCall>,
+}
+
+/// `eth_simulateV1` result for a simulated call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+	/// `1` on success, `0` on failure.
+	pub status: U64,
+	/// Return data, or the deployed code for a contract creation.
+	pub return_data: Bytes,
+	/// Gas used by the call.
+	pub gas_used: U256,
+	/// Logs emitted by the call.
+	pub logs: Vec<Log>,
+	/// Failure reason, if the call failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<SimulateCallError>,
+}
+
+/// Failure reason of a simulated call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct SimulateCallError {
+	/// Error code.
+	pub code: i32,
+	/// Error message.
+	pub message: String,
+}
@@ -22,6 +22,7 @@ use std::{
 	collections::{BTreeMap, HashMap},
 	marker::PhantomData,
 	sync::{Arc, Mutex},
+	time::Duration,
 };
 // use parity_scale_codec as codec;
 use ethereum::BlockV2 as EthereumBlock;
@@ -36,13 +37,14 @@ use sc_client_api::{
 use sc_service::SpawnTaskHandle;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
 // Frontier
 use fc_rpc_core::types::*;
 use fc_storage::StorageOverride;
 use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
 
 use self::lru_cache::LRUCacheByteLimited;
+use crate::metrics::EthMetrics;
 
 type WaitList<Hash, T> = HashMap<Hash, Vec<oneshot::Sender<Option<T>>>>;
 
@@ -261,17 +263,18 @@ where
 	pub async fn filter_pool_task(
 		client: Arc<C>,
 		filter_pool: Arc<Mutex<BTreeMap<U256, FilterPoolItem>>>,
-		retain_threshold: u64,
+		time_to_live: Duration,
+		metrics: Option<Arc<EthMetrics>>,
 	) {
 		let mut notification_st = client.import_notification_stream();
 
-		while let Some(notification) = notification_st.next().await {
+		// Expired filters are swept on block import, nothing can change them in between.
+		while notification_st.next().await.is_some() {
 			if let Ok(filter_pool) = &mut filter_pool.lock() {
-				let imported_number: u64 = UniqueSaturatedInto::<u64>::unique_saturated_into(
-					*notification.header.number(),
-				);
-
-				filter_pool.retain(|_, v| v.at_block + retain_threshold > imported_number);
+				sweep_expired_filters(filter_pool, time_to_live);
+				if let Some(metrics) = &metrics {
+					metrics.set_filter_pool_size(filter_pool.len());
+				}
 			}
 		}
 	}
@@ -281,6 +284,7 @@ where
 		storage_override: Arc<dyn StorageOverride<B>>,
 		fee_history_cache: FeeHistoryCache,
 		block_limit: u64,
+		metrics: Option<Arc<EthMetrics>>,
 	) {
 		struct TransactionHelper {
 			gas_used: u64,
@@ -393,6 +397,9 @@ where
 					let key = first_out - i;
 					fee_history_cache.remove(&key);
 				}
+				if let Some(metrics) = &metrics {
+					metrics.set_fee_history_cache_size(fee_history_cache.len());
+				}
 			}
 		};
 
@@ -425,3 +432,42 @@ where
 		}
 	}
 }
+
+/// Remove the filters that have not been installed or polled within `time_to_live`.
+fn sweep_expired_filters(filter_pool: &mut BTreeMap<U256, FilterPoolItem>, time_to_live: Duration) {
+	filter_pool.retain(|_, v| v.last_activity.elapsed() < time_to_live);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{collections::HashSet, time::Instant};
+
+	fn filter(last_activity: Instant) -> FilterPoolItem {
+		FilterPoolItem {
+			last_poll: BlockNumberOrHash::Num(0),
+			filter_type: FilterType::Block,
+			at_block: 0,
+			pending_transaction_hashes: HashSet::new(),
+			connection_id: None,
+			last_activity,
+		}
+	}
+
+	#[test]
+	fn sweep_removes_filters_idle_for_their_time_to_live() {
+		let time_to_live = Duration::from_secs(60);
+		let now = Instant::now();
+		let mut filter_pool = BTreeMap::new();
+		filter_pool.insert(U256::from(1), filter(now - 2 * time_to_live));
+		filter_pool.insert(U256::from(2), filter(now - time_to_live / 2));
+		filter_pool.insert(U256::from(3), filter(now));
+
+		sweep_expired_filters(&mut filter_pool, time_to_live);
+
+		assert_eq!(
+			filter_pool.keys().copied().collect::<Vec<_>>(),
+			vec![U256::from(2), U256::from(3)]
+		);
+	}
+}
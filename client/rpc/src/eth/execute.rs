@@ -18,14 +18,16 @@
 
 use std::{cell::RefCell, collections::BTreeMap, sync::Arc};
 
-use ethereum_types::{H160, H256, U256};
+use ethereum_types::{H160, H256, U256, U64};
 use evm::{ExitError, ExitReason};
 use jsonrpsee::{core::RpcResult, types::error::CALL_EXECUTION_FAILED_CODE};
 use codec::{Decode, Encode};
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
 use sc_transaction_pool::ChainApi;
-use sp_api::{ApiExt, CallApiAt, CallApiAtParams, CallContext, ProvideRuntimeApi};
+use sp_api::{
+	ApiExt, CallApiAt, CallApiAtParams, CallContext, ProvideRuntimeApi, TransactionOutcome,
+};
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::HeaderBackend;
 use sp_externalities::Extensions;
@@ -39,7 +41,9 @@ use sp_state_machine::OverlayedChanges;
 // Frontier
 use fc_rpc_core::types::*;
 use fp_evm::{ExecutionInfo, ExecutionInfoV2};
-use fp_rpc::{EthereumRuntimeRPCApi, RuntimeStorageOverride};
+use fp_rpc::{
+	EthereumAccessListApi, EthereumRuntimeRPCApi, EthereumSimulateApi, RuntimeStorageOverride,
+};
 use fp_storage::constants::{EVM_ACCOUNT_CODES, EVM_ACCOUNT_STORAGES, PALLET_EVM};
 
 use crate::{
@@ -192,6 +196,7 @@ where
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 					error_on_execution_failure(&info.exit_reason, &info.value)?;
+					self.observe_used_gas("eth_call", info.used_gas);
 					Ok(Bytes(info.value))
 				} else if api_version >= 2 && api_version < 4 {
 					// Post-london
@@ -212,6 +217,7 @@ where
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 					error_on_execution_failure(&info.exit_reason, &info.value)?;
+					self.observe_used_gas("eth_call", info.used_gas);
 					Ok(Bytes(info.value))
 				} else if api_version == 4 || api_version == 5 {
 					// Post-london + access list support
@@ -266,6 +272,7 @@ where
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 						error_on_execution_failure(&info.exit_reason, &info.value)?;
+						self.observe_used_gas("eth_call", info.used_gas);
 						info.value
 					} else if api_version == 5 {
 						let info = self
@@ -285,6 +292,7 @@ where
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 						error_on_execution_failure(&info.exit_reason, &info.value)?;
+						self.observe_used_gas("eth_call", info.used_gas.standard);
 						info.value
 					} else {
 						unreachable!("invalid version");
@@ -313,6 +321,7 @@ where
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 					error_on_execution_failure(&info.exit_reason, &[])?;
+					self.observe_used_gas("eth_call", info.used_gas);
 
 					let code = api
 						.account_code_at(substrate_hash, info.value)
@@ -336,6 +345,7 @@ where
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 					error_on_execution_failure(&info.exit_reason, &[])?;
+					self.observe_used_gas("eth_call", info.used_gas);
 
 					let code = api
 						.account_code_at(substrate_hash, info.value)
@@ -366,6 +376,7 @@ where
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 					error_on_execution_failure(&info.exit_reason, &[])?;
+					self.observe_used_gas("eth_call", info.used_gas);
 
 					let code = api
 						.account_code_at(substrate_hash, info.value)
@@ -396,6 +407,7 @@ where
 						.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 					error_on_execution_failure(&info.exit_reason, &[])?;
+					self.observe_used_gas("eth_call", info.used_gas.standard);
 
 					let code = api
 						.account_code_at(substrate_hash, info.value)
@@ -876,6 +888,344 @@ where
 		}
 	}
 
+	pub async fn create_access_list(
+		&self,
+		request: TransactionRequest,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<AccessListResult>
+	where
+		C::Api: EthereumAccessListApi<B>,
+	{
+		let TransactionRequest {
+			from,
+			to,
+			gas_price,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			gas,
+			value,
+			data,
+			nonce,
+			access_list,
+			..
+		} = request;
+
+		let details = fee_details(gas_price, max_fee_per_gas, max_priority_fee_per_gas)?;
+
+		let (substrate_hash, api) = match frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			number_or_hash,
+		)
+		.await?
+		{
+			Some(id) => {
+				let hash = self.client.expect_block_hash_from_id(&id).map_err(|_| {
+					crate::err(CALL_EXECUTION_FAILED_CODE, "header not found", None)
+				})?;
+				(hash, self.client.runtime_api())
+			}
+			None => {
+				// Not mapped in the db, assume pending.
+				let (hash, api) = self.pending_runtime_api().await.map_err(|err| {
+					internal_err(format!("Create pending runtime api error: {err}"))
+				})?;
+				(hash, api)
+			}
+		};
+
+		if !api
+			.has_api::<dyn EthereumAccessListApi<B>>(substrate_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+		{
+			return Err(internal_err(
+				"runtime does not support access list generation",
+			));
+		}
+
+		let block_gas_limit = api
+			.current_block(substrate_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
+			.header
+			.gas_limit;
+		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
+		let gas_limit = match gas {
+			Some(amount) if amount > max_gas_limit => {
+				return Err(internal_err(format!(
+					"provided gas limit is too high (can be up to {}x the block gas limit)",
+					self.execute_gas_limit_multiplier
+				)));
+			}
+			Some(amount) => amount,
+			None => match api.gas_limit_multiplier_support(substrate_hash) {
+				Ok(_) => max_gas_limit,
+				_ => block_gas_limit,
+			},
+		};
+
+		let data = data.into_bytes().map(|d| d.into_vec()).unwrap_or_default();
+		let access_list = access_list
+			.unwrap_or_default()
+			.into_iter()
+			.map(|item| (item.address, item.storage_keys))
+			.collect();
+
+		let (info, accessed) = converge_access_list(access_list, |access_list| {
+			// Every run starts from the same state, as the runtime api commits its changes.
+			api.execute_in_transaction(|api| {
+				TransactionOutcome::Rollback(api.create_access_list(
+					substrate_hash,
+					from.unwrap_or_default(),
+					to,
+					data.clone(),
+					value.unwrap_or_default(),
+					gas_limit,
+					details.max_fee_per_gas,
+					details.max_priority_fee_per_gas,
+					nonce,
+					Some(access_list.to_vec()),
+				))
+			})
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.map_err(|err| internal_err(format!("execution fatal: {err:?}")))
+		})?;
+
+		let gas_used = info.used_gas.effective;
+		self.observe_used_gas("eth_createAccessList", gas_used);
+		Ok(AccessListResult {
+			access_list: accessed
+				.into_iter()
+				.map(|(address, storage_keys)| ethereum::AccessListItem {
+					address,
+					storage_keys,
+				})
+				.collect(),
+			gas_used,
+			error: error_on_execution_failure(&info.exit_reason, &info.value)
+				.err()
+				.map(|err| err.message().to_string()),
+		})
+	}
+
+	pub async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>>
+	where
+		C::Api: EthereumSimulateApi<B>,
+	{
+		// Keep the cost of a single request bounded, as other clients do.
+		const MAX_SIMULATED_BLOCKS: usize = 256;
+
+		let SimulatePayload {
+			block_state_calls,
+			trace_transfers,
+			validation,
+		} = payload;
+		if block_state_calls.len() > MAX_SIMULATED_BLOCKS {
+			return Err(internal_err(format!(
+				"at most {MAX_SIMULATED_BLOCKS} blocks can be simulated"
+			)));
+		}
+
+		let substrate_hash = match frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			number_or_hash,
+		)
+		.await?
+		{
+			Some(id) => self
+				.client
+				.expect_block_hash_from_id(&id)
+				.map_err(|_| crate::err(CALL_EXECUTION_FAILED_CODE, "header not found", None))?,
+			// Not mapped in the db, simulate on top of the best block.
+			None => self.client.info().best_hash,
+		};
+
+		let api = self.client.runtime_api();
+		if !api
+			.has_api::<dyn EthereumSimulateApi<B>>(substrate_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+		{
+			return Err(internal_err("runtime does not support call simulation"));
+		}
+		let api_version = if let Ok(Some(api_version)) =
+			api.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash)
+		{
+			api_version
+		} else {
+			return Err(internal_err("failed to retrieve Runtime Api version"));
+		};
+		let parent = api
+			.current_block(substrate_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
+			.header;
+		let block_gas_limit = parent.gas_limit;
+		let mut last_number = parent.number;
+
+		// All the simulated blocks share one overlay, so that each builds on the previous ones.
+		let overlayed_changes = RefCell::new(OverlayedChanges::default());
+		let mut blocks = Vec::with_capacity(block_state_calls.len());
+		for SimulateBlock {
+			block_overrides,
+			state_overrides,
+			calls,
+		} in block_state_calls
+		{
+			if let Some(state_overrides) = state_overrides {
+				self.apply_state_overrides(
+					&mut overlayed_changes.borrow_mut(),
+					substrate_hash,
+					api_version,
+					state_overrides,
+				)?;
+			}
+
+			let block_overrides = block_overrides.unwrap_or_default();
+			if let Some(number) = block_overrides.number {
+				if number <= last_number {
+					return Err(internal_err(format!(
+						"block number {number} must be greater than {last_number}"
+					)));
+				}
+			}
+
+			let calls = calls
+				.into_iter()
+				.map(|request| {
+					let details = fee_details(
+						request.gas_price,
+						request.max_fee_per_gas,
+						request.max_priority_fee_per_gas,
+					)?;
+					Ok(fp_rpc::SimulateCall {
+						from: request.from.unwrap_or_default(),
+						to: request.to,
+						value: request.value.unwrap_or_default(),
+						gas_limit: request.gas.unwrap_or(block_gas_limit),
+						max_fee_per_gas: details.max_fee_per_gas,
+						max_priority_fee_per_gas: details.max_priority_fee_per_gas,
+						nonce: request.nonce,
+						access_list: request.access_list.map(|access_list| {
+							access_list
+								.into_iter()
+								.map(|item| (item.address, item.storage_keys))
+								.collect()
+						}),
+						data: request
+							.data
+							.into_bytes()
+							.map(|d| d.into_vec())
+							.unwrap_or_default(),
+					})
+				})
+				.collect::<RpcResult<Vec<_>>>()?;
+
+			let encoded_params = Encode::encode(&(
+				&fp_rpc::SimulateBlockOverrides {
+					number: block_overrides.number,
+					timestamp: block_overrides.time.map(|time| time.as_u64()),
+					base_fee_per_gas: block_overrides.base_fee_per_gas,
+				},
+				&calls,
+				&validation,
+				&trace_transfers,
+			));
+			let params = CallApiAtParams {
+				at: substrate_hash,
+				function: "EthereumSimulateApi_simulate_block",
+				arguments: encoded_params,
+				overlayed_changes: &overlayed_changes,
+				call_context: CallContext::Offchain,
+				recorder: &None,
+				extensions: &RefCell::new(Extensions::new()),
+			};
+			let block = self
+				.client
+				.call_api_at(params)
+				.and_then(|r| {
+					<fp_rpc::SimulatedBlock as Decode>::decode(&mut &r[..]).map_err(|error| {
+						sp_api::ApiError::FailedToDecodeReturnValue {
+							function: "EthereumSimulateApi_simulate_block",
+							error,
+							raw: r,
+						}
+					})
+				})
+				.map_err(|err| internal_err(format!("runtime error: {err}")))?;
+			last_number = block.number;
+
+			let mut gas_used = U256::zero();
+			let mut log_index = 0usize;
+			let mut calls = Vec::with_capacity(block.results.len());
+			for (transaction_index, result) in block.results.into_iter().enumerate() {
+				let info =
+					result.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+				gas_used = gas_used.saturating_add(info.used_gas.effective);
+
+				let error = error_on_execution_failure(&info.exit_reason, &info.value)
+					.err()
+					.map(|err| SimulateCallError {
+						code: err.code(),
+						message: err.message().to_string(),
+					});
+				let logs = info
+					.logs
+					.into_iter()
+					.enumerate()
+					.map(|(transaction_log_index, log)| {
+						log_index += 1;
+						Log {
+							address: log.address,
+							topics: log.topics,
+							data: Bytes(log.data),
+							block_hash: None,
+							block_number: Some(block.number),
+							transaction_hash: None,
+							transaction_index: Some(U256::from(transaction_index)),
+							log_index: Some(U256::from(log_index - 1)),
+							transaction_log_index: Some(U256::from(transaction_log_index)),
+							removed: false,
+						}
+					})
+					.collect();
+
+				calls.push(SimulatedCall {
+					status: if error.is_none() {
+						U64::one()
+					} else {
+						U64::zero()
+					},
+					return_data: Bytes(info.value),
+					gas_used: info.used_gas.effective,
+					logs,
+					error,
+				});
+			}
+
+			blocks.push(SimulatedBlock {
+				number: block.number,
+				timestamp: U256::from(block.timestamp),
+				gas_limit: block_gas_limit,
+				gas_used,
+				base_fee_per_gas: block.base_fee_per_gas,
+				calls,
+			});
+		}
+
+		Ok(blocks)
+	}
+
+	fn observe_used_gas(&self, method: &'static str, used_gas: U256) {
+		if let Some(metrics) = &self.metrics {
+			metrics.observe_gas(method, used_gas);
+		}
+	}
+
 	/// Given an address mapped `CallStateOverride`, creates `OverlayedChanges` to be used for
 	/// `CallApiAt` eth_call.
 	fn create_overrides_overlay(
@@ -886,75 +1236,118 @@ where
 	) -> RpcResult<OverlayedChanges<HashingFor<B>>> {
 		let mut overlayed_changes = OverlayedChanges::default();
 		if let Some(state_overrides) = state_overrides {
-			for (address, state_override) in state_overrides {
-				if EC::RuntimeStorageOverride::is_enabled() {
-					EC::RuntimeStorageOverride::set_overlayed_changes(
-						self.client.as_ref(),
-						&mut overlayed_changes,
-						block_hash,
-						api_version,
-						address,
-						state_override.balance,
-						state_override.nonce,
-					);
-				} else if state_override.balance.is_some() || state_override.nonce.is_some() {
-					return Err(internal_err(
-						"state override unsupported for balance and nonce",
-					));
-				}
+			self.apply_state_overrides(
+				&mut overlayed_changes,
+				block_hash,
+				api_version,
+				state_overrides,
+			)?;
+		}
 
-				if let Some(code) = &state_override.code {
-					let mut key = [twox_128(PALLET_EVM), twox_128(EVM_ACCOUNT_CODES)]
-						.concat()
-						.to_vec();
-					key.extend(blake2_128(address.as_bytes()));
-					key.extend(address.as_bytes());
-					let encoded_code = code.clone().into_vec().encode();
-					overlayed_changes.set_storage(key.clone(), Some(encoded_code));
-				}
+		Ok(overlayed_changes)
+	}
 
-				let mut account_storage_key =
-					[twox_128(PALLET_EVM), twox_128(EVM_ACCOUNT_STORAGES)]
-						.concat()
-						.to_vec();
-				account_storage_key.extend(blake2_128(address.as_bytes()));
-				account_storage_key.extend(address.as_bytes());
-
-				// Use `state` first. If `stateDiff` is also present, it resolves consistently
-				if let Some(state) = &state_override.state {
-					// clear all storage
-					if let Ok(all_keys) = self.client.storage_keys(
-						block_hash,
-						Some(&sp_storage::StorageKey(account_storage_key.clone())),
-						None,
-					) {
-						for key in all_keys {
-							overlayed_changes.set_storage(key.0, None);
-						}
-					}
-					// set provided storage
-					for (k, v) in state {
-						let mut slot_key = account_storage_key.clone();
-						slot_key.extend(blake2_128(k.as_bytes()));
-						slot_key.extend(k.as_bytes());
+	/// Writes an address mapped `CallStateOverride` into `overlayed_changes`.
+	fn apply_state_overrides(
+		&self,
+		overlayed_changes: &mut OverlayedChanges<HashingFor<B>>,
+		block_hash: B::Hash,
+		api_version: u32,
+		state_overrides: BTreeMap<H160, CallStateOverride>,
+	) -> RpcResult<()> {
+		for (address, state_override) in state_overrides {
+			if EC::RuntimeStorageOverride::is_enabled() {
+				EC::RuntimeStorageOverride::set_overlayed_changes(
+					self.client.as_ref(),
+					overlayed_changes,
+					block_hash,
+					api_version,
+					address,
+					state_override.balance,
+					state_override.nonce,
+				);
+			} else if state_override.balance.is_some() || state_override.nonce.is_some() {
+				return Err(internal_err(
+					"state override unsupported for balance and nonce",
+				));
+			}
 
-						overlayed_changes.set_storage(slot_key, Some(v.as_bytes().to_owned()));
+			if let Some(code) = &state_override.code {
+				let mut key = [twox_128(PALLET_EVM), twox_128(EVM_ACCOUNT_CODES)]
+					.concat()
+					.to_vec();
+				key.extend(blake2_128(address.as_bytes()));
+				key.extend(address.as_bytes());
+				let encoded_code = code.clone().into_vec().encode();
+				overlayed_changes.set_storage(key.clone(), Some(encoded_code));
+			}
+
+			let mut account_storage_key = [twox_128(PALLET_EVM), twox_128(EVM_ACCOUNT_STORAGES)]
+				.concat()
+				.to_vec();
+			account_storage_key.extend(blake2_128(address.as_bytes()));
+			account_storage_key.extend(address.as_bytes());
+
+			// Use `state` first. If `stateDiff` is also present, it resolves consistently
+			if let Some(state) = &state_override.state {
+				// clear all storage, the one of the base block and the one written by the calls
+				// of previously simulated blocks
+				if let Ok(all_keys) = self.client.storage_keys(
+					block_hash,
+					Some(&sp_storage::StorageKey(account_storage_key.clone())),
+					None,
+				) {
+					for key in all_keys {
+						overlayed_changes.set_storage(key.0, None);
 					}
 				}
+				overlayed_changes.clear_prefix(&account_storage_key);
+				// set provided storage
+				for (k, v) in state {
+					let mut slot_key = account_storage_key.clone();
+					slot_key.extend(blake2_128(k.as_bytes()));
+					slot_key.extend(k.as_bytes());
+
+					overlayed_changes.set_storage(slot_key, Some(v.as_bytes().to_owned()));
+				}
+			}
 
-				if let Some(state_diff) = &state_override.state_diff {
-					for (k, v) in state_diff {
-						let mut slot_key = account_storage_key.clone();
-						slot_key.extend(blake2_128(k.as_bytes()));
-						slot_key.extend(k.as_bytes());
+			if let Some(state_diff) = &state_override.state_diff {
+				for (k, v) in state_diff {
+					let mut slot_key = account_storage_key.clone();
+					slot_key.extend(blake2_128(k.as_bytes()));
+					slot_key.extend(k.as_bytes());
 
-						overlayed_changes.set_storage(slot_key, Some(v.as_bytes().to_owned()));
-					}
+					overlayed_changes.set_storage(slot_key, Some(v.as_bytes().to_owned()));
 				}
 			}
 		}
 
-		Ok(overlayed_changes)
+		Ok(())
+	}
+}
+
+/// Maximum number of runs of `eth_createAccessList`.
+const MAX_ACCESS_LIST_ITERATIONS: usize = 8;
+
+/// Run `execute` with `access_list`, then with the list each run recorded, until a run records the
+/// list it was given.
+///
+/// Re-running with the recorded list can change the execution path (e.g. gas dependent branches),
+/// so the list may take several runs to settle. The runs are bounded to keep the cost predictable,
+/// the outcome of the last one is returned.
+fn converge_access_list<T>(
+	mut access_list: Vec<(H160, Vec<H256>)>,
+	mut execute: impl FnMut(&[(H160, Vec<H256>)]) -> RpcResult<(T, Vec<(H160, Vec<H256>)>)>,
+) -> RpcResult<(T, Vec<(H160, Vec<H256>)>)> {
+	let mut iteration = 0;
+	loop {
+		iteration += 1;
+		let (outcome, accessed) = execute(&access_list)?;
+		if accessed == access_list || iteration == MAX_ACCESS_LIST_ITERATIONS {
+			return Ok((outcome, accessed));
+		}
+		access_list = accessed;
 	}
 }
 
@@ -1050,3 +1443,60 @@ fn fee_details(
 		}),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn item(byte: u8, slots: &[u64]) -> (H160, Vec<H256>) {
+		(
+			H160::repeat_byte(byte),
+			slots.iter().copied().map(H256::from_low_u64_be).collect(),
+		)
+	}
+
+	#[test]
+	fn access_list_converges_once_a_run_records_its_input() {
+		let mut runs = Vec::new();
+		let (outcome, access_list) = converge_access_list(vec![item(1, &[])], |access_list| {
+			runs.push(access_list.to_vec());
+			// The first run records a slot, which the second run records again.
+			Ok((runs.len(), vec![item(1, &[7])]))
+		})
+		.unwrap();
+
+		assert_eq!(outcome, 2);
+		assert_eq!(access_list, vec![item(1, &[7])]);
+		assert_eq!(runs, vec![vec![item(1, &[])], vec![item(1, &[7])]]);
+	}
+
+	#[test]
+	fn access_list_runs_are_bounded() {
+		let mut runs = 0u64;
+		let (outcome, access_list) = converge_access_list(Vec::new(), |_| {
+			runs += 1;
+			// Every run records a list different from its input.
+			Ok((runs, vec![item(1, &[runs])]))
+		})
+		.unwrap();
+
+		assert_eq!(outcome, MAX_ACCESS_LIST_ITERATIONS as u64);
+		assert_eq!(access_list, vec![item(1, &[runs])]);
+	}
+
+	#[test]
+	fn access_list_run_errors_are_returned() {
+		let mut runs = 0;
+		let result = converge_access_list::<()>(Vec::new(), |_| {
+			runs += 1;
+			if runs == 1 {
+				Ok(((), vec![item(1, &[])]))
+			} else {
+				Err(internal_err("execution fatal"))
+			}
+		});
+
+		assert_eq!(result.unwrap_err().message(), "execution fatal");
+		assert_eq!(runs, 2);
+	}
+}
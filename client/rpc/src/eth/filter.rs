@@ -25,7 +25,12 @@ use std::{
 
 use ethereum::BlockV2 as EthereumBlock;
 use ethereum_types::{H256, U256};
-use jsonrpsee::core::{async_trait, RpcResult};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	types::{ErrorObject, ErrorObjectOwned},
+	ConnectionId, Extensions,
+};
+use serde::Serialize;
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
 use sc_transaction_pool::{ChainApi, Pool};
@@ -41,37 +46,71 @@ use sp_runtime::{
 use fc_rpc_core::{types::*, EthFilterApiServer};
 use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
 
-use crate::{cache::EthBlockDataCacheTask, frontier_backend_client, internal_err};
+use crate::{
+	cache::EthBlockDataCacheTask,
+	frontier_backend_client, internal_err,
+	metrics::{self, EthMetrics},
+};
 
 pub struct EthFilter<B: BlockT, C, BE, A: ChainApi> {
 	client: Arc<C>,
 	backend: Arc<dyn fc_api::Backend<B>>,
 	graph: Arc<Pool<A>>,
 	filter_pool: FilterPool,
-	max_stored_filters: usize,
-	max_past_logs: u32,
+	limits: FilterLimits,
 	block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+	metrics: Option<Arc<EthMetrics>>,
 	_marker: PhantomData<BE>,
 }
 
+/// Resource limits of the filter apis and `eth_getLogs`.
+#[derive(Clone, Debug)]
+pub struct FilterLimits {
+	/// Maximum number of filters installed at once.
+	pub max_stored_filters: usize,
+	/// Maximum number of filters installed at once by a single WebSocket connection.
+	///
+	/// Every HTTP request is served as a connection of its own, so this quota does not apply to
+	/// filters installed over HTTP, which are only bounded by `max_stored_filters`.
+	pub max_filters_per_connection: Option<usize>,
+	/// Maximum number of logs returned by a query.
+	pub max_past_logs: u32,
+	/// Maximum number of blocks a query can range over.
+	pub max_block_range: Option<u32>,
+	/// Maximum size in bytes of the logs returned by a query, as serialized to JSON.
+	pub max_response_size: Option<usize>,
+}
+
+impl Default for FilterLimits {
+	fn default() -> Self {
+		Self {
+			max_stored_filters: 500,
+			max_filters_per_connection: None,
+			max_past_logs: 10000,
+			max_block_range: None,
+			max_response_size: None,
+		}
+	}
+}
+
 impl<B: BlockT, C, BE, A: ChainApi> EthFilter<B, C, BE, A> {
 	pub fn new(
 		client: Arc<C>,
 		backend: Arc<dyn fc_api::Backend<B>>,
 		graph: Arc<Pool<A>>,
 		filter_pool: FilterPool,
-		max_stored_filters: usize,
-		max_past_logs: u32,
+		limits: FilterLimits,
 		block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+		metrics: Option<Arc<EthMetrics>>,
 	) -> Self {
 		Self {
 			client,
 			backend,
 			graph,
 			filter_pool,
-			max_stored_filters,
-			max_past_logs,
+			limits,
 			block_data_cache,
+			metrics,
 			_marker: PhantomData,
 		}
 	}
@@ -85,17 +124,44 @@ where
 	C: HeaderBackend<B> + 'static,
 	A: ChainApi<Block = B> + 'static,
 {
-	fn create_filter(&self, filter_type: FilterType) -> RpcResult<U256> {
+	fn create_filter(
+		&self,
+		filter_type: FilterType,
+		connection_id: Option<usize>,
+	) -> RpcResult<U256> {
 		let info = self.client.info();
 		let best_hash = info.best_hash;
 		let best_number = UniqueSaturatedInto::<u64>::unique_saturated_into(info.best_number);
 		let pool = self.filter_pool.clone();
 		let response = if let Ok(locked) = &mut pool.lock() {
-			if locked.len() >= self.max_stored_filters {
-				return Err(internal_err(format!(
-					"Filter pool is full (limit {:?}).",
-					self.max_stored_filters
-				)));
+			if locked.len() >= self.limits.max_stored_filters {
+				return Err(limit_exceeded(
+					format!(
+						"Filter pool is full (limit {:?}).",
+						self.limits.max_stored_filters
+					),
+					LimitExceededData {
+						max_stored_filters: Some(self.limits.max_stored_filters),
+						..Default::default()
+					},
+				));
+			}
+			if let (Some(max), Some(connection_id)) =
+				(self.limits.max_filters_per_connection, connection_id)
+			{
+				let installed = locked
+					.values()
+					.filter(|item| item.connection_id == Some(connection_id))
+					.count();
+				if installed >= max {
+					return Err(limit_exceeded(
+						format!("Too many filters installed by this connection (limit {max})."),
+						LimitExceededData {
+							max_filters_per_connection: Some(max),
+							..Default::default()
+						},
+					));
+				}
 			}
 			let next_back = {
 				let mut iter = locked.iter();
@@ -135,18 +201,26 @@ where
 					filter_type,
 					at_block: best_number,
 					pending_transaction_hashes,
+					connection_id,
+					last_activity: Instant::now(),
 				},
 			);
+			self.observe_pool_size(locked.len());
 			Ok(key)
 		} else {
 			Err(internal_err("Filter pool is not available."))
 		};
 		response
 	}
+
+	fn observe_pool_size(&self, size: usize) {
+		if let Some(metrics) = &self.metrics {
+			metrics.set_filter_pool_size(size);
+		}
+	}
 }
 
-#[async_trait]
-impl<B, C, BE, A> EthFilterApiServer for EthFilter<B, C, BE, A>
+impl<B, C, BE, A> EthFilter<B, C, BE, A>
 where
 	B: BlockT,
 	C: ProvideRuntimeApi<B>,
@@ -155,16 +229,16 @@ where
 	BE: Backend<B> + 'static,
 	A: ChainApi<Block = B> + 'static,
 {
-	fn new_filter(&self, filter: Filter) -> RpcResult<U256> {
-		self.create_filter(FilterType::Log(filter))
+	fn new_filter(&self, filter: Filter, connection_id: Option<usize>) -> RpcResult<U256> {
+		self.create_filter(FilterType::Log(filter), connection_id)
 	}
 
-	fn new_block_filter(&self) -> RpcResult<U256> {
-		self.create_filter(FilterType::Block)
+	fn new_block_filter(&self, connection_id: Option<usize>) -> RpcResult<U256> {
+		self.create_filter(FilterType::Block, connection_id)
 	}
 
-	fn new_pending_transaction_filter(&self) -> RpcResult<U256> {
-		self.create_filter(FilterType::PendingTransaction)
+	fn new_pending_transaction_filter(&self, connection_id: Option<usize>) -> RpcResult<U256> {
+		self.create_filter(FilterType::PendingTransaction, connection_id)
 	}
 
 	async fn filter_changes(&self, index: Index) -> RpcResult<FilterChanges> {
@@ -214,6 +288,8 @@ where
 								filter_type: pool_item.filter_type.clone(),
 								at_block: pool_item.at_block,
 								pending_transaction_hashes: HashSet::new(),
+								connection_id: pool_item.connection_id,
+								last_activity: Instant::now(),
 							},
 						);
 
@@ -246,6 +322,8 @@ where
 								filter_type: pool_item.filter_type.clone(),
 								at_block: pool_item.at_block,
 								pending_transaction_hashes: current_hashes.clone(),
+								connection_id: pool_item.connection_id,
+								last_activity: Instant::now(),
 							},
 						);
 
@@ -258,29 +336,36 @@ where
 					}
 					// For each event since last poll, get a vector of ethereum logs.
 					FilterType::Log(filter) => {
-						// Update filter `last_poll`.
+						// Either the filter-specific `to` block or best block.
+						let best_number = info.best_number;
+						let mut current_number = filter_block_number::<B, C>(
+							self.client.as_ref(),
+							filter.to_block,
+							best_number,
+						);
+
+						if current_number > best_number {
+							current_number = best_number;
+						}
+
+						// Update filter `last_poll`. Blocks past a `finalized` or `safe` bound
+						// are picked up once they are finalized.
 						locked.insert(
 							key,
 							FilterPoolItem {
-								last_poll: BlockNumberOrHash::Num(best_number + 1),
+								last_poll: BlockNumberOrHash::Num(
+									UniqueSaturatedInto::<u64>::unique_saturated_into(
+										current_number,
+									) + 1,
+								),
 								filter_type: pool_item.filter_type.clone(),
 								at_block: pool_item.at_block,
 								pending_transaction_hashes: HashSet::new(),
+								connection_id: pool_item.connection_id,
+								last_activity: Instant::now(),
 							},
 						);
 
-						// Either the filter-specific `to` block or best block.
-						let best_number = self.client.info().best_number;
-						let mut current_number = filter
-							.to_block
-							.and_then(|v| v.to_min_block_num())
-							.map(|s| s.unique_saturated_into())
-							.unwrap_or(best_number);
-
-						if current_number > best_number {
-							current_number = best_number;
-						}
-
 						// The from clause is the max(last_poll, filter_from).
 						let last_poll = pool_item
 							.last_poll
@@ -288,11 +373,11 @@ where
 							.unwrap()
 							.unique_saturated_into();
 
-						let filter_from = filter
-							.from_block
-							.and_then(|v| v.to_min_block_num())
-							.map(|s| s.unique_saturated_into())
-							.unwrap_or(last_poll);
+						let filter_from = filter_block_number::<B, C>(
+							self.client.as_ref(),
+							filter.from_block,
+							last_poll,
+						);
 
 						let from_number = std::cmp::max(last_poll, filter_from);
 
@@ -314,7 +399,7 @@ where
 		let client = Arc::clone(&self.client);
 		let backend = Arc::clone(&self.backend);
 		let block_data_cache = Arc::clone(&self.block_data_cache);
-		let max_past_logs = self.max_past_logs;
+		let limits = self.limits.clone();
 
 		match path {
 			FuturePath::Error(err) => Err(err),
@@ -346,7 +431,7 @@ where
 						backend.log_indexer(),
 						&block_data_cache,
 						&mut ret,
-						max_past_logs,
+						&limits,
 						&filter,
 						from_number,
 						current_number,
@@ -357,7 +442,7 @@ where
 						client.as_ref(),
 						&block_data_cache,
 						&mut ret,
-						max_past_logs,
+						&limits,
 						&filter,
 						from_number,
 						current_number,
@@ -397,26 +482,20 @@ where
 		let client = Arc::clone(&self.client);
 		let backend = Arc::clone(&self.backend);
 		let block_data_cache = Arc::clone(&self.block_data_cache);
-		let max_past_logs = self.max_past_logs;
+		let limits = self.limits.clone();
 
 		let filter = filter_result?;
 
 		let best_number = client.info().best_number;
-		let mut current_number = filter
-			.to_block
-			.and_then(|v| v.to_min_block_num())
-			.map(|s| s.unique_saturated_into())
-			.unwrap_or(best_number);
+		let mut current_number =
+			filter_block_number::<B, C>(client.as_ref(), filter.to_block, best_number);
 
 		if current_number > best_number {
 			current_number = best_number;
 		}
 
-		let from_number = filter
-			.from_block
-			.and_then(|v| v.to_min_block_num())
-			.map(|s| s.unique_saturated_into())
-			.unwrap_or(best_number);
+		let from_number =
+			filter_block_number::<B, C>(client.as_ref(), filter.from_block, best_number);
 
 		let mut ret: Vec<Log> = Vec::new();
 		if backend.is_indexed() {
@@ -425,7 +504,7 @@ where
 				backend.log_indexer(),
 				&block_data_cache,
 				&mut ret,
-				max_past_logs,
+				&limits,
 				&filter,
 				from_number,
 				current_number,
@@ -436,7 +515,7 @@ where
 				client.as_ref(),
 				&block_data_cache,
 				&mut ret,
-				max_past_logs,
+				&limits,
 				&filter,
 				from_number,
 				current_number,
@@ -452,6 +531,7 @@ where
 		// Try to lock.
 		let response = if let Ok(locked) = &mut pool.lock() {
 			if locked.remove(&key).is_some() {
+				self.observe_pool_size(locked.len());
 				Ok(true)
 			} else {
 				Err(internal_err(format!("Filter id {:?} does not exist.", key)))
@@ -466,7 +546,7 @@ where
 		let client = Arc::clone(&self.client);
 		let block_data_cache = Arc::clone(&self.block_data_cache);
 		let backend = Arc::clone(&self.backend);
-		let max_past_logs = self.max_past_logs;
+		let limits = self.limits.clone();
 
 		let mut ret: Vec<Log> = Vec::new();
 		if let Some(hash) = filter.block_hash {
@@ -491,21 +571,15 @@ where
 			}
 		} else {
 			let best_number = client.info().best_number;
-			let mut current_number = filter
-				.to_block
-				.and_then(|v| v.to_min_block_num())
-				.map(|s| s.unique_saturated_into())
-				.unwrap_or(best_number);
+			let mut current_number =
+				filter_block_number::<B, C>(client.as_ref(), filter.to_block, best_number);
 
 			if current_number > best_number {
 				current_number = best_number;
 			}
 
-			let from_number = filter
-				.from_block
-				.and_then(|v| v.to_min_block_num())
-				.map(|s| s.unique_saturated_into())
-				.unwrap_or(best_number);
+			let from_number =
+				filter_block_number::<B, C>(client.as_ref(), filter.from_block, best_number);
 
 			if backend.is_indexed() {
 				let _ = filter_range_logs_indexed(
@@ -513,7 +587,7 @@ where
 					backend.log_indexer(),
 					&block_data_cache,
 					&mut ret,
-					max_past_logs,
+					&limits,
 					&filter,
 					from_number,
 					current_number,
@@ -524,7 +598,7 @@ where
 					client.as_ref(),
 					&block_data_cache,
 					&mut ret,
-					max_past_logs,
+					&limits,
 					&filter,
 					from_number,
 					current_number,
@@ -536,12 +610,71 @@ where
 	}
 }
 
+#[async_trait]
+impl<B, C, BE, A> EthFilterApiServer for EthFilter<B, C, BE, A>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+	BE: Backend<B> + 'static,
+	A: ChainApi<Block = B> + 'static,
+{
+	fn new_filter(&self, ext: &Extensions, filter: Filter) -> RpcResult<U256> {
+		metrics::observe(self.metrics.as_deref(), "eth_newFilter", || {
+			self.new_filter(filter, connection_id(ext))
+		})
+	}
+
+	fn new_block_filter(&self, ext: &Extensions) -> RpcResult<U256> {
+		metrics::observe(self.metrics.as_deref(), "eth_newBlockFilter", || {
+			self.new_block_filter(connection_id(ext))
+		})
+	}
+
+	fn new_pending_transaction_filter(&self, ext: &Extensions) -> RpcResult<U256> {
+		metrics::observe(
+			self.metrics.as_deref(),
+			"eth_newPendingTransactionFilter",
+			|| self.new_pending_transaction_filter(connection_id(ext)),
+		)
+	}
+
+	async fn filter_changes(&self, index: Index) -> RpcResult<FilterChanges> {
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getFilterChanges",
+			self.filter_changes(index),
+		)
+		.await
+	}
+
+	async fn filter_logs(&self, index: Index) -> RpcResult<Vec<Log>> {
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getFilterLogs",
+			self.filter_logs(index),
+		)
+		.await
+	}
+
+	fn uninstall_filter(&self, index: Index) -> RpcResult<bool> {
+		metrics::observe(self.metrics.as_deref(), "eth_uninstallFilter", || {
+			self.uninstall_filter(index)
+		})
+	}
+
+	async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
+		metrics::observe_async(self.metrics.as_deref(), "eth_getLogs", self.logs(filter)).await
+	}
+}
+
 async fn filter_range_logs_indexed<B, C, BE>(
 	_client: &C,
 	backend: &dyn fc_api::LogIndexerBackend<B>,
 	block_data_cache: &EthBlockDataCacheTask<B>,
 	ret: &mut Vec<Log>,
-	max_past_logs: u32,
+	limits: &FilterLimits,
 	filter: &Filter,
 	from: NumberFor<B>,
 	to: NumberFor<B>,
@@ -553,6 +686,8 @@ where
 	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
 	BE: Backend<B> + 'static,
 {
+	let mut limiter = LogLimiter::new(limits, from, to)?;
+
 	let timer_start = Instant::now();
 	let timer_prepare = Instant::now();
 
@@ -645,12 +780,7 @@ where
 				}
 			}
 			// Check for restrictions
-			if ret.len() as u32 > max_past_logs {
-				return Err(internal_err(format!(
-					"query returned more than {} results",
-					max_past_logs
-				)));
-			}
+			limiter.check(ret)?;
 			if begin_request.elapsed() > max_duration {
 				return Err(internal_err(format!(
 					"query timeout of {} seconds exceeded",
@@ -683,7 +813,7 @@ async fn filter_range_logs<B, C, BE>(
 	client: &C,
 	block_data_cache: &EthBlockDataCacheTask<B>,
 	ret: &mut Vec<Log>,
-	max_past_logs: u32,
+	limits: &FilterLimits,
 	filter: &Filter,
 	from: NumberFor<B>,
 	to: NumberFor<B>,
@@ -695,6 +825,8 @@ where
 	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
 	BE: Backend<B> + 'static,
 {
+	let mut limiter = LogLimiter::new(limits, from, to)?;
+
 	// Max request duration of 10 seconds.
 	let max_duration = Duration::from_secs(10);
 	let begin_request = Instant::now();
@@ -732,12 +864,7 @@ where
 			}
 		}
 		// Check for restrictions
-		if ret.len() as u32 > max_past_logs {
-			return Err(internal_err(format!(
-				"query returned more than {} results",
-				max_past_logs
-			)));
-		}
+		limiter.check(ret)?;
 		if begin_request.elapsed() > max_duration {
 			return Err(internal_err(format!(
 				"query timeout of {} seconds exceeded",
@@ -753,6 +880,127 @@ where
 	Ok(())
 }
 
+/// Error code of requests exceeding a server-side limit, as used by other Ethereum providers.
+const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// Data of a [`LIMIT_EXCEEDED_CODE`] error, telling the client which limit to stay within.
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LimitExceededData {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_stored_filters: Option<usize>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_filters_per_connection: Option<usize>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_past_logs: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_block_range: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_response_size: Option<usize>,
+}
+
+fn limit_exceeded(message: String, data: LimitExceededData) -> ErrorObjectOwned {
+	ErrorObject::owned(LIMIT_EXCEEDED_CODE, message, Some(data))
+}
+
+/// Id of the connection a request was received on. Only stable across the requests of a
+/// WebSocket connection, as each HTTP request is given a new one.
+fn connection_id(ext: &Extensions) -> Option<usize> {
+	ext.get::<ConnectionId>().map(|id| id.0)
+}
+
+/// Enforces the [`FilterLimits`] of a log query while its logs are collected.
+struct LogLimiter<'a> {
+	limits: &'a FilterLimits,
+	/// Number of logs already accounted for in `size`.
+	counted: usize,
+	size: usize,
+}
+
+impl<'a> LogLimiter<'a> {
+	fn new<N: UniqueSaturatedInto<u64>>(
+		limits: &'a FilterLimits,
+		from: N,
+		to: N,
+	) -> RpcResult<Self> {
+		if let Some(max_block_range) = limits.max_block_range {
+			let from = UniqueSaturatedInto::<u64>::unique_saturated_into(from);
+			let to = UniqueSaturatedInto::<u64>::unique_saturated_into(to);
+			if to.saturating_sub(from) >= u64::from(max_block_range) {
+				return Err(limit_exceeded(
+					format!("query exceeds the maximum block range of {max_block_range}"),
+					LimitExceededData {
+						max_block_range: Some(max_block_range),
+						..Default::default()
+					},
+				));
+			}
+		}
+		Ok(Self {
+			limits,
+			counted: 0,
+			size: 0,
+		})
+	}
+
+	fn check(&mut self, logs: &[Log]) -> RpcResult<()> {
+		let max_past_logs = self.limits.max_past_logs;
+		if logs.len() as u32 > max_past_logs {
+			return Err(limit_exceeded(
+				format!("query returned more than {max_past_logs} results"),
+				LimitExceededData {
+					max_past_logs: Some(max_past_logs),
+					..Default::default()
+				},
+			));
+		}
+
+		if let Some(max_response_size) = self.limits.max_response_size {
+			for log in logs.iter().skip(self.counted) {
+				self.size = self.size.saturating_add(log_json_size(log));
+			}
+			self.counted = logs.len();
+			if self.size > max_response_size {
+				return Err(limit_exceeded(
+					format!("query returned more than {max_response_size} bytes"),
+					LimitExceededData {
+						max_response_size: Some(max_response_size),
+						..Default::default()
+					},
+				));
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Approximate size of a log serialized to JSON, without having to serialize it.
+fn log_json_size(log: &Log) -> usize {
+	// Field names, punctuation and the fixed size hex fields (hashes, numbers and address).
+	const FIXED_SIZE: usize = 500;
+	// A quoted, `0x` prefixed and comma separated 32 bytes hex string.
+	const TOPIC_SIZE: usize = 69;
+
+	FIXED_SIZE + TOPIC_SIZE * log.topics.len() + 2 * log.data.0.len()
+}
+
+/// Resolves a filter block bound, `safe` and `finalized` being the GRANDPA finalized head.
+fn filter_block_number<B: BlockT, C: HeaderBackend<B>>(
+	client: &C,
+	number: Option<BlockNumberOrHash>,
+	default: NumberFor<B>,
+) -> NumberFor<B> {
+	match number {
+		Some(BlockNumberOrHash::Safe | BlockNumberOrHash::Finalized) => {
+			client.info().finalized_number
+		}
+		number => number
+			.and_then(|v| v.to_min_block_num())
+			.map(|s| s.unique_saturated_into())
+			.unwrap_or(default),
+	}
+}
+
 fn filter_block_logs<'a>(
 	ret: &'a mut Vec<Log>,
 	filter: &'a Filter,
@@ -812,3 +1060,119 @@ fn filter_block_logs<'a>(
 	}
 	ret
 }
+
+#[cfg(test)]
+mod tests {
+	use futures::executor;
+	use sc_block_builder::BlockBuilderBuilder;
+	use sp_consensus::BlockOrigin;
+	use substrate_test_runtime_client::{
+		prelude::*, runtime::Block, DefaultTestClientBuilderExt, TestClientBuilder,
+	};
+
+	use super::*;
+
+	fn limits() -> FilterLimits {
+		FilterLimits {
+			max_past_logs: 3,
+			..Default::default()
+		}
+	}
+
+	fn log(topics: usize, data: usize) -> Log {
+		Log {
+			address: Default::default(),
+			topics: vec![H256::zero(); topics],
+			data: Bytes(vec![0; data]),
+			block_hash: None,
+			block_number: None,
+			transaction_hash: None,
+			transaction_index: None,
+			log_index: None,
+			transaction_log_index: None,
+			removed: false,
+		}
+	}
+
+	fn assert_limit_exceeded<T: std::fmt::Debug>(result: RpcResult<T>, data: &str) {
+		let err = result.unwrap_err();
+		assert_eq!(err.code(), LIMIT_EXCEEDED_CODE);
+		assert_eq!(err.data().map(|data| data.get()), Some(data));
+	}
+
+	#[test]
+	fn limiter_bounds_block_range() {
+		let limits = FilterLimits {
+			max_block_range: Some(10),
+			..limits()
+		};
+
+		assert!(LogLimiter::new(&limits, 5u64, 14u64).is_ok());
+		assert_limit_exceeded(
+			LogLimiter::new(&limits, 5u64, 15u64).map(|_| ()),
+			r#"{"maxBlockRange":10}"#,
+		);
+		assert!(LogLimiter::new(&limits(), 0u64, u64::MAX).is_ok());
+	}
+
+	#[test]
+	fn limiter_bounds_log_count() {
+		let limits = limits();
+		let mut limiter = LogLimiter::new(&limits, 0u64, 0u64).unwrap();
+		let logs = vec![log(1, 32); 4];
+
+		assert!(limiter.check(&logs[..3]).is_ok());
+		assert_limit_exceeded(limiter.check(&logs), r#"{"maxPastLogs":3}"#);
+	}
+
+	#[test]
+	fn limiter_bounds_response_size() {
+		let logs = vec![log(2, 64), log(0, 0), log(4, 256)];
+		let size = log_json_size(&logs[0]) + log_json_size(&logs[1]);
+		let limits = FilterLimits {
+			max_response_size: Some(size),
+			..limits()
+		};
+		let mut limiter = LogLimiter::new(&limits, 0u64, 0u64).unwrap();
+
+		// Logs already accounted for are not counted again as the result grows.
+		assert!(limiter.check(&logs[..1]).is_ok());
+		assert!(limiter.check(&logs[..2]).is_ok());
+		assert_eq!(limiter.size, size);
+		assert_limit_exceeded(
+			limiter.check(&logs),
+			&format!(r#"{{"maxResponseSize":{size}}}"#),
+		);
+	}
+
+	#[test]
+	fn safe_and_finalized_bounds_are_the_finalized_head() {
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+		let mut client = Arc::new(client);
+		for _ in 0..3 {
+			let chain = client.chain_info();
+			let block = BlockBuilderBuilder::new(&*client)
+				.on_parent_block(chain.best_hash)
+				.with_parent_block_number(chain.best_number)
+				.build()
+				.unwrap()
+				.build()
+				.unwrap()
+				.block;
+			executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		}
+		let finalized = client.hash(1).unwrap().unwrap();
+		client.finalize_block(finalized, None).unwrap();
+
+		let bound = |number| filter_block_number::<Block, _>(&*client, number, 42);
+		assert_eq!(bound(Some(BlockNumberOrHash::Safe)), 1);
+		assert_eq!(bound(Some(BlockNumberOrHash::Finalized)), 1);
+		assert_eq!(bound(Some(BlockNumberOrHash::Num(2))), 2);
+		assert_eq!(bound(Some(BlockNumberOrHash::Earliest)), 0);
+		assert_eq!(bound(Some(BlockNumberOrHash::Latest)), 42);
+		assert_eq!(bound(None), 42);
+	}
+}
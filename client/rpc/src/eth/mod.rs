@@ -34,7 +34,10 @@ use ethereum::{BlockV2 as EthereumBlock, TransactionV2 as EthereumTransaction};
 use ethereum_types::{H160, H256, H64, U256, U64};
 use jsonrpsee::core::{async_trait, RpcResult};
 // Substrate
-use sc_client_api::backend::{Backend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	ProofProvider,
+};
 use sc_network_sync::SyncingService;
 use sc_transaction_pool::{ChainApi, Pool};
 use sc_transaction_pool_api::TransactionPool;
@@ -48,26 +51,35 @@ use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
 use fc_rpc_core::{types::*, EthApiServer};
 use fc_storage::StorageOverride;
 use fp_rpc::{
-	ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi,
-	RuntimeStorageOverride, TransactionStatus,
+	ConvertTransaction, ConvertTransactionRuntimeApi, EthereumAccessListApi, EthereumRuntimeRPCApi,
+	EthereumSimulateApi, RuntimeStorageOverride, TransactionStatus,
 };
 
 use crate::{
-	cache::EthBlockDataCacheTask, frontier_backend_client, internal_err, public_key,
+	cache::EthBlockDataCacheTask,
+	frontier_backend_client, internal_err,
+	metrics::{self, EthMetrics},
+	public_key,
 	signer::EthSigner,
 };
 
-pub use self::{execute::EstimateGasAdapter, filter::EthFilter};
+pub use self::{
+	execute::EstimateGasAdapter,
+	filter::{EthFilter, FilterLimits},
+	state::AccountProofKeys,
+};
 
 // Configuration trait for RPC configuration.
 pub trait EthConfig<B: BlockT, C>: Send + Sync + 'static {
 	type EstimateGasAdapter: EstimateGasAdapter + Send + Sync;
 	type RuntimeStorageOverride: RuntimeStorageOverride<B, C>;
+	type AccountProofKeys: AccountProofKeys;
 }
 
 impl<B: BlockT, C> EthConfig<B, C> for () {
 	type EstimateGasAdapter = ();
 	type RuntimeStorageOverride = ();
+	type AccountProofKeys = ();
 }
 
 /// Eth API implementation.
@@ -91,6 +103,8 @@ pub struct Eth<B: BlockT, C, P, CT, BE, A: ChainApi, CIDP, EC> {
 	/// Something that can create the inherent data providers for pending state.
 	pending_create_inherent_data_providers: CIDP,
 	pending_consensus_data_provider: Option<Box<dyn pending::ConsensusDataProvider<B>>>,
+	/// Prometheus metrics, if enabled.
+	metrics: Option<Arc<EthMetrics>>,
 	_marker: PhantomData<(BE, EC)>,
 }
 
@@ -120,6 +134,7 @@ where
 		forced_parent_hashes: Option<BTreeMap<H256, H256>>,
 		pending_create_inherent_data_providers: CIDP,
 		pending_consensus_data_provider: Option<Box<dyn pending::ConsensusDataProvider<B>>>,
+		metrics: Option<Arc<EthMetrics>>,
 	) -> Self {
 		Self {
 			client,
@@ -138,6 +153,7 @@ where
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			metrics,
 			_marker: PhantomData,
 		}
 	}
@@ -271,6 +287,7 @@ where
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			metrics,
 			_marker: _,
 		} = self;
 
@@ -291,6 +308,7 @@ where
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			metrics,
 			_marker: PhantomData,
 		}
 	}
@@ -300,8 +318,12 @@ where
 impl<B, C, P, CT, BE, A, CIDP, EC> EthApiServer for Eth<B, C, P, CT, BE, A, CIDP, EC>
 where
 	B: BlockT,
-	C: CallApiAt<B> + ProvideRuntimeApi<B>,
-	C::Api: BlockBuilderApi<B> + ConvertTransactionRuntimeApi<B> + EthereumRuntimeRPCApi<B>,
+	C: CallApiAt<B> + ProofProvider<B> + ProvideRuntimeApi<B>,
+	C::Api: BlockBuilderApi<B>
+		+ ConvertTransactionRuntimeApi<B>
+		+ EthereumAccessListApi<B>
+		+ EthereumRuntimeRPCApi<B>
+		+ EthereumSimulateApi<B>,
 	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
 	BE: Backend<B> + 'static,
 	P: TransactionPool<Block = B> + 'static,
@@ -315,27 +337,31 @@ where
 	// ########################################################################
 
 	fn protocol_version(&self) -> RpcResult<u64> {
-		self.protocol_version()
+		metrics::observe(self.metrics.as_deref(), "eth_protocolVersion", || {
+			self.protocol_version()
+		})
 	}
 
 	async fn syncing(&self) -> RpcResult<SyncStatus> {
-		self.syncing().await
+		metrics::observe_async(self.metrics.as_deref(), "eth_syncing", self.syncing()).await
 	}
 
 	fn author(&self) -> RpcResult<H160> {
-		self.author()
+		metrics::observe(self.metrics.as_deref(), "eth_coinbase", || self.author())
 	}
 
 	fn accounts(&self) -> RpcResult<Vec<H160>> {
-		self.accounts()
+		metrics::observe(self.metrics.as_deref(), "eth_accounts", || self.accounts())
 	}
 
 	fn block_number(&self) -> RpcResult<U256> {
-		self.block_number()
+		metrics::observe(self.metrics.as_deref(), "eth_blockNumber", || {
+			self.block_number()
+		})
 	}
 
 	fn chain_id(&self) -> RpcResult<Option<U64>> {
-		self.chain_id()
+		metrics::observe(self.metrics.as_deref(), "eth_chainId", || self.chain_id())
 	}
 
 	// ########################################################################
@@ -343,7 +369,12 @@ where
 	// ########################################################################
 
 	async fn block_by_hash(&self, hash: H256, full: bool) -> RpcResult<Option<RichBlock>> {
-		self.block_by_hash(hash, full).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getBlockByHash",
+			self.block_by_hash(hash, full),
+		)
+		.await
 	}
 
 	async fn block_by_number(
@@ -351,33 +382,61 @@ where
 		number_or_hash: BlockNumberOrHash,
 		full: bool,
 	) -> RpcResult<Option<RichBlock>> {
-		self.block_by_number(number_or_hash, full).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getBlockByNumber",
+			self.block_by_number(number_or_hash, full),
+		)
+		.await
 	}
 
 	async fn block_transaction_count_by_hash(&self, hash: H256) -> RpcResult<Option<U256>> {
-		self.block_transaction_count_by_hash(hash).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getBlockTransactionCountByHash",
+			self.block_transaction_count_by_hash(hash),
+		)
+		.await
 	}
 
 	async fn block_transaction_count_by_number(
 		&self,
 		number_or_hash: BlockNumberOrHash,
 	) -> RpcResult<Option<U256>> {
-		self.block_transaction_count_by_number(number_or_hash).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getBlockTransactionCountByNumber",
+			self.block_transaction_count_by_number(number_or_hash),
+		)
+		.await
 	}
 
 	async fn block_transaction_receipts(
 		&self,
 		number_or_hash: BlockNumberOrHash,
 	) -> RpcResult<Option<Vec<Receipt>>> {
-		self.block_transaction_receipts(number_or_hash).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getBlockReceipts",
+			self.block_transaction_receipts(number_or_hash),
+		)
+		.await
 	}
 
 	fn block_uncles_count_by_hash(&self, hash: H256) -> RpcResult<U256> {
-		self.block_uncles_count_by_hash(hash)
+		metrics::observe(
+			self.metrics.as_deref(),
+			"eth_getUncleCountByBlockHash",
+			|| self.block_uncles_count_by_hash(hash),
+		)
 	}
 
 	fn block_uncles_count_by_number(&self, number_or_hash: BlockNumberOrHash) -> RpcResult<U256> {
-		self.block_uncles_count_by_number(number_or_hash)
+		metrics::observe(
+			self.metrics.as_deref(),
+			"eth_getUncleCountByBlockNumber",
+			|| self.block_uncles_count_by_number(number_or_hash),
+		)
 	}
 
 	fn uncle_by_block_hash_and_index(
@@ -385,7 +444,11 @@ where
 		hash: H256,
 		index: Index,
 	) -> RpcResult<Option<RichBlock>> {
-		self.uncle_by_block_hash_and_index(hash, index)
+		metrics::observe(
+			self.metrics.as_deref(),
+			"eth_getUncleByBlockHashAndIndex",
+			|| self.uncle_by_block_hash_and_index(hash, index),
+		)
 	}
 
 	fn uncle_by_block_number_and_index(
@@ -393,7 +456,11 @@ where
 		number_or_hash: BlockNumberOrHash,
 		index: Index,
 	) -> RpcResult<Option<RichBlock>> {
-		self.uncle_by_block_number_and_index(number_or_hash, index)
+		metrics::observe(
+			self.metrics.as_deref(),
+			"eth_getUncleByBlockNumberAndIndex",
+			|| self.uncle_by_block_number_and_index(number_or_hash, index),
+		)
 	}
 
 	// ########################################################################
@@ -401,7 +468,12 @@ where
 	// ########################################################################
 
 	async fn transaction_by_hash(&self, hash: H256) -> RpcResult<Option<Transaction>> {
-		self.transaction_by_hash(hash).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getTransactionByHash",
+			self.transaction_by_hash(hash),
+		)
+		.await
 	}
 
 	async fn transaction_by_block_hash_and_index(
@@ -409,7 +481,12 @@ where
 		hash: H256,
 		index: Index,
 	) -> RpcResult<Option<Transaction>> {
-		self.transaction_by_block_hash_and_index(hash, index).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getTransactionByBlockHashAndIndex",
+			self.transaction_by_block_hash_and_index(hash, index),
+		)
+		.await
 	}
 
 	async fn transaction_by_block_number_and_index(
@@ -417,13 +494,24 @@ where
 		number_or_hash: BlockNumberOrHash,
 		index: Index,
 	) -> RpcResult<Option<Transaction>> {
-		self.transaction_by_block_number_and_index(number_or_hash, index)
-			.await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getTransactionByBlockNumberAndIndex",
+			self.transaction_by_block_number_and_index(number_or_hash, index),
+		)
+		.await
 	}
 
 	async fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<Receipt>> {
-		let (block_info, index) = self.block_info_by_eth_transaction_hash(hash).await?;
-		self.transaction_receipt(&block_info, hash, index).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getTransactionReceipt",
+			async {
+				let (block_info, index) = self.block_info_by_eth_transaction_hash(hash).await?;
+				self.transaction_receipt(&block_info, hash, index).await
+			},
+		)
+		.await
 	}
 
 	// ########################################################################
@@ -435,7 +523,12 @@ where
 		address: H160,
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<U256> {
-		self.balance(address, number_or_hash).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getBalance",
+			self.balance(address, number_or_hash),
+		)
+		.await
 	}
 
 	async fn storage_at(
@@ -444,7 +537,12 @@ where
 		index: U256,
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<H256> {
-		self.storage_at(address, index, number_or_hash).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getStorageAt",
+			self.storage_at(address, index, number_or_hash),
+		)
+		.await
 	}
 
 	async fn transaction_count(
@@ -452,7 +550,12 @@ where
 		address: H160,
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<U256> {
-		self.transaction_count(address, number_or_hash).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getTransactionCount",
+			self.transaction_count(address, number_or_hash),
+		)
+		.await
 	}
 
 	async fn code_at(
@@ -460,7 +563,26 @@ where
 		address: H160,
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<Bytes> {
-		self.code_at(address, number_or_hash).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getCode",
+			self.code_at(address, number_or_hash),
+		)
+		.await
+	}
+
+	async fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<EthAccount> {
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_getProof",
+			self.proof(address, storage_keys, number_or_hash),
+		)
+		.await
 	}
 
 	// ########################################################################
@@ -473,7 +595,12 @@ where
 		number_or_hash: Option<BlockNumberOrHash>,
 		state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
 	) -> RpcResult<Bytes> {
-		self.call(request, number_or_hash, state_overrides).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_call",
+			self.call(request, number_or_hash, state_overrides),
+		)
+		.await
 	}
 
 	async fn estimate_gas(
@@ -481,7 +608,42 @@ where
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<U256> {
-		self.estimate_gas(request, number_or_hash).await
+		let result = metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_estimateGas",
+			self.estimate_gas(request, number_or_hash),
+		)
+		.await;
+		if let (Some(metrics), Ok(gas)) = (&self.metrics, &result) {
+			metrics.observe_gas("eth_estimateGas", *gas);
+		}
+		result
+	}
+
+	async fn create_access_list(
+		&self,
+		request: TransactionRequest,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<AccessListResult> {
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_createAccessList",
+			self.create_access_list(request, number_or_hash),
+		)
+		.await
+	}
+
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>> {
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_simulateV1",
+			self.simulate_v1(payload, number_or_hash),
+		)
+		.await
 	}
 
 	// ########################################################################
@@ -489,7 +651,7 @@ where
 	// ########################################################################
 
 	fn gas_price(&self) -> RpcResult<U256> {
-		self.gas_price()
+		metrics::observe(self.metrics.as_deref(), "eth_gasPrice", || self.gas_price())
 	}
 
 	async fn fee_history(
@@ -498,12 +660,18 @@ where
 		newest_block: BlockNumberOrHash,
 		reward_percentiles: Option<Vec<f64>>,
 	) -> RpcResult<FeeHistory> {
-		self.fee_history(block_count, newest_block, reward_percentiles)
-			.await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_feeHistory",
+			self.fee_history(block_count, newest_block, reward_percentiles),
+		)
+		.await
 	}
 
 	fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
-		self.max_priority_fee_per_gas()
+		metrics::observe(self.metrics.as_deref(), "eth_maxPriorityFeePerGas", || {
+			self.max_priority_fee_per_gas()
+		})
 	}
 
 	// ########################################################################
@@ -511,23 +679,27 @@ where
 	// ########################################################################
 
 	fn is_mining(&self) -> RpcResult<bool> {
-		self.is_mining()
+		metrics::observe(self.metrics.as_deref(), "eth_mining", || self.is_mining())
 	}
 
 	fn hashrate(&self) -> RpcResult<U256> {
-		self.hashrate()
+		metrics::observe(self.metrics.as_deref(), "eth_hashrate", || self.hashrate())
 	}
 
 	fn work(&self) -> RpcResult<Work> {
-		self.work()
+		metrics::observe(self.metrics.as_deref(), "eth_getWork", || self.work())
 	}
 
 	fn submit_hashrate(&self, hashrate: U256, id: H256) -> RpcResult<bool> {
-		self.submit_hashrate(hashrate, id)
+		metrics::observe(self.metrics.as_deref(), "eth_submitHashrate", || {
+			self.submit_hashrate(hashrate, id)
+		})
 	}
 
 	fn submit_work(&self, nonce: H64, pow_hash: H256, mix_digest: H256) -> RpcResult<bool> {
-		self.submit_work(nonce, pow_hash, mix_digest)
+		metrics::observe(self.metrics.as_deref(), "eth_submitWork", || {
+			self.submit_work(nonce, pow_hash, mix_digest)
+		})
 	}
 
 	// ########################################################################
@@ -535,11 +707,21 @@ where
 	// ########################################################################
 
 	async fn send_transaction(&self, request: TransactionRequest) -> RpcResult<H256> {
-		self.send_transaction(request).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_sendTransaction",
+			self.send_transaction(request),
+		)
+		.await
 	}
 
 	async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256> {
-		self.send_raw_transaction(bytes).await
+		metrics::observe_async(
+			self.metrics.as_deref(),
+			"eth_sendRawTransaction",
+			self.send_raw_transaction(bytes),
+		)
+		.await
 	}
 }
 
@@ -20,19 +20,41 @@ use ethereum_types::{H160, H256, U256};
 use jsonrpsee::core::RpcResult;
 use codec::Encode;
 // Substrate
-use sc_client_api::backend::{Backend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	ProofProvider,
+};
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::hashing::keccak_256;
 use sp_inherents::CreateInherentDataProviders;
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 // Frontier
 use fc_rpc_core::types::*;
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::{eth::Eth, frontier_backend_client, internal_err};
+use crate::{
+	eth::{Eth, EthConfig},
+	frontier_backend_client, internal_err,
+};
+
+/// Storage keys proven in the `accountProof` of `eth_getProof`.
+///
+/// The `System::Account` entry holding the nonce and balance of an address depends on the
+/// `AddressMapping` of the runtime, so only the node can name it. The default implementation
+/// only proves the `EVM::AccountCodes` entry of the address.
+pub trait AccountProofKeys {
+	fn account_proof_keys(address: H160) -> Vec<Vec<u8>>;
+}
+
+impl AccountProofKeys for () {
+	fn account_proof_keys(address: H160) -> Vec<Vec<u8>> {
+		vec![fp_state_proof::keys::account_code(address)]
+	}
+}
 
 impl<B, C, P, CT, BE, A, CIDP, EC> Eth<B, C, P, CT, BE, A, CIDP, EC>
 where
@@ -204,4 +226,84 @@ where
 			Ok(Bytes(vec![]))
 		}
 	}
+
+	pub async fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<EthAccount>
+	where
+		C: ProofProvider<B>,
+		EC: EthConfig<B, C>,
+	{
+		let number_or_hash = number_or_hash.unwrap_or(BlockNumberOrHash::Latest);
+		if number_or_hash == BlockNumberOrHash::Pending {
+			return Err(internal_err("pending block has no proofs"));
+		}
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number_or_hash),
+		)
+		.await?
+		.ok_or_else(|| internal_err("header not found"))?;
+		let substrate_hash = self
+			.client
+			.expect_block_hash_from_id(&id)
+			.map_err(|_| internal_err(format!("Expect block number from id: {id}")))?;
+		let header = self
+			.client
+			.header(substrate_hash)
+			.map_err(|err| internal_err(format!("Fetch header failed: {err}")))?
+			.ok_or_else(|| internal_err("header not found"))?;
+
+		let account = self
+			.client
+			.runtime_api()
+			.account_basic(substrate_hash, address)
+			.map_err(|err| internal_err(format!("Fetch account failed: {err}")))?;
+		let code = self
+			.storage_override
+			.account_code_at(substrate_hash, address)
+			.unwrap_or_default();
+
+		let read_proof = |keys: &[Vec<u8>]| -> RpcResult<Vec<Bytes>> {
+			self.client
+				.read_proof(substrate_hash, &mut keys.iter().map(|key| key.as_slice()))
+				.map(|proof| proof.into_iter_nodes().map(Bytes::new).collect())
+				.map_err(|err| internal_err(format!("Fetch read proof failed: {err}")))
+		};
+
+		let account_proof = read_proof(&EC::AccountProofKeys::account_proof_keys(address))?;
+		let storage_proof = storage_keys
+			.into_iter()
+			.map(|key| {
+				let mut index = [0u8; 32];
+				key.to_big_endian(&mut index);
+				let value = self
+					.storage_override
+					.account_storage_at(substrate_hash, address, key)
+					.unwrap_or_default();
+				Ok(StorageProof {
+					key,
+					value: U256::from_big_endian(value.as_bytes()),
+					proof: read_proof(&[fp_state_proof::keys::account_storage(
+						address,
+						H256(index),
+					)])?,
+				})
+			})
+			.collect::<RpcResult<Vec<_>>>()?;
+
+		Ok(EthAccount {
+			address,
+			balance: account.balance,
+			nonce: account.nonce,
+			code_hash: H256(keccak_256(&code)),
+			storage_hash: H256::from_slice(header.state_root().as_ref()),
+			account_proof,
+			storage_proof,
+		})
+	}
 }
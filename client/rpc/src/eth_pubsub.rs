@@ -18,13 +18,14 @@
 
 use std::{marker::PhantomData, sync::Arc};
 
-use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum::{TransactionAction, TransactionV2 as EthereumTransaction};
+use ethereum_types::{H160, H256};
 use futures::{future, FutureExt as _, StreamExt as _};
 use jsonrpsee::{core::traits::IdProvider, server::PendingSubscriptionSink};
 // Substrate
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
-	client::BlockchainEvents,
+	client::{BlockchainEvents, FinalityNotification},
 };
 use sc_network_sync::SyncingService;
 use sc_rpc::{
@@ -35,12 +36,15 @@ use sc_transaction_pool_api::{InPoolTransaction, TransactionPool, TxHash};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_consensus::SyncOracle;
+use sp_core::hashing::keccak_256;
 use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
 // Frontier
 use fc_mapping_sync::{EthereumBlockNotification, EthereumBlockNotificationSinks};
 use fc_rpc_core::{
 	types::{
-		pubsub::{Kind, Params, PubSubResult, PubSubSyncing, SyncingStatus},
+		pubsub::{
+			Kind, Params, PendingTransactionFilter, PubSubResult, PubSubSyncing, SyncingStatus,
+		},
 		FilteredParams,
 	},
 	EthPubSubApiServer,
@@ -48,6 +52,8 @@ use fc_rpc_core::{
 use fc_storage::StorageOverride;
 use fp_rpc::EthereumRuntimeRPCApi;
 
+use crate::public_key;
+
 #[derive(Debug)]
 pub struct EthereumSubIdProvider;
 impl IdProvider for EthereumSubIdProvider {
@@ -128,29 +134,102 @@ where
 		future::ready(res.map(PubSubResult::header))
 	}
 
+	fn notify_finalized_headers(
+		&self,
+		notification: FinalityNotification<B>,
+	) -> future::Ready<impl Iterator<Item = PubSubResult>> {
+		let headers = finalized_hashes(&notification)
+			.filter_map(|hash| self.storage_override.current_block(hash))
+			.map(PubSubResult::header)
+			.collect::<Vec<_>>();
+		future::ready(headers.into_iter())
+	}
+
 	fn notify_logs(
 		&self,
 		notification: EthereumBlockNotification<B>,
 		params: &FilteredParams,
 	) -> future::Ready<Option<impl Iterator<Item = PubSubResult>>> {
 		let res = if notification.is_new_best {
-			let substrate_hash = notification.hash;
+			let mut logs = Vec::new();
+			// Like geth, first report the logs of retracted blocks as removed, oldest first, then
+			// those of the blocks enacted before the new best block.
+			if let Some(reorg_info) = &notification.reorg_info {
+				for hash in reorg_info.retracted.iter().rev() {
+					logs.extend(self.block_logs(*hash, params, true));
+				}
+				for hash in &reorg_info.enacted {
+					logs.extend(self.block_logs(*hash, params, false));
+				}
+			}
+			logs.extend(self.block_logs(notification.hash, params, false));
+			Some(logs.into_iter())
+		} else {
+			None
+		};
+		future::ready(res)
+	}
 
-			let block = self.storage_override.current_block(substrate_hash);
-			let receipts = self.storage_override.current_receipts(substrate_hash);
+	fn block_logs(
+		&self,
+		substrate_hash: B::Hash,
+		params: &FilteredParams,
+		removed: bool,
+	) -> Vec<PubSubResult> {
+		let block = self.storage_override.current_block(substrate_hash);
+		let receipts = self.storage_override.current_receipts(substrate_hash);
 
-			match (block, receipts) {
-				(Some(block), Some(receipts)) => Some((block, receipts)),
-				_ => None,
+		match (block, receipts) {
+			(Some(block), Some(receipts)) => {
+				PubSubResult::logs(block, receipts, params, removed).collect()
 			}
+			_ => Vec::new(),
+		}
+	}
+
+	fn notify_pending_transaction(
+		&self,
+		hash: &TxHash<P>,
+		filter: &PendingTransactionFilter,
+	) -> future::Ready<Option<PubSubResult>> {
+		let res = self
+			.pending_transaction(hash)
+			.and_then(|tx| Self::filter_pending_transaction(tx, filter));
+		future::ready(res)
+	}
+
+	fn filter_pending_transaction(
+		tx: EthereumTransaction,
+		filter: &PendingTransactionFilter,
+	) -> Option<PubSubResult> {
+		let from = if filter.requires_sender() {
+			let pk = public_key(&tx).ok()?;
+			H160::from(H256::from(keccak_256(&pk)))
 		} else {
-			None
+			H160::default()
+		};
+		let action = match &tx {
+			EthereumTransaction::Legacy(t) => &t.action,
+			EthereumTransaction::EIP2930(t) => &t.action,
+			EthereumTransaction::EIP1559(t) => &t.action,
 		};
-		future::ready(res.map(|(block, receipts)| PubSubResult::logs(block, receipts, params)))
+		let to = match action {
+			TransactionAction::Call(to) => Some(*to),
+			TransactionAction::Create => None,
+		};
+
+		if !filter.matches(&from, to.as_ref()) {
+			return None;
+		}
+		Some(if filter.hashes_only {
+			PubSubResult::transaction_hash(&tx)
+		} else {
+			PubSubResult::transaction(from, &tx)
+		})
 	}
 
-	fn pending_transaction(&self, hash: &TxHash<P>) -> future::Ready<Option<PubSubResult>> {
-		let res = if let Some(xt) = self.pool.ready_transaction(hash) {
+	fn pending_transaction(&self, hash: &TxHash<P>) -> Option<EthereumTransaction> {
+		if let Some(xt) = self.pool.ready_transaction(hash) {
 			let best_block = self.client.info().best_hash;
 
 			let api = self.client.runtime_api();
@@ -160,7 +239,7 @@ where
 			{
 				api_version
 			} else {
-				return future::ready(None);
+				return None;
 			};
 
 			let xts = vec![xt.data().clone()];
@@ -188,8 +267,7 @@ where
 			}
 		} else {
 			None
-		};
-		future::ready(res.map(|tx| PubSubResult::transaction_hash(&tx)))
+		}
 	}
 
 	async fn syncing_status(&self) -> PubSubSyncing {
@@ -211,6 +289,18 @@ where
 	}
 }
 
+/// Blocks finalized by a finality notification: those finalized implicitly come first, oldest
+/// first, then the newly finalized head.
+fn finalized_hashes<B: BlockT>(
+	notification: &FinalityNotification<B>,
+) -> impl Iterator<Item = B::Hash> + '_ {
+	notification
+		.tree_route
+		.iter()
+		.copied()
+		.chain(std::iter::once(notification.hash))
+}
+
 impl<B: BlockT, P, C, BE> EthPubSubApiServer for EthPubSub<B, P, C, BE>
 where
 	B: BlockT,
@@ -222,9 +312,17 @@ where
 	BE: Backend<B> + 'static,
 {
 	fn subscribe(&self, pending: PendingSubscriptionSink, kind: Kind, params: Option<Params>) {
-		let filtered_params = match params {
-			Some(Params::Logs(filter)) => FilteredParams::new(Some(filter)),
-			_ => FilteredParams::default(),
+		let (filtered_params, pending_transaction_filter) = match params {
+			Some(Params::Logs(filter)) => (FilteredParams::new(Some(filter)), None),
+			Some(Params::Bool(full_transactions)) => (
+				FilteredParams::default(),
+				Some(PendingTransactionFilter {
+					hashes_only: !full_transactions,
+					..Default::default()
+				}),
+			),
+			Some(Params::PendingTransactions(filter)) => (FilteredParams::default(), Some(filter)),
+			_ => (FilteredParams::default(), None),
 		};
 
 		let pubsub = self.clone();
@@ -240,6 +338,14 @@ where
 						.filter_map(move |notification| pubsub.notify_header(notification));
 					pipe_from_stream(pending, stream).await
 				}
+				Kind::FinalizedHeads => {
+					let stream = pubsub
+						.client
+						.finality_notification_stream()
+						.then(move |notification| pubsub.notify_finalized_headers(notification))
+						.flat_map(futures::stream::iter);
+					pipe_from_stream(pending, stream).await
+				}
 				Kind::Logs => {
 					let stream = block_notification_stream
 						.filter_map(move |notification| {
@@ -249,10 +355,12 @@ where
 					pipe_from_stream(pending, stream).await
 				}
 				Kind::NewPendingTransactions => {
+					let filter =
+						pending_transaction_filter.unwrap_or_else(PendingTransactionFilter::hashes);
 					let pool = pubsub.pool.clone();
 					let stream = pool
 						.import_notification_stream()
-						.filter_map(move |hash| pubsub.pending_transaction(&hash));
+						.filter_map(move |hash| pubsub.notify_pending_transaction(&hash, &filter));
 					pipe_from_stream(pending, stream).await;
 				}
 				Kind::Syncing => {
@@ -291,3 +399,58 @@ where
 			.spawn("frontier-rpc-subscription", Some("rpc"), fut);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use futures::{executor, StreamExt as _};
+	use sc_block_builder::BlockBuilderBuilder;
+	use sp_consensus::BlockOrigin;
+	use substrate_test_runtime_client::{
+		prelude::*, runtime::Block, DefaultTestClientBuilderExt, TestClientBuilder,
+	};
+
+	use super::*;
+
+	fn import_blocks(client: &mut Arc<TestClient>, count: usize) -> Vec<<Block as BlockT>::Hash> {
+		(0..count)
+			.map(|_| {
+				let chain = client.chain_info();
+				let block = BlockBuilderBuilder::new(&**client)
+					.on_parent_block(chain.best_hash)
+					.with_parent_block_number(chain.best_number)
+					.build()
+					.unwrap()
+					.build()
+					.unwrap()
+					.block;
+				let hash = block.header.hash();
+				executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+				hash
+			})
+			.collect()
+	}
+
+	#[test]
+	fn finalized_heads_are_reported_oldest_first() {
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+		let mut client = Arc::new(client);
+		let mut finality_notifications = client.finality_notification_stream();
+
+		// Finalizing the third block implicitly finalizes the first two.
+		let hashes = import_blocks(&mut client, 3);
+		client.finalize_block(hashes[2], None).unwrap();
+		let notification = executor::block_on(finality_notifications.next()).unwrap();
+		assert_eq!(finalized_hashes(&notification).collect::<Vec<_>>(), hashes);
+
+		// Only the blocks finalized since are reported by the next notification.
+		let hashes = import_blocks(&mut client, 2);
+		client.finalize_block(hashes[1], None).unwrap();
+		let notification = executor::block_on(finality_notifications.next()).unwrap();
+		assert_eq!(finalized_hashes(&notification).collect::<Vec<_>>(), hashes);
+	}
+}
@@ -30,6 +30,7 @@ mod cache;
 mod debug;
 mod eth;
 mod eth_pubsub;
+mod metrics;
 mod net;
 mod signer;
 #[cfg(feature = "txpool")]
@@ -41,8 +42,12 @@ pub use self::txpool::TxPool;
 pub use self::{
 	cache::{EthBlockDataCacheTask, EthTask},
 	debug::Debug,
-	eth::{format, pending, EstimateGasAdapter, Eth, EthConfig, EthFilter},
+	eth::{
+		format, pending, AccountProofKeys, EstimateGasAdapter, Eth, EthConfig, EthFilter,
+		FilterLimits,
+	},
 	eth_pubsub::{EthPubSub, EthereumSubIdProvider},
+	metrics::EthMetrics,
 	net::Net,
 	signer::{EthDevSigner, EthSigner},
 	web3::Web3,
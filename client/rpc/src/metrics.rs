@@ -0,0 +1,157 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{future::Future, sync::Arc, time::Instant};
+
+use ethereum_types::U256;
+use jsonrpsee::core::RpcResult;
+use prometheus_endpoint::{
+	exponential_buckets, register, CounterVec, Gauge, HistogramOpts, HistogramVec, Opts,
+	PrometheusError, Registry, U64,
+};
+
+/// Prometheus metrics of the Ethereum RPC, shared by `Eth`, `EthFilter` and the
+/// background `EthTask`s.
+pub struct EthMetrics {
+	/// Latency of `eth_*` calls, labeled by method.
+	call_duration: HistogramVec,
+	/// Number of `eth_*` calls that returned an error, labeled by method.
+	call_errors: CounterVec<U64>,
+	/// Gas used by `eth_call` and estimated by `eth_estimateGas`, labeled by method.
+	execution_gas: HistogramVec,
+	/// Number of filters currently installed in the filter pool.
+	filter_pool_size: Gauge<U64>,
+	/// Number of blocks currently held in the fee history cache.
+	fee_history_cache_size: Gauge<U64>,
+}
+
+impl EthMetrics {
+	/// Registers the metrics, logging and disabling them on failure.
+	pub fn new(prometheus_registry: Option<&Registry>) -> Option<Arc<Self>> {
+		let registry = prometheus_registry?;
+		match Self::register(registry) {
+			Ok(metrics) => Some(Arc::new(metrics)),
+			Err(e) => {
+				log::error!(target: "eth-metrics", "Failed to register metrics: {:?}", e);
+				None
+			}
+		}
+	}
+
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			call_duration: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"frontier_eth_rpc_call_duration_seconds",
+						"Duration of eth RPC calls.",
+					)
+					.buckets(exponential_buckets(0.001, 4.0, 9)?),
+					&["method"],
+				)?,
+				registry,
+			)?,
+			call_errors: register(
+				CounterVec::new(
+					Opts::new(
+						"frontier_eth_rpc_errors_total",
+						"Number of eth RPC calls that returned an error.",
+					),
+					&["method"],
+				)?,
+				registry,
+			)?,
+			execution_gas: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"frontier_eth_rpc_execution_gas",
+						"Gas used by eth_call and estimated by eth_estimateGas.",
+					)
+					.buckets(exponential_buckets(21_000.0, 2.0, 12)?),
+					&["method"],
+				)?,
+				registry,
+			)?,
+			filter_pool_size: register(
+				Gauge::new(
+					"frontier_eth_filter_pool_size",
+					"Number of filters installed in the eth filter pool.",
+				)?,
+				registry,
+			)?,
+			fee_history_cache_size: register(
+				Gauge::new(
+					"frontier_eth_fee_history_cache_size",
+					"Number of blocks held in the eth fee history cache.",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	fn observe_call<T>(&self, method: &'static str, started: Instant, result: &RpcResult<T>) {
+		self.call_duration
+			.with_label_values(&[method])
+			.observe(started.elapsed().as_secs_f64());
+		if result.is_err() {
+			self.call_errors.with_label_values(&[method]).inc();
+		}
+	}
+
+	pub(crate) fn observe_gas(&self, method: &'static str, gas: U256) {
+		self.execution_gas
+			.with_label_values(&[method])
+			.observe(u64::try_from(gas).unwrap_or(u64::MAX) as f64);
+	}
+
+	pub(crate) fn set_filter_pool_size(&self, size: usize) {
+		self.filter_pool_size.set(size as u64);
+	}
+
+	pub(crate) fn set_fee_history_cache_size(&self, size: usize) {
+		self.fee_history_cache_size.set(size as u64);
+	}
+}
+
+/// Runs a synchronous RPC method, recording its latency and outcome.
+pub(crate) fn observe<T>(
+	metrics: Option<&EthMetrics>,
+	method: &'static str,
+	call: impl FnOnce() -> RpcResult<T>,
+) -> RpcResult<T> {
+	let started = Instant::now();
+	let result = call();
+	if let Some(metrics) = metrics {
+		metrics.observe_call(method, started, &result);
+	}
+	result
+}
+
+/// Awaits an asynchronous RPC method, recording its latency and outcome.
+pub(crate) async fn observe_async<T>(
+	metrics: Option<&EthMetrics>,
+	method: &'static str,
+	call: impl Future<Output = RpcResult<T>>,
+) -> RpcResult<T> {
+	let started = Instant::now();
+	let result = call.await;
+	if let Some(metrics) = metrics {
+		metrics.observe_call(method, started, &result);
+	}
+	result
+}
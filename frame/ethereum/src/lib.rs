@@ -512,7 +512,8 @@ impl<T: Config> Pallet<T> {
 		.validate_in_pool_for(&who)
 		.and_then(|v| v.with_chain_id())
 		.and_then(|v| v.with_base_fee())
-		.and_then(|v| v.with_balance_for(&who))
+		.and_then(|v| Self::with_fee_balance_for(v, &origin, &who))
+		.and_then(|v| Self::with_create_allowed_for(v, &origin))
 		.map_err(|e| e.0)?;
 
 		// EIP-3607: https://eips.ethereum.org/EIPS/eip-3607
@@ -880,12 +881,58 @@ impl<T: Config> Pallet<T> {
 		.validate_in_block_for(&who)
 		.and_then(|v| v.with_chain_id())
 		.and_then(|v| v.with_base_fee())
-		.and_then(|v| v.with_balance_for(&who))
+		.and_then(|v| Self::with_fee_balance_for(v, &origin, &who))
+		.and_then(|v| Self::with_create_allowed_for(v, &origin))
 		.map_err(|e| TransactionValidityError::Invalid(e.0))?;
 
 		Ok(())
 	}
 
+	// Balance check of the sender. If the fee is sponsored by the configured
+	// `OnChargeTransaction`, the sender only needs to afford the transferred value.
+	fn with_fee_balance_for<'a, 'config>(
+		check: &'a CheckEvmTransaction<'config, InvalidTransactionWrapper>,
+		origin: &H160,
+		who: &pallet_evm::Account,
+	) -> Result<
+		&'a CheckEvmTransaction<'config, InvalidTransactionWrapper>,
+		InvalidTransactionWrapper,
+	> {
+		use pallet_evm::OnChargeEVMTransaction;
+
+		let fee = check.max_fee()?;
+		if <T as pallet_evm::Config>::OnChargeTransaction::is_fee_sponsored(
+			origin,
+			check.transaction.to,
+			&check.transaction.input,
+			fee,
+		) {
+			check.with_sponsored_balance_for(who)
+		} else {
+			check.with_balance_for(who)
+		}
+	}
+
+	// Deployment check of the sender. Creates the EVM pallet's `CreateFilter` would reject are
+	// kept out of the pool and of blocks.
+	fn with_create_allowed_for<'a, 'config>(
+		check: &'a CheckEvmTransaction<'config, InvalidTransactionWrapper>,
+		origin: &H160,
+	) -> Result<
+		&'a CheckEvmTransaction<'config, InvalidTransactionWrapper>,
+		InvalidTransactionWrapper,
+	> {
+		use pallet_evm::CreateFilter;
+
+		if check.transaction.to.is_none() {
+			let init_code_hash = H256::from(sp_io::hashing::keccak_256(&check.transaction.input));
+			if !<T as pallet_evm::Config>::CreateFilter::can_create(origin, Some(init_code_hash)) {
+				return Err(TransactionValidationError::DeploymentNotAllowed.into());
+			}
+		}
+		Ok(check)
+	}
+
 	pub fn migrate_block_v0_to_v2() -> Weight {
 		let db_weights = T::DbWeight::get();
 		let mut weight: Weight = db_weights.reads(1);
@@ -1022,6 +1069,9 @@ impl From<TransactionValidationError> for InvalidTransactionWrapper {
 			TransactionValidationError::UnknownError => InvalidTransactionWrapper(
 				InvalidTransaction::Custom(TransactionValidationError::UnknownError as u8),
 			),
+			TransactionValidationError::DeploymentNotAllowed => InvalidTransactionWrapper(
+				InvalidTransaction::Custom(TransactionValidationError::DeploymentNotAllowed as u8),
+			),
 		}
 	}
 }
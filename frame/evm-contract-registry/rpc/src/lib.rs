@@ -0,0 +1,142 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the EVM contract registry.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::{error::ErrorObject, ErrorObjectOwned},
+};
+use serde::{Deserialize, Serialize};
+// Substrate
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H160, H256};
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_evm_contract_registry_runtime_api::EvmContractRegistryApi as EvmContractRegistryRuntimeApi;
+
+/// Provenance of a deployed contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractRecord<BlockNumber> {
+	/// Address that deployed the contract.
+	pub deployer: H160,
+	/// Block in which the contract was deployed.
+	pub block_number: BlockNumber,
+	/// Keccak-256 hash of the init code of the contract.
+	pub init_code_hash: H256,
+	/// Verified source metadata hash attached by the deployer, if any.
+	pub metadata: Option<Bytes>,
+}
+
+#[rpc(client, server)]
+pub trait ContractRegistryApi<BlockHash, BlockNumber> {
+	/// Provenance of `contract`, or `None` if it has not been recorded.
+	#[method(name = "contractRegistry_contractInfo")]
+	fn contract_info(
+		&self,
+		contract: H160,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ContractRecord<BlockNumber>>>;
+
+	/// Recorded contracts deployed by `deployer`.
+	#[method(name = "contractRegistry_contractsOf")]
+	fn contracts_of(&self, deployer: H160, at: Option<BlockHash>) -> RpcResult<Vec<H160>>;
+}
+
+/// Provides RPC methods to query the EVM contract registry.
+pub struct ContractRegistry<C, B> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> ContractRegistry<C, B> {
+	/// Creates a new instance of the ContractRegistry Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(error: impl ToString, desc: &'static str) -> ErrorObjectOwned {
+	ErrorObject::owned(Error::RuntimeError.into(), desc, Some(error.to_string()))
+}
+
+impl<C, Block, BlockNumber> ContractRegistryApiServer<<Block as BlockT>::Hash, BlockNumber>
+	for ContractRegistry<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: EvmContractRegistryRuntimeApi<Block, BlockNumber>,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+{
+	fn contract_info(
+		&self,
+		contract: H160,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<ContractRecord<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let Some(info) = api
+			.contract_info(at_hash, contract)
+			.map_err(|e| runtime_error(e, "Unable to query contract info."))?
+		else {
+			return Ok(None);
+		};
+		let metadata = api
+			.metadata_of(at_hash, contract)
+			.map_err(|e| runtime_error(e, "Unable to query contract metadata."))?;
+
+		Ok(Some(ContractRecord {
+			deployer: info.deployer,
+			block_number: info.block,
+			init_code_hash: info.init_code_hash,
+			metadata: metadata.map(Into::into),
+		}))
+	}
+
+	fn contracts_of(&self, deployer: H160, at: Option<Block::Hash>) -> RpcResult<Vec<H160>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.contracts_of(at_hash, deployer)
+			.map_err(|e| runtime_error(e, "Unable to query deployed contracts."))
+	}
+}
@@ -0,0 +1,40 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API for querying the EVM contract registry.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+use sp_core::H160;
+
+pub use pallet_evm_contract_registry::ContractInfo;
+
+sp_api::decl_runtime_apis! {
+	pub trait EvmContractRegistryApi<BlockNumber> where BlockNumber: Codec {
+		/// Deployer, block and init code hash of `contract`, if it has been recorded.
+		fn contract_info(contract: H160) -> Option<ContractInfo<BlockNumber>>;
+		/// Verified source metadata hash attached to `contract`, if any.
+		fn metadata_of(contract: H160) -> Option<Vec<u8>>;
+		/// Recorded contracts deployed by `deployer`.
+		fn contracts_of(deployer: H160) -> Vec<H160>;
+	}
+}
@@ -0,0 +1,40 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, BenchmarkError};
+use frame_support::{traits::Get, BoundedVec};
+
+use super::*;
+
+const CONTRACT: H160 = H160::repeat_byte(0x22);
+
+benchmarks! {
+	set_metadata {
+		let (deployer, origin) = T::MetadataOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		<Pallet<T> as OnCreate<T>>::on_create(deployer, CONTRACT, H256::repeat_byte(0x33));
+		let metadata: BoundedVec<u8, T::MaxMetadataLength> =
+			alloc::vec![0xab; T::MaxMetadataLength::get() as usize].try_into().unwrap();
+	}: _<T::RuntimeOrigin>(origin, CONTRACT, metadata.clone())
+	verify {
+		assert_eq!(Metadata::<T>::get(CONTRACT), Some(metadata));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,205 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # EVM contract registry pallet
+//!
+//! Keeps an on-chain record of the contracts deployed on the EVM.
+//!
+//! The pallet is plugged into the EVM pallet as its `OnCreate` hook and records, for every
+//! contract deployed by a transaction or a `create`/`create2` extrinsic, the deployer, the block
+//! of the deployment and the hash of the init code. The deployer can then attach the hash of the
+//! verified source metadata of the contract, typically the IPFS CID of the Solidity metadata
+//! file, so that explorers can show where a contract comes from without scanning every block.
+//!
+//! Deployers sending Ethereum transactions attach metadata through the contract registry
+//! precompile, which checks that the EVM caller is the deployer.
+//!
+//! Contracts deployed by other contracts through the `CREATE`/`CREATE2` opcodes, and contracts
+//! deployed before the pallet was added, are not recorded: `OnCreate` only runs for the
+//! deployment of a transaction or extrinsic, and recording factory deployments is out of scope.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+// Substrate
+use frame_support::BoundedVec;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::{H160, H256};
+use sp_runtime::RuntimeDebug;
+// Frontier
+use pallet_evm::{EnsureAddressOrigin, OnCreate};
+
+pub use self::{pallet::*, weights::WeightInfo};
+
+/// Provenance of a deployed contract.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct ContractInfo<BlockNumber> {
+	/// Address that deployed the contract.
+	pub deployer: H160,
+	/// Block in which the contract was deployed.
+	pub block: BlockNumber,
+	/// Keccak-256 hash of the init code of the contract.
+	pub init_code_hash: H256,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_evm::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Checks that an origin acts for the deployer of a contract.
+		type MetadataOrigin: EnsureAddressOrigin<Self::RuntimeOrigin>;
+		/// Maximum length of a metadata hash.
+		#[pallet::constant]
+		type MaxMetadataLength: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Provenance of the recorded contracts.
+	#[pallet::storage]
+	pub type Contracts<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, ContractInfo<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Contracts recorded for each deployer.
+	#[pallet::storage]
+	pub type DeployerContracts<T> =
+		StorageDoubleMap<_, Blake2_128Concat, H160, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// Verified source metadata hash attached to a contract by its deployer.
+	#[pallet::storage]
+	pub type Metadata<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, BoundedVec<u8, T::MaxMetadataLength>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A contract deployment has been recorded.
+		ContractRegistered {
+			contract: H160,
+			deployer: H160,
+			init_code_hash: H256,
+		},
+		/// The deployer of a contract has attached its metadata hash.
+		MetadataSet {
+			contract: H160,
+			metadata: BoundedVec<u8, T::MaxMetadataLength>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The contract has not been recorded.
+		UnknownContract,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Attach the hash of the verified source metadata of `contract`, replacing any previous
+		/// one.
+		///
+		/// The origin must act for the deployer of the contract, as checked by
+		/// [`Config::MetadataOrigin`]. Deployers calling from the EVM use the contract registry
+		/// precompile instead.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_metadata())]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			contract: H160,
+			metadata: BoundedVec<u8, T::MaxMetadataLength>,
+		) -> DispatchResult {
+			let info = Contracts::<T>::get(contract).ok_or(Error::<T>::UnknownContract)?;
+			T::MetadataOrigin::ensure_address_origin(&info.deployer, origin)?;
+
+			Self::do_set_metadata(contract, metadata);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Provenance of `contract`, if it has been recorded.
+	pub fn contract_info(contract: H160) -> Option<ContractInfo<BlockNumberFor<T>>> {
+		Contracts::<T>::get(contract)
+	}
+
+	/// Metadata hash attached to `contract`, if any.
+	pub fn metadata_of(contract: H160) -> Option<Vec<u8>> {
+		Metadata::<T>::get(contract).map(Into::into)
+	}
+
+	/// Attach `metadata` to `contract`, replacing any previous one.
+	///
+	/// The caller must have checked that it acts for the deployer of `contract`. Used by
+	/// [`Pallet::set_metadata`], and by the contract registry precompile for deployers calling
+	/// from the EVM, which `MetadataOrigin` does not cover.
+	pub fn do_set_metadata(contract: H160, metadata: BoundedVec<u8, T::MaxMetadataLength>) {
+		Metadata::<T>::insert(contract, &metadata);
+
+		Self::deposit_event(Event::MetadataSet { contract, metadata });
+	}
+
+	/// Recorded contracts deployed by `deployer`.
+	pub fn contracts_of(deployer: H160) -> Vec<H160> {
+		DeployerContracts::<T>::iter_key_prefix(deployer).collect()
+	}
+}
+
+impl<T: Config> OnCreate<T> for Pallet<T> {
+	fn on_create(owner: H160, contract: H160, init_code_hash: H256) {
+		// A `CREATE2` address can be reused once the previous contract self-destructed.
+		if let Some(previous) = Contracts::<T>::get(contract) {
+			DeployerContracts::<T>::remove(previous.deployer, contract);
+			Metadata::<T>::remove(contract);
+		}
+
+		let info = ContractInfo {
+			deployer: owner,
+			block: frame_system::Pallet::<T>::block_number(),
+			init_code_hash,
+		};
+		Contracts::<T>::insert(contract, info);
+		DeployerContracts::<T>::insert(owner, contract, ());
+
+		Self::deposit_event(Event::ContractRegistered {
+			contract,
+			deployer: owner,
+			init_code_hash,
+		});
+	}
+}
@@ -0,0 +1,215 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{assert_noop, assert_ok, traits::ConstU32, BoundedVec};
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchError;
+// Frontier
+use pallet_evm::{CreateInfo, Runner as _};
+
+use super::*;
+use crate::mock::*;
+
+// PUSH1 0 PUSH1 0 RETURN
+const INIT_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xf3];
+// PUSH1 0 PUSH1 0 REVERT
+const REVERTING_INIT_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+
+fn create(source: H160, init: Vec<u8>) -> CreateInfo {
+	<Test as pallet_evm::Config>::Runner::create(
+		source,
+		init,
+		U256::zero(),
+		1_000_000,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false, // non-transactional
+		false, // not validated
+		None,
+		None,
+		<Test as pallet_evm::Config>::config(),
+	)
+	.expect("create runs")
+}
+
+fn create2(source: H160, init: Vec<u8>, salt: H256) -> CreateInfo {
+	<Test as pallet_evm::Config>::Runner::create2(
+		source,
+		init,
+		salt,
+		U256::zero(),
+		1_000_000,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false, // non-transactional
+		false, // not validated
+		None,
+		None,
+		<Test as pallet_evm::Config>::config(),
+	)
+	.expect("create2 runs")
+}
+
+fn metadata(bytes: &[u8]) -> BoundedVec<u8, ConstU32<64>> {
+	bytes.to_vec().try_into().unwrap()
+}
+
+fn keccak(code: &[u8]) -> H256 {
+	H256::from(sp_io::hashing::keccak_256(code))
+}
+
+#[test]
+fn create_records_deployment() {
+	new_test_ext().execute_with(|| {
+		let info = create(DEPLOYER, INIT_CODE.to_vec());
+		assert!(info.exit_reason.is_succeed());
+		let contract = info.value;
+
+		assert_eq!(
+			ContractRegistry::contract_info(contract),
+			Some(ContractInfo {
+				deployer: DEPLOYER,
+				block: 1,
+				init_code_hash: keccak(&INIT_CODE),
+			})
+		);
+		assert_eq!(ContractRegistry::contracts_of(DEPLOYER), vec![contract]);
+		assert_eq!(ContractRegistry::metadata_of(contract), None);
+		System::assert_last_event(
+			Event::ContractRegistered {
+				contract,
+				deployer: DEPLOYER,
+				init_code_hash: keccak(&INIT_CODE),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn create2_records_deployment() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(7);
+		let info = create2(DEPLOYER, INIT_CODE.to_vec(), H256::repeat_byte(0x01));
+		assert!(info.exit_reason.is_succeed());
+
+		let recorded = ContractRegistry::contract_info(info.value).expect("recorded");
+		assert_eq!(recorded.deployer, DEPLOYER);
+		assert_eq!(recorded.block, 7);
+		assert_eq!(recorded.init_code_hash, keccak(&INIT_CODE));
+	});
+}
+
+#[test]
+fn failed_create_is_not_recorded() {
+	new_test_ext().execute_with(|| {
+		let info = create(DEPLOYER, REVERTING_INIT_CODE.to_vec());
+		assert!(!info.exit_reason.is_succeed());
+
+		assert_eq!(ContractRegistry::contract_info(info.value), None);
+		assert!(ContractRegistry::contracts_of(DEPLOYER).is_empty());
+	});
+}
+
+#[test]
+fn deployer_can_set_metadata() {
+	new_test_ext().execute_with(|| {
+		let contract = create(DEPLOYER, INIT_CODE.to_vec()).value;
+		let cid = metadata(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+
+		assert_ok!(ContractRegistry::set_metadata(
+			RuntimeOrigin::signed(DEPLOYER),
+			contract,
+			cid.clone()
+		));
+		assert_eq!(ContractRegistry::metadata_of(contract), Some(cid.to_vec()));
+		System::assert_last_event(
+			Event::MetadataSet {
+				contract,
+				metadata: cid,
+			}
+			.into(),
+		);
+
+		// The metadata can be replaced.
+		assert_ok!(ContractRegistry::set_metadata(
+			RuntimeOrigin::signed(DEPLOYER),
+			contract,
+			metadata(b"updated")
+		));
+		assert_eq!(
+			ContractRegistry::metadata_of(contract),
+			Some(b"updated".to_vec())
+		);
+	});
+}
+
+#[test]
+fn only_deployer_can_set_metadata() {
+	new_test_ext().execute_with(|| {
+		let contract = create(DEPLOYER, INIT_CODE.to_vec()).value;
+
+		assert_noop!(
+			ContractRegistry::set_metadata(RuntimeOrigin::signed(USER), contract, metadata(b"cid")),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ContractRegistry::set_metadata(RuntimeOrigin::root(), contract, metadata(b"cid")),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_metadata_fails_for_unknown_contract() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ContractRegistry::set_metadata(
+				RuntimeOrigin::signed(DEPLOYER),
+				H160::repeat_byte(0x99),
+				metadata(b"cid")
+			),
+			Error::<Test>::UnknownContract
+		);
+	});
+}
+
+#[test]
+fn redeployment_replaces_record() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::repeat_byte(0x44);
+		ContractRegistry::on_create(DEPLOYER, contract, H256::repeat_byte(0x01));
+		assert_ok!(ContractRegistry::set_metadata(
+			RuntimeOrigin::signed(DEPLOYER),
+			contract,
+			metadata(b"cid")
+		));
+
+		ContractRegistry::on_create(USER, contract, H256::repeat_byte(0x02));
+
+		let recorded = ContractRegistry::contract_info(contract).expect("recorded");
+		assert_eq!(recorded.deployer, USER);
+		assert_eq!(recorded.init_code_hash, H256::repeat_byte(0x02));
+		assert_eq!(ContractRegistry::metadata_of(contract), None);
+		assert!(ContractRegistry::contracts_of(DEPLOYER).is_empty());
+		assert_eq!(ContractRegistry::contracts_of(USER), vec![contract]);
+	});
+}
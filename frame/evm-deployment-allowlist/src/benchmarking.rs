@@ -0,0 +1,71 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, BenchmarkError};
+use frame_support::traits::EnsureOrigin;
+
+use super::*;
+
+const DEPLOYER: H160 = H160::repeat_byte(0x11);
+const CODE_HASH: H256 = H256::repeat_byte(0x22);
+
+fn manager_origin<T: Config>() -> Result<T::RuntimeOrigin, BenchmarkError> {
+	T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)
+}
+
+benchmarks! {
+	set_restricted {
+		let origin = manager_origin::<T>()?;
+	}: _<T::RuntimeOrigin>(origin, true)
+	verify {
+		assert!(Restricted::<T>::get());
+	}
+
+	allow_deployer {
+		let origin = manager_origin::<T>()?;
+	}: _<T::RuntimeOrigin>(origin, DEPLOYER)
+	verify {
+		assert!(AllowedDeployers::<T>::contains_key(DEPLOYER));
+	}
+
+	disallow_deployer {
+		let origin = manager_origin::<T>()?;
+		AllowedDeployers::<T>::insert(DEPLOYER, ());
+	}: _<T::RuntimeOrigin>(origin, DEPLOYER)
+	verify {
+		assert!(!AllowedDeployers::<T>::contains_key(DEPLOYER));
+	}
+
+	allow_code_hash {
+		let origin = manager_origin::<T>()?;
+	}: _<T::RuntimeOrigin>(origin, CODE_HASH)
+	verify {
+		assert!(AllowedCodeHashes::<T>::contains_key(CODE_HASH));
+	}
+
+	disallow_code_hash {
+		let origin = manager_origin::<T>()?;
+		AllowedCodeHashes::<T>::insert(CODE_HASH, ());
+	}: _<T::RuntimeOrigin>(origin, CODE_HASH)
+	verify {
+		assert!(!AllowedCodeHashes::<T>::contains_key(CODE_HASH));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
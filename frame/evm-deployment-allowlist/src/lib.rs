@@ -0,0 +1,215 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # EVM deployment allow-list pallet
+//!
+//! Restricts who can deploy EVM contracts.
+//!
+//! While deployments are restricted, a contract can only be created by an allowed deployer, or,
+//! for contracts deployed by a transaction, from allowed creation code. Both lists and the
+//! restriction itself are managed by [`Config::ManagerOrigin`], so that deployments can be opened
+//! gradually.
+//!
+//! The pallet is plugged into the EVM pallet as its `CreateFilter`, which is checked for
+//! transactions, `create` extrinsics and `CREATE`/`CREATE2` from contracts. Since the creation
+//! code of the latter is not known, they are only allowed for allowed deployers, typically
+//! factory contracts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use alloc::vec::Vec;
+use sp_core::{H160, H256};
+// Frontier
+use pallet_evm::CreateFilter;
+
+pub use self::{pallet::*, weights::WeightInfo};
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Origin allowed to manage the allow-lists and the restriction.
+		type ManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Whether deployments are restricted to the allow-lists.
+	#[pallet::storage]
+	pub type Restricted<T> = StorageValue<_, bool, ValueQuery>;
+
+	/// Addresses allowed to deploy contracts.
+	#[pallet::storage]
+	pub type AllowedDeployers<T> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// Creation code hashes anyone can deploy.
+	#[pallet::storage]
+	pub type AllowedCodeHashes<T> = StorageMap<_, Identity, H256, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T> {
+		pub restricted: bool,
+		pub deployers: Vec<H160>,
+		pub code_hashes: Vec<H256>,
+		#[serde(skip)]
+		pub _marker: PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			Restricted::<T>::put(self.restricted);
+			for deployer in &self.deployers {
+				AllowedDeployers::<T>::insert(deployer, ());
+			}
+			for code_hash in &self.code_hashes {
+				AllowedCodeHashes::<T>::insert(code_hash, ());
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Deployments have been restricted to the allow-lists, or opened to everyone.
+		RestrictionSet { restricted: bool },
+		/// An address has been allowed to deploy contracts.
+		DeployerAllowed { deployer: H160 },
+		/// An address is no longer allowed to deploy contracts.
+		DeployerDisallowed { deployer: H160 },
+		/// A creation code hash has been allowed.
+		CodeHashAllowed { code_hash: H256 },
+		/// A creation code hash is no longer allowed.
+		CodeHashDisallowed { code_hash: H256 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The deployer or code hash is already allowed.
+		AlreadyAllowed,
+		/// The deployer or code hash is not allowed.
+		NotAllowed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Restrict deployments to the allow-lists, or open them to everyone.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_restricted())]
+		pub fn set_restricted(origin: OriginFor<T>, restricted: bool) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			Restricted::<T>::put(restricted);
+
+			Self::deposit_event(Event::RestrictionSet { restricted });
+			Ok(())
+		}
+
+		/// Allow `deployer` to deploy contracts.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::allow_deployer())]
+		pub fn allow_deployer(origin: OriginFor<T>, deployer: H160) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ensure!(
+				!AllowedDeployers::<T>::contains_key(deployer),
+				Error::<T>::AlreadyAllowed
+			);
+
+			AllowedDeployers::<T>::insert(deployer, ());
+
+			Self::deposit_event(Event::DeployerAllowed { deployer });
+			Ok(())
+		}
+
+		/// Remove `deployer` from the allowed deployers. Its existing contracts are left as is.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::disallow_deployer())]
+		pub fn disallow_deployer(origin: OriginFor<T>, deployer: H160) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ensure!(
+				AllowedDeployers::<T>::contains_key(deployer),
+				Error::<T>::NotAllowed
+			);
+
+			AllowedDeployers::<T>::remove(deployer);
+
+			Self::deposit_event(Event::DeployerDisallowed { deployer });
+			Ok(())
+		}
+
+		/// Allow anyone to deploy contracts from the creation code hashing to `code_hash`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::allow_code_hash())]
+		pub fn allow_code_hash(origin: OriginFor<T>, code_hash: H256) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ensure!(
+				!AllowedCodeHashes::<T>::contains_key(code_hash),
+				Error::<T>::AlreadyAllowed
+			);
+
+			AllowedCodeHashes::<T>::insert(code_hash, ());
+
+			Self::deposit_event(Event::CodeHashAllowed { code_hash });
+			Ok(())
+		}
+
+		/// Remove `code_hash` from the allowed creation code hashes.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::disallow_code_hash())]
+		pub fn disallow_code_hash(origin: OriginFor<T>, code_hash: H256) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ensure!(
+				AllowedCodeHashes::<T>::contains_key(code_hash),
+				Error::<T>::NotAllowed
+			);
+
+			AllowedCodeHashes::<T>::remove(code_hash);
+
+			Self::deposit_event(Event::CodeHashDisallowed { code_hash });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> CreateFilter for Pallet<T> {
+	fn can_create(deployer: &H160, init_code_hash: Option<H256>) -> bool {
+		!Restricted::<T>::get()
+			|| AllowedDeployers::<T>::contains_key(deployer)
+			|| matches!(init_code_hash, Some(code_hash) if AllowedCodeHashes::<T>::contains_key(code_hash))
+	}
+}
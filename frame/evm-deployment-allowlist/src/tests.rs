@@ -0,0 +1,205 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{assert_noop, assert_ok};
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchError;
+// Frontier
+use pallet_evm::{CreateInfo, Runner as _};
+
+use super::*;
+use crate::mock::*;
+
+// PUSH1 0 PUSH1 0 PUSH1 0 CREATE PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+const FACTORY_CODE: [u8; 15] = [
+	0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf0, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+];
+
+fn create(source: H160, init: Vec<u8>) -> Result<CreateInfo, DispatchError> {
+	<Test as pallet_evm::Config>::Runner::create(
+		source,
+		init,
+		U256::zero(),
+		1_000_000,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false, // non-transactional
+		false, // not validated
+		None,
+		None,
+		<Test as pallet_evm::Config>::config(),
+	)
+	.map_err(|e| e.error.into())
+}
+
+// Address created by the factory when called by `USER`, zero if the deployment failed.
+fn create_from_factory() -> H160 {
+	pallet_evm::AccountCodes::<Test>::insert(FACTORY, FACTORY_CODE.to_vec());
+	let info = <Test as pallet_evm::Config>::Runner::call(
+		USER,
+		FACTORY,
+		Vec::new(),
+		U256::zero(),
+		1_000_000,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false, // non-transactional
+		false, // not validated
+		None,
+		None,
+		<Test as pallet_evm::Config>::config(),
+	)
+	.expect("call succeeds");
+	H160::from_slice(&info.value[12..])
+}
+
+#[test]
+fn manager_origin_is_required() {
+	new_test_ext().execute_with(|| {
+		let origin = RuntimeOrigin::signed(DEPLOYER);
+		assert_noop!(
+			DeploymentAllowlist::set_restricted(origin.clone(), false),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			DeploymentAllowlist::allow_deployer(origin.clone(), USER),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			DeploymentAllowlist::disallow_deployer(origin.clone(), DEPLOYER),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			DeploymentAllowlist::allow_code_hash(origin.clone(), H256::repeat_byte(1)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			DeploymentAllowlist::disallow_code_hash(origin, H256::repeat_byte(1)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn deployers_can_be_managed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			DeploymentAllowlist::allow_deployer(RuntimeOrigin::root(), DEPLOYER),
+			Error::<Test>::AlreadyAllowed
+		);
+		assert_noop!(
+			DeploymentAllowlist::disallow_deployer(RuntimeOrigin::root(), USER),
+			Error::<Test>::NotAllowed
+		);
+
+		assert_ok!(DeploymentAllowlist::allow_deployer(
+			RuntimeOrigin::root(),
+			USER
+		));
+		System::assert_last_event(Event::DeployerAllowed { deployer: USER }.into());
+		assert!(DeploymentAllowlist::can_create(&USER, None));
+
+		assert_ok!(DeploymentAllowlist::disallow_deployer(
+			RuntimeOrigin::root(),
+			USER
+		));
+		System::assert_last_event(Event::DeployerDisallowed { deployer: USER }.into());
+		assert!(!DeploymentAllowlist::can_create(&USER, None));
+	});
+}
+
+#[test]
+fn code_hashes_can_be_managed() {
+	new_test_ext().execute_with(|| {
+		let code_hash = H256::repeat_byte(1);
+
+		assert_ok!(DeploymentAllowlist::allow_code_hash(
+			RuntimeOrigin::root(),
+			code_hash
+		));
+		System::assert_last_event(Event::CodeHashAllowed { code_hash }.into());
+		assert_noop!(
+			DeploymentAllowlist::allow_code_hash(RuntimeOrigin::root(), code_hash),
+			Error::<Test>::AlreadyAllowed
+		);
+		assert!(DeploymentAllowlist::can_create(&USER, Some(code_hash)));
+		assert!(!DeploymentAllowlist::can_create(&USER, None));
+
+		assert_ok!(DeploymentAllowlist::disallow_code_hash(
+			RuntimeOrigin::root(),
+			code_hash
+		));
+		System::assert_last_event(Event::CodeHashDisallowed { code_hash }.into());
+		assert_noop!(
+			DeploymentAllowlist::disallow_code_hash(RuntimeOrigin::root(), code_hash),
+			Error::<Test>::NotAllowed
+		);
+		assert!(!DeploymentAllowlist::can_create(&USER, Some(code_hash)));
+	});
+}
+
+#[test]
+fn lifting_the_restriction_allows_everyone() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DeploymentAllowlist::set_restricted(
+			RuntimeOrigin::root(),
+			false
+		));
+		System::assert_last_event(Event::RestrictionSet { restricted: false }.into());
+
+		assert!(DeploymentAllowlist::can_create(&USER, None));
+		assert_ok!(create(USER, vec![0x00]));
+	});
+}
+
+#[test]
+fn transaction_deployments_are_filtered() {
+	new_test_ext().execute_with(|| {
+		// STOP
+		let init = vec![0x00];
+
+		assert_eq!(
+			create(USER, init.clone()).map(|_| ()),
+			Err(pallet_evm::Error::<Test>::DeploymentNotAllowed.into())
+		);
+		assert_ok!(create(DEPLOYER, init.clone()));
+
+		let code_hash = H256::from(sp_io::hashing::keccak_256(&init));
+		assert_ok!(DeploymentAllowlist::allow_code_hash(
+			RuntimeOrigin::root(),
+			code_hash
+		));
+		assert_ok!(create(USER, init));
+	});
+}
+
+#[test]
+fn contract_deployments_are_filtered() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(create_from_factory(), H160::zero());
+
+		assert_ok!(DeploymentAllowlist::allow_deployer(
+			RuntimeOrigin::root(),
+			FACTORY
+		));
+		assert_ne!(create_from_factory(), H160::zero());
+	});
+}
@@ -0,0 +1,36 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API for querying EVM fee sponsorship policies.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+use codec::Codec;
+use sp_core::H160;
+
+sp_api::decl_runtime_apis! {
+	pub trait EvmFeeSponsorshipApi<Balance> where Balance: Codec {
+		/// Policy sponsoring calls to `contract`, if any.
+		fn policy_of(contract: H160) -> Option<u32>;
+		/// Fees `user` can still get sponsored by a policy, or `None` if it does not exist or has
+		/// expired.
+		fn remaining_allowance(policy_id: u32, user: H160) -> Option<Balance>;
+		/// Balance of the pot of a policy, or `None` if it does not exist.
+		fn pot_balance(policy_id: u32) -> Option<Balance>;
+	}
+}
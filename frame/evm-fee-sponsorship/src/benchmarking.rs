@@ -0,0 +1,119 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use alloc::vec::Vec;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use super::*;
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	let _ = <T as pallet_evm::Config>::Currency::make_free_balance_be(
+		&caller,
+		BalanceOf::<T>::max_value() / 2u32.into(),
+	);
+	caller
+}
+
+fn targets<T: Config>(n: u32) -> BoundedVec<H160, T::MaxTargets> {
+	(0..n as u64)
+		.map(H160::from_low_u64_le)
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("n is bounded by MaxTargets; qed")
+}
+
+fn register<T: Config>(sponsor: &T::AccountId, n: u32) -> PolicyId {
+	let policy_id = NextPolicyId::<T>::get();
+	Pallet::<T>::register_policy(
+		RawOrigin::Signed(sponsor.clone()).into(),
+		targets::<T>(n),
+		BoundedVec::default(),
+		1_000u32.into(),
+		BlockNumberFor::<T>::max_value(),
+		1_000u32.into(),
+	)
+	.expect("policy registration succeeds; qed");
+	policy_id
+}
+
+benchmarks! {
+	register_policy {
+		let n in 1 .. T::MaxTargets::get();
+
+		let caller = funded_caller::<T>();
+		let targets = targets::<T>(n);
+		let selectors: BoundedVec<Selector, T::MaxSelectors> = (0..T::MaxSelectors::get())
+			.map(|i| i.to_be_bytes())
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("selectors are bounded by MaxSelectors; qed");
+	}: _(
+		RawOrigin::Signed(caller),
+		targets,
+		selectors,
+		1_000u32.into(),
+		BlockNumberFor::<T>::max_value(),
+		1_000u32.into()
+	)
+	verify {
+		assert!(Policies::<T>::contains_key(0));
+	}
+
+	deposit {
+		let caller = funded_caller::<T>();
+		let policy_id = register::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), policy_id, 1_000u32.into())
+	verify {
+		assert_eq!(Pallet::<T>::pot_balance(policy_id), Some(2_000u32.into()));
+	}
+
+	withdraw {
+		let caller = funded_caller::<T>();
+		let policy_id = register::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), policy_id, 500u32.into())
+	verify {
+		assert_eq!(Pallet::<T>::pot_balance(policy_id), Some(500u32.into()));
+	}
+
+	update_policy {
+		let caller = funded_caller::<T>();
+		let policy_id = register::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), policy_id, 2_000u32.into(), BlockNumberFor::<T>::max_value())
+	verify {
+		assert_eq!(
+			Policies::<T>::get(policy_id).map(|policy| policy.user_quota),
+			Some(2_000u32.into())
+		);
+	}
+
+	remove_policy {
+		let n in 1 .. T::MaxTargets::get();
+
+		let caller = funded_caller::<T>();
+		let policy_id = register::<T>(&caller, n);
+	}: _(RawOrigin::Signed(caller), policy_id)
+	verify {
+		assert!(!Policies::<T>::contains_key(policy_id));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
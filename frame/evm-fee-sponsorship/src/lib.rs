@@ -0,0 +1,562 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # EVM fee sponsorship pallet
+//!
+//! Lets sponsors pay the fees of EVM transactions sent to their contracts.
+//!
+//! A sponsor registers a [`SponsorshipPolicy`] covering a set of contracts, optionally restricted
+//! to some function selectors, with a per-user fee quota and an expiry block. Fees are drawn from
+//! the policy's pot, an EVM address funded by the sponsor.
+//!
+//! Sponsorship is applied by [`SponsoredFeeAdapter`], which wraps the runtime's
+//! `OnChargeEVMTransaction` implementation.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use scale_info::TypeInfo;
+// Substrate
+use frame_support::{
+	traits::{Currency, ExistenceRequirement, Get},
+	BoundedVec, CloneNoBound, EqNoBound, PalletId, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::{H160, U256};
+use sp_runtime::{
+	traits::{Saturating, UniqueSaturatedInto},
+	RuntimeDebug,
+};
+// Frontier
+use pallet_evm::{AddressMapping, OnChargeEVMTransaction};
+
+pub use self::{pallet::*, weights::WeightInfo};
+
+/// Identifier of a sponsorship policy.
+pub type PolicyId = u32;
+
+/// Four-byte function selector.
+pub type Selector = [u8; 4];
+
+/// Type alias for currency balance.
+pub type BalanceOf<T> = pallet_evm::BalanceOf<T>;
+
+/// A sponsor's commitment to pay the fees of transactions sent to some contracts.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct SponsorshipPolicy<T: Config> {
+	/// Account that registered the policy and owns its pot.
+	pub sponsor: T::AccountId,
+	/// Sponsored contracts.
+	pub targets: BoundedVec<H160, T::MaxTargets>,
+	/// Sponsored function selectors. Any call to a sponsored contract matches if empty.
+	pub selectors: BoundedVec<Selector, T::MaxSelectors>,
+	/// Total fees the policy pays on behalf of a single user.
+	pub user_quota: BalanceOf<T>,
+	/// Last block at which the policy applies.
+	pub expiry: BlockNumberFor<T>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_evm::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Used to derive the pot address of each policy.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Maximum number of contracts covered by a single policy.
+		#[pallet::constant]
+		type MaxTargets: Get<u32>;
+		/// Maximum number of selectors a policy can be restricted to.
+		#[pallet::constant]
+		type MaxSelectors: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Id of the next registered policy.
+	#[pallet::storage]
+	pub type NextPolicyId<T> = StorageValue<_, PolicyId, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Policies<T: Config> =
+		StorageMap<_, Twox64Concat, PolicyId, SponsorshipPolicy<T>, OptionQuery>;
+
+	/// Policy covering each sponsored contract.
+	#[pallet::storage]
+	pub type SponsoredContracts<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, PolicyId, OptionQuery>;
+
+	/// Fees already paid by a policy on behalf of each user.
+	#[pallet::storage]
+	pub type QuotaUsed<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PolicyId,
+		Blake2_128Concat,
+		H160,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A policy has been registered, with its pot funded by `deposit`.
+		PolicyRegistered {
+			policy_id: PolicyId,
+			sponsor: T::AccountId,
+			pot: H160,
+			deposit: BalanceOf<T>,
+		},
+		/// The quota or expiry of a policy has been updated.
+		PolicyUpdated {
+			policy_id: PolicyId,
+			user_quota: BalanceOf<T>,
+			expiry: BlockNumberFor<T>,
+		},
+		/// A policy has been removed and its pot refunded to the sponsor.
+		PolicyRemoved {
+			policy_id: PolicyId,
+			refunded: BalanceOf<T>,
+		},
+		/// Funds have been added to the pot of a policy.
+		Deposited {
+			policy_id: PolicyId,
+			amount: BalanceOf<T>,
+		},
+		/// Funds have been withdrawn from the pot of a policy.
+		Withdrawn {
+			policy_id: PolicyId,
+			amount: BalanceOf<T>,
+		},
+		/// The fee of a transaction from `user` has been paid by a policy.
+		FeeSponsored {
+			policy_id: PolicyId,
+			user: H160,
+			fee: BalanceOf<T>,
+		},
+		/// A call from `user` covered by a policy has been charged to `user`, as the policy could
+		/// no longer pay its fee.
+		FeeNotSponsored { policy_id: PolicyId, user: H160 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The policy does not exist.
+		PolicyNotFound,
+		/// The origin is not the sponsor of the policy.
+		NotSponsor,
+		/// A policy must cover at least one contract.
+		NoTargets,
+		/// One of the contracts is already covered by another policy.
+		ContractAlreadySponsored,
+		/// The expiry is not in the future.
+		InvalidExpiry,
+		/// No more policy ids are available.
+		PolicyIdOverflow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a policy sponsoring the fees of calls to `targets`, and fund its pot with
+		/// `deposit`.
+		///
+		/// Calls only match if their selector is in `selectors`, unless it is empty. Each user can
+		/// be sponsored up to `user_quota`, until block `expiry` included.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::register_policy(targets.len() as u32))]
+		pub fn register_policy(
+			origin: OriginFor<T>,
+			targets: BoundedVec<H160, T::MaxTargets>,
+			selectors: BoundedVec<Selector, T::MaxSelectors>,
+			user_quota: BalanceOf<T>,
+			expiry: BlockNumberFor<T>,
+			deposit: BalanceOf<T>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(!targets.is_empty(), Error::<T>::NoTargets);
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+			for target in targets.iter() {
+				ensure!(
+					!SponsoredContracts::<T>::contains_key(target),
+					Error::<T>::ContractAlreadySponsored
+				);
+			}
+
+			let policy_id = NextPolicyId::<T>::get();
+			let next_policy_id = policy_id
+				.checked_add(1)
+				.ok_or(Error::<T>::PolicyIdOverflow)?;
+
+			<T as pallet_evm::Config>::Currency::transfer(
+				&sponsor,
+				&Self::pot_account(policy_id),
+				deposit,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			for target in targets.iter() {
+				SponsoredContracts::<T>::insert(target, policy_id);
+			}
+			Policies::<T>::insert(
+				policy_id,
+				SponsorshipPolicy {
+					sponsor: sponsor.clone(),
+					targets,
+					selectors,
+					user_quota,
+					expiry,
+				},
+			);
+			NextPolicyId::<T>::put(next_policy_id);
+
+			Self::deposit_event(Event::PolicyRegistered {
+				policy_id,
+				sponsor,
+				pot: Self::pot_address(policy_id),
+				deposit,
+			});
+			Ok(())
+		}
+
+		/// Add `amount` to the pot of a policy. Only the sponsor can fund its pot.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::deposit())]
+		pub fn deposit(
+			origin: OriginFor<T>,
+			policy_id: PolicyId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_sponsor(policy_id, &who)?;
+
+			<T as pallet_evm::Config>::Currency::transfer(
+				&who,
+				&Self::pot_account(policy_id),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::Deposited { policy_id, amount });
+			Ok(())
+		}
+
+		/// Withdraw `amount` from the pot of a policy to its sponsor.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw())]
+		pub fn withdraw(
+			origin: OriginFor<T>,
+			policy_id: PolicyId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_sponsor(policy_id, &who)?;
+
+			<T as pallet_evm::Config>::Currency::transfer(
+				&Self::pot_account(policy_id),
+				&who,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			Self::deposit_event(Event::Withdrawn { policy_id, amount });
+			Ok(())
+		}
+
+		/// Update the per-user quota and the expiry of a policy.
+		///
+		/// Fees already sponsored keep counting against the new quota.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::update_policy())]
+		pub fn update_policy(
+			origin: OriginFor<T>,
+			policy_id: PolicyId,
+			user_quota: BalanceOf<T>,
+			expiry: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut policy = Self::ensure_sponsor(policy_id, &who)?;
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+
+			policy.user_quota = user_quota;
+			policy.expiry = expiry;
+			Policies::<T>::insert(policy_id, policy);
+
+			Self::deposit_event(Event::PolicyUpdated {
+				policy_id,
+				user_quota,
+				expiry,
+			});
+			Ok(())
+		}
+
+		/// Remove a policy and refund the remainder of its pot to the sponsor.
+		///
+		/// Policy ids are never reused, so the per-user usage of a removed policy is left as is.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_policy(T::MaxTargets::get()))]
+		pub fn remove_policy(origin: OriginFor<T>, policy_id: PolicyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let policy = Self::ensure_sponsor(policy_id, &who)?;
+
+			let pot = Self::pot_account(policy_id);
+			let refunded = <T as pallet_evm::Config>::Currency::free_balance(&pot);
+			<T as pallet_evm::Config>::Currency::transfer(
+				&pot,
+				&who,
+				refunded,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			for target in policy.targets.iter() {
+				SponsoredContracts::<T>::remove(target);
+			}
+			Policies::<T>::remove(policy_id);
+
+			Self::deposit_event(Event::PolicyRemoved {
+				policy_id,
+				refunded,
+			});
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// EVM address of the pot of a policy, from which its sponsored fees are drawn.
+	pub fn pot_address(policy_id: PolicyId) -> H160 {
+		let hash = sp_io::hashing::keccak_256(&(T::PalletId::get(), policy_id).encode());
+		H160::from_slice(&hash[12..])
+	}
+
+	/// Account id of the pot of a policy.
+	pub fn pot_account(policy_id: PolicyId) -> T::AccountId {
+		<T as pallet_evm::Config>::AddressMapping::into_account_id(Self::pot_address(policy_id))
+	}
+
+	/// Fees `user` can still get sponsored by a policy. `None` if the policy does not exist or
+	/// has expired.
+	pub fn remaining_allowance(policy_id: PolicyId, user: &H160) -> Option<BalanceOf<T>> {
+		let policy = Policies::<T>::get(policy_id)?;
+		if frame_system::Pallet::<T>::block_number() > policy.expiry {
+			return None;
+		}
+		Some(
+			policy
+				.user_quota
+				.saturating_sub(QuotaUsed::<T>::get(policy_id, user)),
+		)
+	}
+
+	/// Balance of the pot of a policy. `None` if the policy does not exist.
+	pub fn pot_balance(policy_id: PolicyId) -> Option<BalanceOf<T>> {
+		Policies::<T>::contains_key(policy_id).then(|| {
+			<T as pallet_evm::Config>::Currency::free_balance(&Self::pot_account(policy_id))
+		})
+	}
+
+	/// Policy paying a fee of up to `fee` for a transaction from `user` to `target` with `input`,
+	/// if any.
+	pub fn sponsoring_policy(
+		user: &H160,
+		target: Option<H160>,
+		input: &[u8],
+		fee: U256,
+	) -> Option<PolicyId>
+	where
+		U256: UniqueSaturatedInto<BalanceOf<T>>,
+	{
+		let policy_id = Self::covering_policy(target, input)?;
+
+		let fee: BalanceOf<T> = fee.unique_saturated_into();
+		let remaining = Self::remaining_allowance(policy_id, user)?;
+		let available =
+			<T as pallet_evm::Config>::Currency::free_balance(&Self::pot_account(policy_id));
+		if fee > remaining || fee > available {
+			return None;
+		}
+
+		Some(policy_id)
+	}
+
+	/// Policy covering a call to `target` with `input`, whether or not it can still pay its fee.
+	pub fn covering_policy(target: Option<H160>, input: &[u8]) -> Option<PolicyId> {
+		let policy_id = SponsoredContracts::<T>::get(target?)?;
+		let policy = Policies::<T>::get(policy_id)?;
+
+		if !policy.selectors.is_empty() {
+			let selector = input.get(0..4)?;
+			if !policy.selectors.iter().any(|s| &s[..] == selector) {
+				return None;
+			}
+		}
+
+		Some(policy_id)
+	}
+
+	fn ensure_sponsor(
+		policy_id: PolicyId,
+		who: &T::AccountId,
+	) -> Result<SponsorshipPolicy<T>, Error<T>> {
+		let policy = Policies::<T>::get(policy_id).ok_or(Error::<T>::PolicyNotFound)?;
+		if &policy.sponsor != who {
+			return Err(Error::<T>::NotSponsor);
+		}
+		Ok(policy)
+	}
+
+	fn note_sponsored_fee(sponsorship: &Sponsorship, fee: BalanceOf<T>) {
+		QuotaUsed::<T>::mutate(sponsorship.policy_id, sponsorship.user, |used| {
+			*used = used.saturating_add(fee)
+		});
+		Self::deposit_event(Event::FeeSponsored {
+			policy_id: sponsorship.policy_id,
+			user: sponsorship.user,
+			fee,
+		});
+	}
+}
+
+/// A fee withdrawn from the pot of a policy, kept until the fee is corrected.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub struct Sponsorship {
+	pub policy_id: PolicyId,
+	pub user: H160,
+	pub pot: H160,
+}
+
+/// Charges the fees of transactions matching a sponsorship policy to the policy's pot, and
+/// everything else to the sender, using `I` to withdraw, refund and deposit fees in both cases.
+pub struct SponsoredFeeAdapter<T, I>(PhantomData<(T, I)>);
+
+impl<T, I> OnChargeEVMTransaction<T> for SponsoredFeeAdapter<T, I>
+where
+	T: Config,
+	I: OnChargeEVMTransaction<T>,
+	U256: UniqueSaturatedInto<BalanceOf<T>>,
+{
+	type LiquidityInfo = (Option<Sponsorship>, I::LiquidityInfo);
+
+	fn withdraw_fee(who: &H160, fee: U256) -> Result<Self::LiquidityInfo, pallet_evm::Error<T>> {
+		I::withdraw_fee(who, fee).map(|paid| (None, paid))
+	}
+
+	fn withdraw_fee_for_call(
+		who: &H160,
+		target: Option<H160>,
+		input: &[u8],
+		fee: U256,
+	) -> Result<Self::LiquidityInfo, pallet_evm::Error<T>> {
+		match Pallet::<T>::sponsoring_policy(who, target, input, fee) {
+			Some(policy_id) => {
+				let pot = Pallet::<T>::pot_address(policy_id);
+				let paid = I::withdraw_fee(&pot, fee)?;
+				Ok((
+					Some(Sponsorship {
+						policy_id,
+						user: *who,
+						pot,
+					}),
+					paid,
+				))
+			}
+			// The call may have been validated as sponsored, without checking that `who` can pay
+			// the fee, so it is only charged to `who` if it still can.
+			None => match Pallet::<T>::covering_policy(target, input) {
+				Some(policy_id) => {
+					let paid = I::withdraw_fee(who, fee)
+						.map_err(|_| pallet_evm::Error::<T>::FeeSponsorshipUnavailable)?;
+					Pallet::<T>::deposit_event(Event::FeeNotSponsored {
+						policy_id,
+						user: *who,
+					});
+					Ok((None, paid))
+				}
+				None => Self::withdraw_fee(who, fee),
+			},
+		}
+	}
+
+	fn correct_and_deposit_fee(
+		who: &H160,
+		corrected_fee: U256,
+		base_fee: U256,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Self::LiquidityInfo {
+		match already_withdrawn {
+			(Some(sponsorship), paid) => {
+				Pallet::<T>::note_sponsored_fee(
+					&sponsorship,
+					corrected_fee.unique_saturated_into(),
+				);
+				let tip =
+					I::correct_and_deposit_fee(&sponsorship.pot, corrected_fee, base_fee, paid);
+				(Some(sponsorship), tip)
+			}
+			(None, paid) => (
+				None,
+				I::correct_and_deposit_fee(who, corrected_fee, base_fee, paid),
+			),
+		}
+	}
+
+	fn pay_priority_fee(tip: Self::LiquidityInfo) {
+		I::pay_priority_fee(tip.1)
+	}
+
+	fn is_fee_sponsored(who: &H160, target: Option<H160>, input: &[u8], fee: U256) -> bool {
+		Pallet::<T>::sponsoring_policy(who, target, input, fee).is_some()
+	}
+}
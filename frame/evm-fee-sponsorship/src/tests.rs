@@ -0,0 +1,341 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use sp_core::{H160, U256};
+
+use super::*;
+use crate::mock::*;
+
+const SELECTOR: Selector = [0xa9, 0x05, 0x9c, 0xbb];
+const GAS_LIMIT: u64 = 1_000_000;
+const GAS_PRICE: u64 = 1_000_000_000;
+const MAX_FEE: u64 = GAS_LIMIT * GAS_PRICE;
+
+fn register(selectors: Vec<Selector>, user_quota: u64, deposit: u64) -> PolicyId {
+	let policy_id = NextPolicyId::<Test>::get();
+	assert_ok!(FeeSponsorship::register_policy(
+		RuntimeOrigin::signed(SPONSOR),
+		vec![CONTRACT].try_into().unwrap(),
+		selectors.try_into().unwrap(),
+		user_quota,
+		100,
+		deposit,
+	));
+	policy_id
+}
+
+fn call(source: H160, input: Vec<u8>) {
+	assert_ok!(EVM::call(
+		RuntimeOrigin::root(),
+		source,
+		CONTRACT,
+		input,
+		U256::zero(),
+		GAS_LIMIT,
+		U256::from(GAS_PRICE),
+		None,
+		None,
+		Vec::new(),
+	));
+}
+
+#[test]
+fn register_policy_funds_pot() {
+	new_test_ext().execute_with(|| {
+		let policy_id = register(vec![], 10 * MAX_FEE, 100 * MAX_FEE);
+
+		assert_eq!(SponsoredContracts::<Test>::get(CONTRACT), Some(policy_id));
+		assert_eq!(FeeSponsorship::pot_balance(policy_id), Some(100 * MAX_FEE));
+		assert_eq!(
+			Balances::free_balance(SPONSOR),
+			INITIAL_BALANCE - 100 * MAX_FEE
+		);
+		System::assert_last_event(
+			Event::PolicyRegistered {
+				policy_id,
+				sponsor: SPONSOR,
+				pot: FeeSponsorship::pot_address(policy_id),
+				deposit: 100 * MAX_FEE,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn register_policy_rejects_invalid_policies() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeSponsorship::register_policy(
+				RuntimeOrigin::signed(SPONSOR),
+				vec![].try_into().unwrap(),
+				vec![].try_into().unwrap(),
+				1,
+				100,
+				0,
+			),
+			Error::<Test>::NoTargets
+		);
+		assert_noop!(
+			FeeSponsorship::register_policy(
+				RuntimeOrigin::signed(SPONSOR),
+				vec![CONTRACT].try_into().unwrap(),
+				vec![].try_into().unwrap(),
+				1,
+				1,
+				0,
+			),
+			Error::<Test>::InvalidExpiry
+		);
+
+		register(vec![], 1, 0);
+		assert_noop!(
+			FeeSponsorship::register_policy(
+				RuntimeOrigin::signed(SPONSOR),
+				vec![CONTRACT].try_into().unwrap(),
+				vec![].try_into().unwrap(),
+				1,
+				100,
+				0,
+			),
+			Error::<Test>::ContractAlreadySponsored
+		);
+	});
+}
+
+#[test]
+fn only_sponsor_can_manage_policy() {
+	new_test_ext().execute_with(|| {
+		let policy_id = register(vec![], 1, 0);
+
+		assert_noop!(
+			FeeSponsorship::withdraw(RuntimeOrigin::signed(USER), policy_id, 1),
+			Error::<Test>::NotSponsor
+		);
+		assert_noop!(
+			FeeSponsorship::update_policy(RuntimeOrigin::signed(USER), policy_id, 2, 100),
+			Error::<Test>::NotSponsor
+		);
+		assert_noop!(
+			FeeSponsorship::remove_policy(RuntimeOrigin::signed(USER), policy_id),
+			Error::<Test>::NotSponsor
+		);
+		assert_noop!(
+			FeeSponsorship::deposit(RuntimeOrigin::signed(SPONSOR), policy_id + 1, 1),
+			Error::<Test>::PolicyNotFound
+		);
+	});
+}
+
+#[test]
+fn deposit_and_withdraw_move_pot_funds() {
+	new_test_ext().execute_with(|| {
+		let policy_id = register(vec![], 1, 1_000);
+
+		assert_ok!(FeeSponsorship::deposit(
+			RuntimeOrigin::signed(SPONSOR),
+			policy_id,
+			500
+		));
+		assert_eq!(FeeSponsorship::pot_balance(policy_id), Some(1_500));
+
+		assert_ok!(FeeSponsorship::withdraw(
+			RuntimeOrigin::signed(SPONSOR),
+			policy_id,
+			1_200
+		));
+		assert_eq!(FeeSponsorship::pot_balance(policy_id), Some(300));
+		assert_eq!(Balances::free_balance(SPONSOR), INITIAL_BALANCE - 300);
+	});
+}
+
+#[test]
+fn remove_policy_refunds_pot() {
+	new_test_ext().execute_with(|| {
+		let policy_id = register(vec![], 1, 1_000);
+
+		assert_ok!(FeeSponsorship::remove_policy(
+			RuntimeOrigin::signed(SPONSOR),
+			policy_id
+		));
+
+		assert_eq!(Policies::<Test>::get(policy_id), None);
+		assert_eq!(SponsoredContracts::<Test>::get(CONTRACT), None);
+		assert_eq!(FeeSponsorship::pot_balance(policy_id), None);
+		assert_eq!(Balances::free_balance(SPONSOR), INITIAL_BALANCE);
+		System::assert_last_event(
+			Event::PolicyRemoved {
+				policy_id,
+				refunded: 1_000,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn sponsoring_policy_matches_selectors() {
+	new_test_ext().execute_with(|| {
+		let policy_id = register(vec![SELECTOR], MAX_FEE, MAX_FEE);
+		let fee = U256::from(MAX_FEE);
+
+		assert_eq!(
+			FeeSponsorship::sponsoring_policy(&USER, Some(CONTRACT), &SELECTOR, fee),
+			Some(policy_id)
+		);
+		assert_eq!(
+			FeeSponsorship::sponsoring_policy(&USER, Some(CONTRACT), &[0u8; 4], fee),
+			None
+		);
+		assert_eq!(
+			FeeSponsorship::sponsoring_policy(&USER, Some(CONTRACT), &[], fee),
+			None
+		);
+		assert_eq!(
+			FeeSponsorship::sponsoring_policy(&USER, Some(H160::zero()), &SELECTOR, fee),
+			None
+		);
+		assert_eq!(
+			FeeSponsorship::sponsoring_policy(&USER, None, &SELECTOR, fee),
+			None
+		);
+	});
+}
+
+#[test]
+fn sponsoring_policy_respects_quota_pot_and_expiry() {
+	new_test_ext().execute_with(|| {
+		let policy_id = register(vec![], MAX_FEE, 2 * MAX_FEE);
+
+		assert_eq!(
+			FeeSponsorship::sponsoring_policy(&USER, Some(CONTRACT), &[], U256::from(MAX_FEE)),
+			Some(policy_id)
+		);
+		assert_eq!(
+			FeeSponsorship::sponsoring_policy(&USER, Some(CONTRACT), &[], U256::from(MAX_FEE + 1)),
+			None
+		);
+
+		assert_ok!(FeeSponsorship::update_policy(
+			RuntimeOrigin::signed(SPONSOR),
+			policy_id,
+			4 * MAX_FEE,
+			100
+		));
+		assert_eq!(
+			FeeSponsorship::sponsoring_policy(&USER, Some(CONTRACT), &[], U256::from(3 * MAX_FEE)),
+			None
+		);
+
+		System::set_block_number(101);
+		assert_eq!(FeeSponsorship::remaining_allowance(policy_id, &USER), None);
+		assert_eq!(
+			FeeSponsorship::sponsoring_policy(&USER, Some(CONTRACT), &[], U256::from(1)),
+			None
+		);
+	});
+}
+
+#[test]
+fn sponsored_call_is_paid_from_pot() {
+	new_test_ext().execute_with(|| {
+		let policy_id = register(vec![], 10 * MAX_FEE, 10 * MAX_FEE);
+		assert_eq!(Balances::free_balance(USER), 0);
+
+		call(USER, Vec::new());
+
+		let used = QuotaUsed::<Test>::get(policy_id, USER);
+		assert_eq!(used, 21_000 * GAS_PRICE);
+		assert_eq!(Balances::free_balance(USER), 0);
+		assert_eq!(
+			FeeSponsorship::pot_balance(policy_id),
+			Some(10 * MAX_FEE - used)
+		);
+		assert_eq!(
+			FeeSponsorship::remaining_allowance(policy_id, &USER),
+			Some(10 * MAX_FEE - used)
+		);
+		System::assert_has_event(
+			Event::FeeSponsored {
+				policy_id,
+				user: USER,
+				fee: used,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn unsponsored_call_is_paid_by_sender() {
+	new_test_ext().execute_with(|| {
+		let policy_id = register(vec![SELECTOR], 10 * MAX_FEE, 10 * MAX_FEE);
+		let _ = Balances::deposit_creating(&USER, MAX_FEE);
+
+		call(USER, Vec::new());
+
+		assert_eq!(QuotaUsed::<Test>::get(policy_id, USER), 0);
+		assert_eq!(FeeSponsorship::pot_balance(policy_id), Some(10 * MAX_FEE));
+		assert_eq!(Balances::free_balance(USER), MAX_FEE - 21_000 * GAS_PRICE);
+	});
+}
+
+#[test]
+fn covered_call_falls_back_to_sender_that_can_pay() {
+	new_test_ext().execute_with(|| {
+		let policy_id = register(vec![], 0, 10 * MAX_FEE);
+		let _ = Balances::deposit_creating(&USER, MAX_FEE);
+
+		assert!(
+			<Test as pallet_evm::Config>::OnChargeTransaction::withdraw_fee_for_call(
+				&USER,
+				Some(CONTRACT),
+				&[],
+				U256::from(MAX_FEE),
+			)
+			.is_ok()
+		);
+
+		assert_eq!(Balances::free_balance(USER), 0);
+		assert_eq!(FeeSponsorship::pot_balance(policy_id), Some(10 * MAX_FEE));
+		System::assert_has_event(
+			Event::FeeNotSponsored {
+				policy_id,
+				user: USER,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn covered_call_fails_if_sender_cannot_pay() {
+	new_test_ext().execute_with(|| {
+		register(vec![], 0, 10 * MAX_FEE);
+
+		assert!(matches!(
+			<Test as pallet_evm::Config>::OnChargeTransaction::withdraw_fee_for_call(
+				&USER,
+				Some(CONTRACT),
+				&[],
+				U256::from(MAX_FEE),
+			),
+			Err(pallet_evm::Error::<Test>::FeeSponsorshipUnavailable)
+		));
+	});
+}
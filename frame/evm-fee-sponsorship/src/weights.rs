@@ -0,0 +1,118 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_evm_fee_sponsorship
+//!
+//! These are hand-written estimates from the storage accesses of each extrinsic, not benchmark
+//! results. Regenerate this file with the benchmark CLI
+//! (`benchmark pallet --pallet=pallet_evm_fee_sponsorship`) before relying on them.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_evm_fee_sponsorship.
+pub trait WeightInfo {
+	fn register_policy(n: u32, ) -> Weight;
+	fn deposit() -> Weight;
+	fn withdraw() -> Weight;
+	fn update_policy() -> Weight;
+	fn remove_policy(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_evm_fee_sponsorship using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn register_policy(n: u32, ) -> Weight {
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(38_000_000, 6_196)
+			.saturating_add(Weight::from_parts(2_900_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	fn deposit() -> Weight {
+		// Minimum execution time: 30_000_000 picoseconds.
+		Weight::from_parts(30_000_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn withdraw() -> Weight {
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(31_000_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn update_policy() -> Weight {
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3_706)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn remove_policy(n: u32, ) -> Weight {
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(36_000_000, 6_196)
+			.saturating_add(Weight::from_parts(1_800_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn register_policy(n: u32, ) -> Weight {
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(38_000_000, 6_196)
+			.saturating_add(Weight::from_parts(2_900_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	fn deposit() -> Weight {
+		// Minimum execution time: 30_000_000 picoseconds.
+		Weight::from_parts(30_000_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn withdraw() -> Weight {
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(31_000_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn update_policy() -> Weight {
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3_706)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn remove_policy(n: u32, ) -> Weight {
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(36_000_000, 6_196)
+			.saturating_add(Weight::from_parts(1_800_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+}
@@ -0,0 +1,367 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # EVM randomness pallet
+//!
+//! Exposes the randomness of the consensus engine to EVM contracts.
+//!
+//! [`Config::VrfRandomness`] and [`Config::EpochRandomness`] can be read at any time, and
+//! [`PrevRandao`] feeds the former to `pallet_evm` as the value of `PREVRANDAO`. As the author of a
+//! block knows its VRF output in advance, contracts that need unbiasable randomness should request
+//! it ahead of time instead: the VRF output of a future block is recorded once that block is
+//! produced, and the requester can then fulfil the request to get its result.
+//!
+//! A request that is not fulfilled within [`Config::ExpirationDelay`] blocks of its target block
+//! expires: it can no longer be fulfilled, and anyone can purge it to refund its deposit to the
+//! requester.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use scale_info::TypeInfo;
+// Substrate
+use frame_support::{
+	traits::{Currency, ExistenceRequirement, Get, Randomness},
+	CloneNoBound, EqNoBound, PalletId, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::{H160, H256};
+use sp_runtime::{
+	traits::{One, Saturating},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
+// Frontier
+use pallet_evm::AddressMapping;
+
+pub use self::pallet::*;
+
+/// Identifier of a randomness request.
+pub type RequestId = u64;
+
+/// Type alias for currency balance.
+pub type BalanceOf<T> = pallet_evm::BalanceOf<T>;
+
+/// Subject mixed into the randomness read from the configured sources.
+pub const RANDOMNESS_SUBJECT: &[u8] = b"evm-randomness";
+
+/// A pending request for the randomness of a future block.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct RandomnessRequest<T: Config> {
+	/// Address that made the request, the only one able to fulfil it.
+	pub requester: H160,
+	/// Salt mixed into the result, to derive different results from the same block.
+	pub salt: H256,
+	/// Block whose VRF output the result is derived from.
+	pub block: BlockNumberFor<T>,
+	/// Last block at which the request can be fulfilled.
+	pub expires: BlockNumberFor<T>,
+	/// Deposit locked until the request is fulfilled or purged.
+	pub deposit: BalanceOf<T>,
+}
+
+/// Randomness recorded for a block targeted by requests.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct BlockRandomness {
+	/// Randomness of the block, `None` until the block has been produced.
+	pub randomness: Option<H256>,
+	/// Number of requests not fulfilled yet.
+	pub requests: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_evm::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Randomness derived from the VRF output of the parent block, such as BABE's
+		/// `ParentBlockRandomness`.
+		type VrfRandomness: Randomness<Option<H256>, BlockNumberFor<Self>>;
+		/// Randomness of the current epoch, such as BABE's `RandomnessFromOneEpochAgo`. Used when
+		/// no VRF output is available.
+		type EpochRandomness: Randomness<H256, BlockNumberFor<Self>>;
+		/// Used to derive the address holding request deposits.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Deposit locked by each request until it is fulfilled or purged.
+		#[pallet::constant]
+		type Deposit: Get<BalanceOf<Self>>;
+		/// Minimum number of blocks between a request and its target block. At least one.
+		#[pallet::constant]
+		type MinBlockDelay: Get<u32>;
+		/// Maximum number of blocks between a request and its target block.
+		#[pallet::constant]
+		type MaxBlockDelay: Get<u32>;
+		/// Number of blocks after its target block during which a request can be fulfilled,
+		/// after which anyone can purge it. At least one.
+		#[pallet::constant]
+		type ExpirationDelay: Get<u32>;
+	}
+
+	/// Id of the next request.
+	#[pallet::storage]
+	pub type NextRequestId<T> = StorageValue<_, RequestId, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Requests<T: Config> =
+		StorageMap<_, Twox64Concat, RequestId, RandomnessRequest<T>, OptionQuery>;
+
+	/// Randomness of the blocks targeted by pending requests.
+	#[pallet::storage]
+	pub type RandomnessResults<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, BlockRandomness, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The randomness of `block` has been requested by `requester`.
+		RandomnessRequested {
+			request_id: RequestId,
+			requester: H160,
+			block: BlockNumberFor<T>,
+		},
+		/// The randomness of a block targeted by requests has been recorded.
+		RandomnessRecorded { block: BlockNumberFor<T> },
+		/// A request has been fulfilled and its deposit refunded.
+		RandomnessFulfilled { request_id: RequestId },
+		/// An expired request has been purged and its deposit refunded.
+		RequestPurged { request_id: RequestId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The target block is too close.
+		DelayTooShort,
+		/// The target block is too far.
+		DelayTooLong,
+		/// The request does not exist.
+		RequestNotFound,
+		/// The caller is not the requester.
+		NotRequester,
+		/// The randomness of the target block has not been recorded yet.
+		RandomnessNotReady,
+		/// No more request ids are available.
+		RequestIdOverflow,
+		/// The request has expired and can only be purged.
+		RequestExpired,
+		/// The request has not expired yet.
+		RequestNotExpired,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let parent = n.saturating_sub(One::one());
+			match RandomnessResults::<T>::get(parent) {
+				Some(mut result) if result.randomness.is_none() => {
+					result.randomness = Some(Self::parent_block_randomness(parent));
+					RandomnessResults::<T>::insert(parent, result);
+					Self::deposit_event(Event::RandomnessRecorded { block: parent });
+					T::DbWeight::get().reads_writes(3, 2)
+				}
+				_ => T::DbWeight::get().reads(1),
+			}
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// EVM address holding request deposits.
+	pub fn deposit_address() -> H160 {
+		let hash = sp_io::hashing::keccak_256(&T::PalletId::get().encode());
+		H160::from_slice(&hash[12..])
+	}
+
+	/// Account id holding request deposits.
+	pub fn deposit_account() -> T::AccountId {
+		<T as pallet_evm::Config>::AddressMapping::into_account_id(Self::deposit_address())
+	}
+
+	/// Randomness derived from the VRF output of the parent block, if any.
+	pub fn vrf_randomness() -> Option<H256> {
+		T::VrfRandomness::random(RANDOMNESS_SUBJECT).0
+	}
+
+	/// Randomness of the current epoch.
+	pub fn epoch_randomness() -> H256 {
+		T::EpochRandomness::random(RANDOMNESS_SUBJECT).0
+	}
+
+	/// Request the randomness of the block `delay` blocks from now, locking the deposit of
+	/// `requester`. The result is derived from the randomness of that block and `salt`.
+	pub fn request_randomness(
+		requester: H160,
+		salt: H256,
+		delay: u32,
+	) -> Result<RequestId, DispatchError> {
+		if delay < T::MinBlockDelay::get().max(1) {
+			return Err(Error::<T>::DelayTooShort.into());
+		}
+		if delay > T::MaxBlockDelay::get() {
+			return Err(Error::<T>::DelayTooLong.into());
+		}
+
+		let request_id = NextRequestId::<T>::get();
+		let next_request_id = request_id
+			.checked_add(1)
+			.ok_or(Error::<T>::RequestIdOverflow)?;
+
+		let deposit = T::Deposit::get();
+		<T as pallet_evm::Config>::Currency::transfer(
+			&<T as pallet_evm::Config>::AddressMapping::into_account_id(requester),
+			&Self::deposit_account(),
+			deposit,
+			ExistenceRequirement::AllowDeath,
+		)?;
+
+		let block = frame_system::Pallet::<T>::block_number().saturating_add(delay.into());
+		let expires = block.saturating_add(T::ExpirationDelay::get().max(1).into());
+		RandomnessResults::<T>::mutate(block, |result| {
+			let result = result.get_or_insert_with(Default::default);
+			result.requests = result.requests.saturating_add(1);
+		});
+		Requests::<T>::insert(
+			request_id,
+			RandomnessRequest {
+				requester,
+				salt,
+				block,
+				expires,
+				deposit,
+			},
+		);
+		NextRequestId::<T>::put(next_request_id);
+
+		Self::deposit_event(Event::RandomnessRequested {
+			request_id,
+			requester,
+			block,
+		});
+		Ok(request_id)
+	}
+
+	/// Whether a request can be fulfilled.
+	pub fn is_ready(request_id: RequestId) -> bool {
+		Requests::<T>::get(request_id)
+			.filter(|request| !Self::is_expired(request))
+			.and_then(|request| RandomnessResults::<T>::get(request.block))
+			.is_some_and(|result| result.randomness.is_some())
+	}
+
+	/// Fulfil a request of `requester`, returning its result and refunding its deposit.
+	pub fn fulfil_randomness(
+		requester: H160,
+		request_id: RequestId,
+	) -> Result<H256, DispatchError> {
+		let request = Requests::<T>::get(request_id).ok_or(Error::<T>::RequestNotFound)?;
+		if request.requester != requester {
+			return Err(Error::<T>::NotRequester.into());
+		}
+		if Self::is_expired(&request) {
+			return Err(Error::<T>::RequestExpired.into());
+		}
+		let randomness = RandomnessResults::<T>::get(request.block)
+			.and_then(|result| result.randomness)
+			.ok_or(Error::<T>::RandomnessNotReady)?;
+
+		Self::remove_request(request_id, &request)?;
+
+		Self::deposit_event(Event::RandomnessFulfilled { request_id });
+		Ok(sp_io::hashing::keccak_256(&(randomness, request.salt, request_id).encode()).into())
+	}
+
+	/// Purge an expired request, refunding its deposit to its requester. Can be called by anyone.
+	pub fn purge_expired_request(request_id: RequestId) -> DispatchResult {
+		let request = Requests::<T>::get(request_id).ok_or(Error::<T>::RequestNotFound)?;
+		if !Self::is_expired(&request) {
+			return Err(Error::<T>::RequestNotExpired.into());
+		}
+
+		Self::remove_request(request_id, &request)?;
+
+		Self::deposit_event(Event::RequestPurged { request_id });
+		Ok(())
+	}
+
+	/// Whether a request can no longer be fulfilled.
+	fn is_expired(request: &RandomnessRequest<T>) -> bool {
+		frame_system::Pallet::<T>::block_number() > request.expires
+	}
+
+	/// Refund the deposit of a request and remove it, along with the randomness of its target
+	/// block once no request targets it anymore.
+	fn remove_request(request_id: RequestId, request: &RandomnessRequest<T>) -> DispatchResult {
+		<T as pallet_evm::Config>::Currency::transfer(
+			&Self::deposit_account(),
+			&<T as pallet_evm::Config>::AddressMapping::into_account_id(request.requester),
+			request.deposit,
+			ExistenceRequirement::AllowDeath,
+		)?;
+
+		RandomnessResults::<T>::mutate_exists(request.block, |result| {
+			if let Some(inner) = result {
+				inner.requests = inner.requests.saturating_sub(1);
+				if inner.requests == 0 {
+					*result = None;
+				}
+			}
+		});
+		Requests::<T>::remove(request_id);
+		Ok(())
+	}
+
+	/// Randomness recorded for the parent block, falling back to the epoch randomness mixed with
+	/// its number when the block carries no VRF output.
+	fn parent_block_randomness(parent: BlockNumberFor<T>) -> H256 {
+		Self::vrf_randomness()
+			.unwrap_or_else(|| T::EpochRandomness::random(&(RANDOMNESS_SUBJECT, parent).encode()).0)
+	}
+}
+
+/// Randomness of the current block exposed to the EVM through `PREVRANDAO`, for use as
+/// `pallet_evm::Config::BlockRandomness`.
+pub struct PrevRandao<T>(PhantomData<T>);
+
+impl<T: Config> Get<Option<H256>> for PrevRandao<T> {
+	fn get() -> Option<H256> {
+		Pallet::<T>::vrf_randomness()
+	}
+}
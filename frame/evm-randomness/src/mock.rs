@@ -0,0 +1,228 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::str::FromStr;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, FindAuthor, Randomness},
+	weights::Weight,
+	PalletId,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	BuildStorage, ConsensusEngineId,
+};
+// Frontier
+use pallet_evm::{
+	EVMCurrencyAdapter, EnsureAddressNever, EnsureAddressRoot, FeeCalculator,
+	IdentityAddressMapping,
+};
+
+use crate as pallet_evm_randomness;
+
+frame_support::construct_runtime! {
+	pub enum Test {
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
+		EVM: pallet_evm::{Pallet, Call, Storage, Config<T>, Event<T>},
+		EvmRandomness: pallet_evm_randomness::{Pallet, Storage, Event<T>},
+	}
+}
+
+pub const REQUESTER: H160 = H160::repeat_byte(0x11);
+pub const INITIAL_BALANCE: u64 = 1_000_000;
+pub const DEPOSIT: u64 = 1_000;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default()
+		.build_storage()
+		.unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(REQUESTER, INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = H160;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = frame_system::mocking::MockBlock<Self>;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 0;
+}
+impl pallet_balances::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type WeightInfo = ();
+	type Balance = u64;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type ReserveIdentifier = [u8; 8];
+	type FreezeIdentifier = RuntimeFreezeReason;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type MaxFreezes = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1000;
+}
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+pub struct FixedGasPrice;
+impl FeeCalculator for FixedGasPrice {
+	fn min_gas_price() -> (U256, Weight) {
+		(1_000_000_000u128.into(), Weight::from_parts(7u64, 0))
+	}
+}
+
+pub struct FindAuthorTruncated;
+impl FindAuthor<H160> for FindAuthorTruncated {
+	fn find_author<'a, I>(_digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		Some(H160::from_str("1234500000000000000000000000000000000000").unwrap())
+	}
+}
+
+const BLOCK_GAS_LIMIT: u64 = 150_000_000;
+const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
+
+parameter_types! {
+	pub BlockGasLimit: U256 = U256::from(BLOCK_GAS_LIMIT);
+	pub const GasLimitPovSizeRatio: u64 = BLOCK_GAS_LIMIT.saturating_div(MAX_POV_SIZE);
+	pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
+	pub SuicideQuickClearLimit: u32 = 0;
+}
+impl pallet_evm::Config for Test {
+	type FeeCalculator = FixedGasPrice;
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+	type CallOrigin = EnsureAddressRoot<Self::AccountId>;
+	type WithdrawOrigin = EnsureAddressNever<Self::AccountId>;
+	type AddressMapping = IdentityAddressMapping;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type PrecompilesType = ();
+	type PrecompilesValue = ();
+	type ChainId = ();
+	type BlockGasLimit = BlockGasLimit;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type OnChargeTransaction = EVMCurrencyAdapter<Balances, ()>;
+	type OnCreate = ();
+	type CreateFilter = ();
+	type FindAuthor = FindAuthorTruncated;
+	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = ();
+	type SuicideQuickClearLimit = SuicideQuickClearLimit;
+	type Timestamp = Timestamp;
+	type BlockRandomness = crate::PrevRandao<Self>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	/// VRF output of the parent block, set by tests.
+	pub storage VrfOutput: Option<H256> = None;
+	pub storage EpochOutput: H256 = H256::repeat_byte(0xee);
+}
+
+pub struct MockVrfRandomness;
+impl Randomness<Option<H256>, BlockNumberFor<Test>> for MockVrfRandomness {
+	fn random(_subject: &[u8]) -> (Option<H256>, BlockNumberFor<Test>) {
+		(VrfOutput::get(), System::block_number().saturating_sub(1))
+	}
+}
+
+pub struct MockEpochRandomness;
+impl Randomness<H256, BlockNumberFor<Test>> for MockEpochRandomness {
+	fn random(subject: &[u8]) -> (H256, BlockNumberFor<Test>) {
+		let mut subject = subject.to_vec();
+		subject.extend_from_slice(EpochOutput::get().as_bytes());
+		(BlakeTwo256::hash(&subject), 0)
+	}
+}
+
+parameter_types! {
+	pub const RandomnessPalletId: PalletId = PalletId(*b"evm/rand");
+	pub const RequestDeposit: u64 = DEPOSIT;
+}
+impl crate::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type VrfRandomness = MockVrfRandomness;
+	type EpochRandomness = MockEpochRandomness;
+	type PalletId = RandomnessPalletId;
+	type Deposit = RequestDeposit;
+	type MinBlockDelay = ConstU32<2>;
+	type MaxBlockDelay = ConstU32<10>;
+	type ExpirationDelay = ConstU32<5>;
+}
+
+/// Move to block `n`, with the VRF output of its parent set to `vrf_output`.
+pub fn run_to_block(n: u64, vrf_output: Option<H256>) {
+	use frame_support::traits::Hooks;
+
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		VrfOutput::set(&vrf_output);
+		EvmRandomness::on_initialize(System::block_number());
+	}
+}
@@ -0,0 +1,250 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+
+use super::*;
+use crate::mock::*;
+
+const SALT: H256 = H256::repeat_byte(0x5a);
+const VRF_OUTPUT: H256 = H256::repeat_byte(0x42);
+
+fn expected_result(randomness: H256, salt: H256, request_id: RequestId) -> H256 {
+	sp_io::hashing::keccak_256(&(randomness, salt, request_id).encode()).into()
+}
+
+#[test]
+fn request_locks_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, SALT, 3));
+
+		assert_eq!(
+			Requests::<Test>::get(0),
+			Some(RandomnessRequest {
+				requester: REQUESTER,
+				salt: SALT,
+				block: 4,
+				expires: 9,
+				deposit: DEPOSIT,
+			})
+		);
+		assert_eq!(
+			RandomnessResults::<Test>::get(4),
+			Some(BlockRandomness {
+				randomness: None,
+				requests: 1,
+			})
+		);
+		assert_eq!(Balances::free_balance(REQUESTER), INITIAL_BALANCE - DEPOSIT);
+		assert_eq!(
+			Balances::free_balance(EvmRandomness::deposit_account()),
+			DEPOSIT
+		);
+		System::assert_last_event(
+			Event::RandomnessRequested {
+				request_id: 0,
+				requester: REQUESTER,
+				block: 4,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn request_checks_delay() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EvmRandomness::request_randomness(REQUESTER, SALT, 1),
+			Error::<Test>::DelayTooShort
+		);
+		assert_noop!(
+			EvmRandomness::request_randomness(REQUESTER, SALT, 11),
+			Error::<Test>::DelayTooLong
+		);
+	});
+}
+
+#[test]
+fn request_requires_deposit() {
+	new_test_ext().execute_with(|| {
+		assert!(EvmRandomness::request_randomness(H160::repeat_byte(0x99), SALT, 2).is_err());
+		assert_eq!(NextRequestId::<Test>::get(), 0);
+	});
+}
+
+#[test]
+fn fulfil_after_target_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, SALT, 2));
+
+		run_to_block(3, Some(H256::repeat_byte(0x01)));
+		assert!(!EvmRandomness::is_ready(0));
+		assert_noop!(
+			EvmRandomness::fulfil_randomness(REQUESTER, 0),
+			Error::<Test>::RandomnessNotReady
+		);
+
+		// The VRF output of block 3 is read at the start of block 4.
+		run_to_block(4, Some(VRF_OUTPUT));
+		assert!(EvmRandomness::is_ready(0));
+		System::assert_last_event(Event::RandomnessRecorded { block: 3 }.into());
+
+		// Later VRF outputs do not change the result.
+		run_to_block(6, Some(H256::repeat_byte(0x02)));
+		assert_eq!(
+			EvmRandomness::fulfil_randomness(REQUESTER, 0),
+			Ok(expected_result(VRF_OUTPUT, SALT, 0))
+		);
+
+		assert_eq!(Requests::<Test>::get(0), None);
+		assert_eq!(RandomnessResults::<Test>::get(3), None);
+		assert_eq!(Balances::free_balance(REQUESTER), INITIAL_BALANCE);
+		System::assert_last_event(Event::RandomnessFulfilled { request_id: 0 }.into());
+	});
+}
+
+#[test]
+fn fulfil_only_by_requester() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, SALT, 2));
+		run_to_block(4, Some(VRF_OUTPUT));
+
+		assert_noop!(
+			EvmRandomness::fulfil_randomness(H160::repeat_byte(0x99), 0),
+			Error::<Test>::NotRequester
+		);
+		assert_noop!(
+			EvmRandomness::fulfil_randomness(REQUESTER, 1),
+			Error::<Test>::RequestNotFound
+		);
+	});
+}
+
+#[test]
+fn expired_request_cannot_be_fulfilled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, SALT, 2));
+
+		// Block 3 is targeted, so the request can be fulfilled up to block 8.
+		run_to_block(8, Some(VRF_OUTPUT));
+		assert!(EvmRandomness::is_ready(0));
+
+		run_to_block(9, Some(VRF_OUTPUT));
+		assert!(!EvmRandomness::is_ready(0));
+		assert_noop!(
+			EvmRandomness::fulfil_randomness(REQUESTER, 0),
+			Error::<Test>::RequestExpired
+		);
+	});
+}
+
+#[test]
+fn purge_expired_request_refunds_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, SALT, 2));
+
+		run_to_block(8, Some(VRF_OUTPUT));
+		assert_noop!(
+			EvmRandomness::purge_expired_request(0),
+			Error::<Test>::RequestNotExpired
+		);
+
+		run_to_block(9, Some(VRF_OUTPUT));
+		assert_ok!(EvmRandomness::purge_expired_request(0));
+
+		assert_eq!(Requests::<Test>::get(0), None);
+		assert_eq!(RandomnessResults::<Test>::get(3), None);
+		assert_eq!(Balances::free_balance(REQUESTER), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(EvmRandomness::deposit_account()), 0);
+		System::assert_last_event(Event::RequestPurged { request_id: 0 }.into());
+
+		assert_noop!(
+			EvmRandomness::purge_expired_request(0),
+			Error::<Test>::RequestNotFound
+		);
+	});
+}
+
+#[test]
+fn purge_keeps_randomness_of_other_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, SALT, 2));
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, SALT, 2));
+		run_to_block(9, Some(VRF_OUTPUT));
+
+		assert_ok!(EvmRandomness::purge_expired_request(0));
+		assert_eq!(
+			RandomnessResults::<Test>::get(3).map(|result| result.requests),
+			Some(1)
+		);
+
+		assert_ok!(EvmRandomness::purge_expired_request(1));
+		assert_eq!(RandomnessResults::<Test>::get(3), None);
+		assert_eq!(Balances::free_balance(REQUESTER), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn requests_share_block_randomness() {
+	new_test_ext().execute_with(|| {
+		let other_salt = H256::repeat_byte(0xa5);
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, SALT, 2));
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, other_salt, 2));
+		run_to_block(4, Some(VRF_OUTPUT));
+
+		let first = EvmRandomness::fulfil_randomness(REQUESTER, 0).unwrap();
+		assert_eq!(
+			RandomnessResults::<Test>::get(3).map(|result| result.requests),
+			Some(1)
+		);
+
+		let second = EvmRandomness::fulfil_randomness(REQUESTER, 1).unwrap();
+		assert_eq!(second, expected_result(VRF_OUTPUT, other_salt, 1));
+		assert_ne!(first, second);
+		assert_eq!(RandomnessResults::<Test>::get(3), None);
+	});
+}
+
+#[test]
+fn falls_back_to_epoch_randomness() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EvmRandomness::request_randomness(REQUESTER, SALT, 2));
+		run_to_block(4, None);
+
+		let randomness = MockEpochRandomness::random(&(RANDOMNESS_SUBJECT, 3u64).encode()).0;
+		assert_eq!(
+			EvmRandomness::fulfil_randomness(REQUESTER, 0),
+			Ok(expected_result(randomness, SALT, 0))
+		);
+	});
+}
+
+#[test]
+fn prev_randao_is_vrf_randomness() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(PrevRandao::<Test>::get(), None);
+
+		run_to_block(2, Some(VRF_OUTPUT));
+		assert_eq!(PrevRandao::<Test>::get(), Some(VRF_OUTPUT));
+		assert_eq!(
+			<Test as pallet_evm::Config>::BlockRandomness::get(),
+			Some(VRF_OUTPUT)
+		);
+	});
+}
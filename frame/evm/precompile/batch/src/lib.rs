@@ -0,0 +1,290 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batch precompile. This precompile lets an account perform several calls from a single
+//! transaction, either atomically or not depending on the selected mode.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use fp_evm::{Context, ExitError, ExitReason, Log, PrecompileFailure, Transfer};
+use precompile_utils::{evm::costs::call_cost, prelude::*, EvmResult};
+use sp_core::{H160, U256};
+use sp_runtime::traits::ConstU32;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mode {
+	/// Perform all subcalls, ignoring the ones that fail.
+	BatchSome,
+	/// Perform subcalls until one of them fails.
+	BatchSomeUntilFailure,
+	/// Perform all subcalls, reverting them all if one of them fails.
+	BatchAll,
+}
+
+pub const LOG_SUBCALL_SUCCEEDED: [u8; 32] = keccak256!("SubcallSucceeded(uint256)");
+pub const LOG_SUBCALL_FAILED: [u8; 32] = keccak256!("SubcallFailed(uint256)");
+pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+pub const ARRAY_LIMIT: u32 = 2u32.pow(9);
+
+type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
+type GetArrayLimit = ConstU32<ARRAY_LIMIT>;
+
+pub fn log_subcall_succeeded(address: impl Into<H160>, index: usize) -> Log {
+	log1(
+		address,
+		LOG_SUBCALL_SUCCEEDED,
+		solidity::encode_event_data(U256::from(index)),
+	)
+}
+
+pub fn log_subcall_failed(address: impl Into<H160>, index: usize) -> Log {
+	log1(
+		address,
+		LOG_SUBCALL_FAILED,
+		solidity::encode_event_data(U256::from(index)),
+	)
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> BatchPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	/// Subcalls are made on behalf of the caller, so a contract must not be able to run the batch
+	/// in its own context with DELEGATECALL or CALLCODE.
+	#[precompile::pre_check]
+	fn pre_check(handle: &mut impl PrecompileHandle) -> EvmResult {
+		if handle.code_address() != handle.context().address {
+			return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"));
+		}
+		Ok(())
+	}
+
+	/// Perform all subcalls, ignoring the ones that fail.
+	#[precompile::public("batchSome(address[],uint256[],bytes[],uint64[])")]
+	fn batch_some(
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		Self::inner_batch(Mode::BatchSome, handle, to, value, call_data, gas_limit)
+	}
+
+	/// Perform subcalls until one of them fails. The batch still succeeds.
+	#[precompile::public("batchSomeUntilFailure(address[],uint256[],bytes[],uint64[])")]
+	fn batch_some_until_failure(
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		Self::inner_batch(
+			Mode::BatchSomeUntilFailure,
+			handle,
+			to,
+			value,
+			call_data,
+			gas_limit,
+		)
+	}
+
+	/// Perform all subcalls, forwarding the first failure and reverting all previous subcalls.
+	#[precompile::public("batchAll(address[],uint256[],bytes[],uint64[])")]
+	fn batch_all(
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		Self::inner_batch(Mode::BatchAll, handle, to, value, call_data, gas_limit)
+	}
+
+	/// Perform the subcalls described by `to`. Missing `value`, `call_data` and `gas_limit`
+	/// entries default to zero, empty and all the remaining gas respectively; a zero gas limit
+	/// also forwards all the remaining gas.
+	fn inner_batch(
+		mode: Mode,
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		let addresses = Vec::from(to).into_iter().enumerate();
+		let values = Vec::from(value)
+			.into_iter()
+			.map(Some)
+			.chain(core::iter::repeat(None));
+		let calls_data = Vec::from(call_data)
+			.into_iter()
+			.map(|x| Some(Vec::<u8>::from(x)))
+			.chain(core::iter::repeat(None));
+		let gas_limits = Vec::from(gas_limit)
+			.into_iter()
+			.map(|x| if x == 0 { None } else { Some(x) })
+			.chain(core::iter::repeat(None));
+
+		let batch_address = handle.code_address();
+
+		// Cost of a subcall log, which doesn't depend on its index.
+		let log_cost = log_subcall_failed(batch_address, 0)
+			.compute_cost()
+			.map_err(|_| revert("Failed to compute log cost"))?;
+
+		for ((i, Address(address)), (value, (call_data, gas_limit))) in
+			addresses.zip(values.zip(calls_data.zip(gas_limits)))
+		{
+			let value = value.unwrap_or_default();
+			let call_data = call_data.unwrap_or_default();
+
+			let sub_context = Context {
+				caller: handle.context().caller,
+				address,
+				apparent_value: value,
+			};
+
+			let transfer = if value.is_zero() {
+				None
+			} else {
+				Some(Transfer {
+					source: handle.context().caller,
+					target: address,
+					value,
+				})
+			};
+
+			// We reserve enough gas to emit a final log and perform the subcall.
+			let remaining_gas = handle.remaining_gas();
+			let forwarded_gas = match (remaining_gas.checked_sub(log_cost), mode) {
+				(Some(remaining), _) => remaining,
+				(None, Mode::BatchAll) => {
+					return Err(PrecompileFailure::Error {
+						exit_status: ExitError::OutOfGas,
+					})
+				}
+				(None, _) => return Ok(()),
+			};
+
+			// Cost of the call itself that the batch precompile must pay.
+			let call_cost = call_cost(value, <Runtime as pallet_evm::Config>::config());
+
+			let forwarded_gas = match forwarded_gas.checked_sub(call_cost) {
+				Some(remaining) => remaining,
+				None => {
+					Self::record_subcall_log(handle, log_subcall_failed(batch_address, i))?;
+
+					match mode {
+						Mode::BatchAll => {
+							return Err(PrecompileFailure::Error {
+								exit_status: ExitError::OutOfGas,
+							})
+						}
+						Mode::BatchSomeUntilFailure => return Ok(()),
+						Mode::BatchSome => continue,
+					}
+				}
+			};
+
+			// If there is a provided gas limit we ensure there is enough gas remaining.
+			let forwarded_gas = match gas_limit {
+				None => forwarded_gas,
+				Some(limit) if limit <= forwarded_gas => limit,
+				Some(_) => {
+					Self::record_subcall_log(handle, log_subcall_failed(batch_address, i))?;
+
+					match mode {
+						Mode::BatchAll => {
+							return Err(PrecompileFailure::Error {
+								exit_status: ExitError::OutOfGas,
+							})
+						}
+						Mode::BatchSomeUntilFailure => return Ok(()),
+						Mode::BatchSome => continue,
+					}
+				}
+			};
+
+			let (reason, output) = handle.call(
+				address,
+				transfer,
+				call_data,
+				Some(forwarded_gas),
+				false,
+				&sub_context,
+			);
+
+			// We reserved enough gas so logging should not run out of gas.
+			match reason {
+				ExitReason::Revert(_) | ExitReason::Error(_) => {
+					Self::record_subcall_log(handle, log_subcall_failed(batch_address, i))?
+				}
+				ExitReason::Succeed(_) => {
+					Self::record_subcall_log(handle, log_subcall_succeeded(batch_address, i))?
+				}
+				_ => (),
+			}
+
+			match (mode, reason) {
+				// Fatal is always fatal.
+				(_, ExitReason::Fatal(exit_status)) => {
+					return Err(PrecompileFailure::Fatal { exit_status })
+				}
+
+				// BatchAll: reverts and errors are immediately forwarded.
+				(Mode::BatchAll, ExitReason::Revert(exit_status)) => {
+					return Err(PrecompileFailure::Revert {
+						exit_status,
+						output,
+					})
+				}
+				(Mode::BatchAll, ExitReason::Error(exit_status)) => {
+					return Err(PrecompileFailure::Error { exit_status })
+				}
+
+				// BatchSomeUntilFailure: reverts and errors stop the batch, which still succeeds.
+				(Mode::BatchSomeUntilFailure, ExitReason::Revert(_) | ExitReason::Error(_)) => {
+					return Ok(())
+				}
+
+				// Success or ignored revert/error.
+				(_, _) => (),
+			}
+		}
+
+		Ok(())
+	}
+
+	fn record_subcall_log(handle: &mut impl PrecompileHandle, log: Log) -> EvmResult {
+		handle.record_log_costs(&[&log])?;
+		log.record(handle)
+	}
+}
@@ -0,0 +1,257 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{
+	log_subcall_failed, log_subcall_succeeded,
+	mock::{ExtBuilder, PCall, Precompiles, PrecompilesValue, Runtime},
+};
+use precompile_utils::{solidity::codec::Address, testing::*};
+use sp_core::{H160, U256};
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+type MakeCall = fn(Vec<Address>, Vec<U256>, Vec<Vec<u8>>, Vec<u64>) -> PCall;
+
+fn batch_call(make_call: MakeCall) -> PCall {
+	make_call(
+		vec![
+			Address(Bob.into()),
+			Address(Charlie.into()),
+			Address(David.into()),
+		],
+		vec![U256::zero(), U256::from(42), U256::zero()],
+		vec![b"one".to_vec(), b"two".to_vec(), b"three".to_vec()],
+		vec![],
+	)
+}
+
+fn batch_some(
+	to: Vec<Address>,
+	value: Vec<U256>,
+	call_data: Vec<Vec<u8>>,
+	gas_limit: Vec<u64>,
+) -> PCall {
+	PCall::batch_some {
+		to: to.into(),
+		value: value.into(),
+		call_data: call_data
+			.into_iter()
+			.map(Into::into)
+			.collect::<Vec<_>>()
+			.into(),
+		gas_limit: gas_limit.into(),
+	}
+}
+
+fn batch_some_until_failure(
+	to: Vec<Address>,
+	value: Vec<U256>,
+	call_data: Vec<Vec<u8>>,
+	gas_limit: Vec<u64>,
+) -> PCall {
+	PCall::batch_some_until_failure {
+		to: to.into(),
+		value: value.into(),
+		call_data: call_data
+			.into_iter()
+			.map(Into::into)
+			.collect::<Vec<_>>()
+			.into(),
+		gas_limit: gas_limit.into(),
+	}
+}
+
+fn batch_all(
+	to: Vec<Address>,
+	value: Vec<U256>,
+	call_data: Vec<Vec<u8>>,
+	gas_limit: Vec<u64>,
+) -> PCall {
+	PCall::batch_all {
+		to: to.into(),
+		value: value.into(),
+		call_data: call_data
+			.into_iter()
+			.map(Into::into)
+			.collect::<Vec<_>>()
+			.into(),
+		gas_limit: gas_limit.into(),
+	}
+}
+
+/// Subcall handle recording the called addresses, and reverting calls to `Charlie`.
+fn subcall_handle(called: Rc<RefCell<Vec<H160>>>) -> impl SubcallTrait {
+	move |Subcall { address, .. }| {
+		called.borrow_mut().push(address);
+		if address == Charlie.into() {
+			SubcallOutput::revert()
+		} else {
+			SubcallOutput::succeed()
+		}
+	}
+}
+
+#[test]
+fn subcalls_are_made_on_behalf_of_caller() {
+	ExtBuilder.build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				batch_all(
+					vec![Address(Bob.into())],
+					vec![U256::from(42)],
+					vec![b"input".to_vec()],
+					vec![1_000],
+				),
+			)
+			.with_subcall_handle(|subcall| {
+				let Subcall {
+					address,
+					transfer,
+					input,
+					target_gas,
+					is_static,
+					context,
+				} = subcall;
+
+				assert_eq!(address, Bob.into());
+				assert_eq!(input, b"input".to_vec());
+				assert_eq!(target_gas, Some(1_000));
+				assert!(!is_static);
+				assert_eq!(context.caller, Alice.into());
+				assert_eq!(context.address, Bob.into());
+				assert_eq!(context.apparent_value, U256::from(42));
+
+				let transfer = transfer.expect("value is transferred");
+				assert_eq!(transfer.source, Alice.into());
+				assert_eq!(transfer.target, Bob.into());
+				assert_eq!(transfer.value, U256::from(42));
+
+				SubcallOutput::succeed()
+			})
+			.expect_log(log_subcall_succeeded(Precompile1, 0))
+			.execute_returns(());
+	})
+}
+
+#[test]
+fn batch_some_ignores_failures() {
+	ExtBuilder.build().execute_with(|| {
+		let called = Rc::new(RefCell::new(Vec::new()));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, batch_call(batch_some))
+			.with_subcall_handle(subcall_handle(called.clone()))
+			.expect_log(log_subcall_succeeded(Precompile1, 0))
+			.expect_log(log_subcall_failed(Precompile1, 1))
+			.expect_log(log_subcall_succeeded(Precompile1, 2))
+			.execute_returns(());
+
+		assert_eq!(
+			*called.borrow(),
+			vec![Bob.into(), Charlie.into(), David.into()]
+		);
+	})
+}
+
+#[test]
+fn batch_some_until_failure_stops_at_first_failure() {
+	ExtBuilder.build().execute_with(|| {
+		let called = Rc::new(RefCell::new(Vec::new()));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, batch_call(batch_some_until_failure))
+			.with_subcall_handle(subcall_handle(called.clone()))
+			.expect_log(log_subcall_succeeded(Precompile1, 0))
+			.expect_log(log_subcall_failed(Precompile1, 1))
+			.execute_returns(());
+
+		assert_eq!(*called.borrow(), vec![Bob.into(), Charlie.into()]);
+	})
+}
+
+#[test]
+fn batch_all_reverts_on_failure() {
+	ExtBuilder.build().execute_with(|| {
+		let called = Rc::new(RefCell::new(Vec::new()));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, batch_call(batch_all))
+			.with_subcall_handle(subcall_handle(called.clone()))
+			.execute_reverts(|_| true);
+
+		assert_eq!(*called.borrow(), vec![Bob.into(), Charlie.into()]);
+	})
+}
+
+#[test]
+fn batch_all_succeeds_if_all_subcalls_succeed() {
+	ExtBuilder.build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				batch_all(
+					vec![Address(Bob.into()), Address(David.into())],
+					vec![],
+					vec![],
+					vec![],
+				),
+			)
+			.with_subcall_handle(
+				|Subcall {
+				     input, transfer, ..
+				 }| {
+					// Missing values and call data default to zero and empty.
+					assert!(input.is_empty());
+					assert!(transfer.is_none());
+					SubcallOutput::succeed()
+				},
+			)
+			.expect_log(log_subcall_succeeded(Precompile1, 0))
+			.expect_log(log_subcall_succeeded(Precompile1, 1))
+			.execute_returns(());
+	})
+}
+
+#[test]
+fn gas_limit_above_remaining_gas_fails_subcall() {
+	ExtBuilder.build().execute_with(|| {
+		let call = |make_call: MakeCall| {
+			make_call(vec![Address(Bob.into())], vec![], vec![], vec![1_000_000])
+		};
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, call(batch_some))
+			.with_target_gas(Some(100_000))
+			.with_subcall_handle(|_| panic!("subcall should not be made"))
+			.expect_log(log_subcall_failed(Precompile1, 0))
+			.execute_returns(());
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, call(batch_all))
+			.with_target_gas(Some(100_000))
+			.with_subcall_handle(|_| panic!("subcall should not be made"))
+			.execute_error(fp_evm::ExitError::OutOfGas);
+	})
+}
@@ -0,0 +1,261 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Call permit precompile. This precompile dispatches a call on behalf of an account which
+//! authorized it by signing an EIP-712 permit, allowing a relayer to pay for the transaction.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use fp_evm::{Context, ExitReason, ExitRevert, PrecompileFailure, Transfer};
+use frame_support::{
+	storage::types::{StorageMap, ValueQuery},
+	traits::{Get, StorageInstance, Time},
+	Blake2_128Concat,
+};
+use precompile_utils::{evm::costs::call_cost, prelude::*, EvmResult};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::{ConstU32, UniqueSaturatedInto};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Storage prefix for nonces.
+pub struct Nonces;
+
+impl StorageInstance for Nonces {
+	fn pallet_prefix() -> &'static str {
+		"PrecompileCallPermit"
+	}
+
+	const STORAGE_PREFIX: &'static str = "Nonces";
+}
+
+/// Storage type used to store the next permit nonce of each signer.
+pub type NoncesStorage = StorageMap<Nonces, Blake2_128Concat, H160, U256, ValueQuery>;
+
+/// EIP-712 type hash of a call permit.
+pub const PERMIT_TYPEHASH: [u8; 32] = keccak256!(
+	"CallPermit(address from,address to,uint256 value,bytes data,uint64 gaslimit,uint256 nonce,uint256 deadline)"
+);
+
+/// EIP-712 type hash of the permit domain.
+pub const PERMIT_DOMAIN: [u8; 32] = keccak256!(
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+);
+
+/// Cost of recovering the signer of a permit, same as the ECRecover precompile.
+pub const ECRECOVER_COST: u64 = 3_000;
+
+// Storage key of a nonce: Blake2_128(16) + H160(20), and its U256(32) value.
+const NONCE_STORAGE_SIZE: usize = 16 + 20 + 32;
+
+pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+
+type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
+
+#[derive(Debug, Clone)]
+pub struct CallPermitPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> CallPermitPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	/// Calls are dispatched on behalf of the signer, so a contract must not be able to run the
+	/// precompile in its own context with DELEGATECALL or CALLCODE.
+	#[precompile::pre_check]
+	fn pre_check(handle: &mut impl PrecompileHandle) -> EvmResult {
+		if handle.code_address() != handle.context().address {
+			return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"));
+		}
+		Ok(())
+	}
+
+	/// EIP-712 domain separator, bound to the chain id and the address of the precompile.
+	pub fn compute_domain_separator(address: H160) -> [u8; 32] {
+		let name: H256 = keccak_256(b"Call Permit Precompile").into();
+		let version: H256 = keccak256!("1").into();
+		let chain_id: U256 = <Runtime as pallet_evm::Config>::ChainId::get().into();
+
+		let domain_separator_inner = solidity::encode_arguments((
+			H256::from(PERMIT_DOMAIN),
+			name,
+			version,
+			chain_id,
+			Address(address),
+		));
+
+		keccak_256(&domain_separator_inner)
+	}
+
+	/// Digest a signer must sign to authorize the described call.
+	#[allow(clippy::too_many_arguments)]
+	pub fn generate_permit(
+		address: H160,
+		from: H160,
+		to: H160,
+		value: U256,
+		data: &[u8],
+		gas_limit: u64,
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		let domain_separator = Self::compute_domain_separator(address);
+
+		let permit_content = solidity::encode_arguments((
+			H256::from(PERMIT_TYPEHASH),
+			Address(from),
+			Address(to),
+			value,
+			H256::from(keccak_256(data)),
+			gas_limit,
+			nonce,
+			deadline,
+		));
+		let permit_content = keccak_256(&permit_content);
+
+		let mut pre_digest = Vec::with_capacity(2 + 32 + 32);
+		pre_digest.extend_from_slice(b"\x19\x01");
+		pre_digest.extend_from_slice(&domain_separator);
+		pre_digest.extend_from_slice(&permit_content);
+		keccak_256(&pre_digest)
+	}
+
+	/// Recover the address which signed `digest`, if any.
+	pub fn recover_signer(digest: &[u8; 32], v: u8, r: H256, s: H256) -> Option<H160> {
+		let mut signature = [0u8; 65];
+		signature[0..32].copy_from_slice(r.as_bytes());
+		signature[32..64].copy_from_slice(s.as_bytes());
+		signature[64] = v;
+
+		let public = sp_io::crypto::secp256k1_ecdsa_recover(&signature, digest).ok()?;
+		Some(H160::from_slice(&keccak_256(&public)[12..]))
+	}
+
+	/// Dispatch a call on behalf of `from`, who signed a permit for it. The call is made with
+	/// exactly `gasLimit` gas, and the permit can only be used once before `deadline` (in
+	/// seconds).
+	#[allow(clippy::too_many_arguments)]
+	#[precompile::public(
+		"dispatch(address,address,uint256,bytes,uint64,uint256,uint8,bytes32,bytes32)"
+	)]
+	fn dispatch(
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		value: U256,
+		data: BoundedBytes<GetCallDataLimit>,
+		gas_limit: u64,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult<UnboundedBytes> {
+		// Timestamp::Now (read), Nonces (read and write), signature recovery
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		handle.record_db_read::<Runtime>(NONCE_STORAGE_SIZE)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		handle.record_cost(ECRECOVER_COST)?;
+
+		let from: H160 = from.into();
+		let to: H160 = to.into();
+		let data: Vec<u8> = data.into();
+
+		// The subcall must be able to use all of `gas_limit`.
+		let call_cost = call_cost(value, <Runtime as pallet_evm::Config>::config());
+		let total_cost = gas_limit
+			.checked_add(call_cost)
+			.ok_or_else(|| revert("Call requires too much gas (uint64 overflow)"))?;
+		if total_cost > handle.remaining_gas() {
+			return Err(revert("Gaslimit is too low to dispatch provided call"));
+		}
+
+		// Blockchain time is in ms while Ethereum uses second timestamps.
+		let timestamp: u128 =
+			<Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		if deadline < U256::from(timestamp / 1000) {
+			return Err(revert("Permit expired"));
+		}
+
+		let nonce = NoncesStorage::get(from);
+		let permit = Self::generate_permit(
+			handle.context().address,
+			from,
+			to,
+			value,
+			&data,
+			gas_limit,
+			nonce,
+			deadline,
+		);
+		match Self::recover_signer(&permit, v, r, s) {
+			Some(signer) if signer != H160::zero() && signer == from => (),
+			_ => return Err(revert("Invalid permit")),
+		}
+
+		NoncesStorage::insert(from, nonce.saturating_add(U256::one()));
+
+		let sub_context = Context {
+			caller: from,
+			address: to,
+			apparent_value: value,
+		};
+		let transfer = if value.is_zero() {
+			None
+		} else {
+			Some(Transfer {
+				source: from,
+				target: to,
+				value,
+			})
+		};
+
+		let (reason, output) =
+			handle.call(to, transfer, data, Some(gas_limit), false, &sub_context);
+
+		match reason {
+			ExitReason::Error(exit_status) => Err(PrecompileFailure::Error { exit_status }),
+			ExitReason::Fatal(exit_status) => Err(PrecompileFailure::Fatal { exit_status }),
+			ExitReason::Revert(_) => Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output,
+			}),
+			ExitReason::Succeed(_) => Ok(output.into()),
+		}
+	}
+
+	/// Nonce the next permit of `owner` must be signed with.
+	#[precompile::public("nonces(address)")]
+	#[precompile::view]
+	fn nonces(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(NONCE_STORAGE_SIZE)?;
+
+		Ok(NoncesStorage::get(H160::from(owner)))
+	}
+
+	#[precompile::public("DOMAIN_SEPARATOR()")]
+	#[precompile::view]
+	fn domain_separator(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		Ok(Self::compute_domain_separator(handle.context().address).into())
+	}
+}
@@ -0,0 +1,286 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	mock::{ExtBuilder, PCall, Precompiles, PrecompilesValue, Runtime, Timestamp},
+	CallPermitPrecompile, NoncesStorage, PERMIT_DOMAIN,
+};
+use fc_ecdsa_keyring::Keyring;
+use fp_evm::{Context, Precompile};
+use precompile_utils::{prelude::*, testing::*};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+
+const GAS_LIMIT: u64 = 100_000;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+/// A signed permit for a call to `Bob`.
+struct Permit {
+	from: H160,
+	value: U256,
+	data: Vec<u8>,
+	deadline: U256,
+	signature: [u8; 65],
+}
+
+impl Permit {
+	fn sign(signer: Keyring, from: H160, value: U256, data: Vec<u8>, deadline: U256) -> Self {
+		let permit = CallPermitPrecompile::<Runtime>::generate_permit(
+			Precompile1.into(),
+			from,
+			Bob.into(),
+			value,
+			&data,
+			GAS_LIMIT,
+			NoncesStorage::get(from),
+			deadline,
+		);
+		let mut signature = [0u8; 65];
+		signature.copy_from_slice(signer.pair().sign_prehashed(&permit).as_ref());
+
+		Self {
+			from,
+			value,
+			data,
+			deadline,
+			signature,
+		}
+	}
+
+	fn call(&self) -> PCall {
+		PCall::dispatch {
+			from: Address(self.from),
+			to: Address(Bob.into()),
+			value: self.value,
+			data: self.data.clone().into(),
+			gas_limit: GAS_LIMIT,
+			deadline: self.deadline,
+			v: self.signature[64],
+			r: H256::from_slice(&self.signature[0..32]),
+			s: H256::from_slice(&self.signature[32..64]),
+		}
+	}
+}
+
+fn alith() -> H160 {
+	Keyring::Alith.to_h160_public()
+}
+
+fn setup() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default()
+		.with_balances(vec![(alith().into(), 1_000)])
+		.build();
+	ext.execute_with(|| Timestamp::set_timestamp(10_000));
+	ext
+}
+
+#[test]
+fn valid_permit_dispatches_call_on_behalf_of_signer() {
+	setup().execute_with(|| {
+		let permit = Permit::sign(
+			Keyring::Alith,
+			alith(),
+			U256::from(42),
+			b"input".to_vec(),
+			U256::from(20),
+		);
+
+		precompiles()
+			.prepare_test(Charlie, Precompile1, permit.call())
+			.with_subcall_handle(|subcall| {
+				let Subcall {
+					address,
+					transfer,
+					input,
+					target_gas,
+					is_static,
+					context,
+				} = subcall;
+
+				assert_eq!(address, Bob.into());
+				assert_eq!(input, b"input".to_vec());
+				assert_eq!(target_gas, Some(GAS_LIMIT));
+				assert!(!is_static);
+				assert_eq!(context.caller, alith());
+				assert_eq!(context.apparent_value, U256::from(42));
+
+				let transfer = transfer.expect("value is transferred");
+				assert_eq!(transfer.source, alith());
+				assert_eq!(transfer.value, U256::from(42));
+
+				SubcallOutput {
+					output: b"output".to_vec(),
+					..SubcallOutput::succeed()
+				}
+			})
+			.execute_returns(UnboundedBytes::from(b"output".to_vec()));
+
+		assert_eq!(NoncesStorage::get(alith()), U256::one());
+	})
+}
+
+#[test]
+fn permit_cannot_be_replayed() {
+	setup().execute_with(|| {
+		let permit = Permit::sign(
+			Keyring::Alith,
+			alith(),
+			U256::zero(),
+			vec![],
+			U256::from(20),
+		);
+
+		precompiles()
+			.prepare_test(Charlie, Precompile1, permit.call())
+			.with_subcall_handle(|_| SubcallOutput::succeed())
+			.execute_returns(UnboundedBytes::from(vec![]));
+
+		precompiles()
+			.prepare_test(Charlie, Precompile1, permit.call())
+			.with_subcall_handle(|_| panic!("subcall should not be made"))
+			.execute_reverts(|output| output == b"Invalid permit");
+
+		assert_eq!(NoncesStorage::get(alith()), U256::one());
+	})
+}
+
+#[test]
+fn expired_permit_reverts() {
+	setup().execute_with(|| {
+		let permit = Permit::sign(Keyring::Alith, alith(), U256::zero(), vec![], U256::from(9));
+
+		precompiles()
+			.prepare_test(Charlie, Precompile1, permit.call())
+			.with_subcall_handle(|_| panic!("subcall should not be made"))
+			.execute_reverts(|output| output == b"Permit expired");
+	})
+}
+
+#[test]
+fn permit_signed_by_another_account_reverts() {
+	setup().execute_with(|| {
+		let permit = Permit::sign(
+			Keyring::Baltathar,
+			alith(),
+			U256::zero(),
+			vec![],
+			U256::from(20),
+		);
+
+		precompiles()
+			.prepare_test(Charlie, Precompile1, permit.call())
+			.with_subcall_handle(|_| panic!("subcall should not be made"))
+			.execute_reverts(|output| output == b"Invalid permit");
+
+		assert_eq!(NoncesStorage::get(alith()), U256::zero());
+	})
+}
+
+#[test]
+fn subcall_revert_is_forwarded() {
+	setup().execute_with(|| {
+		let permit = Permit::sign(
+			Keyring::Alith,
+			alith(),
+			U256::zero(),
+			vec![],
+			U256::from(20),
+		);
+
+		precompiles()
+			.prepare_test(Charlie, Precompile1, permit.call())
+			.with_subcall_handle(|_| SubcallOutput {
+				output: precompile_utils::solidity::revert::revert_as_bytes("reason"),
+				..SubcallOutput::revert()
+			})
+			.execute_reverts(|output| output == b"reason");
+	})
+}
+
+#[test]
+fn gas_limit_must_be_available() {
+	setup().execute_with(|| {
+		let permit = Permit::sign(
+			Keyring::Alith,
+			alith(),
+			U256::zero(),
+			vec![],
+			U256::from(20),
+		);
+
+		precompiles()
+			.prepare_test(Charlie, Precompile1, permit.call())
+			.with_target_gas(Some(GAS_LIMIT))
+			.with_subcall_handle(|_| panic!("subcall should not be made"))
+			.execute_reverts(|output| output == b"Gaslimit is too low to dispatch provided call");
+	})
+}
+
+#[test]
+fn nonces_and_domain_separator() {
+	setup().execute_with(|| {
+		NoncesStorage::insert(alith(), U256::from(7));
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::nonces {
+					owner: Address(alith()),
+				},
+			)
+			.expect_no_logs()
+			.execute_returns(U256::from(7));
+
+		let expected = keccak_256(&solidity::encode_arguments((
+			H256::from(PERMIT_DOMAIN),
+			H256::from(keccak_256(b"Call Permit Precompile")),
+			H256::from(keccak_256(b"1")),
+			U256::from(42),
+			Address(Precompile1.into()),
+		)));
+
+		precompiles()
+			.prepare_test(Charlie, Precompile1, PCall::domain_separator {})
+			.expect_no_logs()
+			.execute_returns(H256::from(expected));
+	})
+}
+
+#[test]
+fn cannot_be_called_with_delegatecall() {
+	setup().execute_with(|| {
+		// Run in the context of a contract, as DELEGATECALL does.
+		let mut handle = MockHandle::new(
+			Precompile1.into(),
+			Context {
+				address: Bob.into(),
+				caller: Alice.into(),
+				apparent_value: U256::zero(),
+			},
+		);
+		handle.input = PCall::domain_separator {}.encode();
+
+		assert_eq!(
+			CallPermitPrecompile::<Runtime>::execute(&mut handle).err(),
+			Some(revert("Cannot be called with DELEGATECALL or CALLCODE"))
+		);
+	})
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity >=0.8.3;
+
+/// @dev The ContractRegistry contract's address.
+address constant CONTRACT_REGISTRY_ADDRESS = 0x000000000000000000000000000000000000080F;
+
+/// @dev The ContractRegistry contract's instance.
+ContractRegistry constant CONTRACT_REGISTRY_CONTRACT = ContractRegistry(
+    CONTRACT_REGISTRY_ADDRESS
+);
+
+/// @title Contract registry precompile
+/// @notice Attach the verified source metadata of the contracts deployed by the caller.
+/// @custom:address 0x000000000000000000000000000000000000080F
+interface ContractRegistry {
+    /// @dev Attach the hash of the verified source metadata of `contractAddress`, typically the
+    /// IPFS CID of its Solidity metadata file, replacing any previous one. The caller must be
+    /// the recorded deployer of the contract.
+    /// @custom:selector 9fd8b5fa
+    /// @param contractAddress The contract to attach the metadata to.
+    /// @param metadata The metadata hash.
+    function setMetadata(address contractAddress, bytes memory metadata) external;
+
+    /// @dev The metadata hash attached to `contractAddress`, empty if there is none.
+    /// @custom:selector e194aa25
+    /// @param contractAddress The contract to read the metadata of.
+    /// @return The metadata hash.
+    function metadataOf(address contractAddress) external view returns (bytes memory);
+}
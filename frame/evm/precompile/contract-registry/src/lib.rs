@@ -0,0 +1,107 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contract registry precompile. Lets the deployer of a contract recorded by
+//! `pallet-evm-contract-registry` attach its verified source metadata from the EVM, where the
+//! deployer is the `msg.sender` of the call rather than a Substrate origin checked by
+//! `MetadataOrigin`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use frame_support::traits::Get;
+use pallet_evm::GasWeightMapping;
+use pallet_evm_contract_registry::{Contracts, Metadata, WeightInfo};
+use precompile_utils::{prelude::*, EvmResult};
+use sp_core::H160;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// Storage key of a contract: Blake2_128Concat(16) + H160(20).
+const CONTRACT_KEY_SIZE: usize = 16 + 20;
+// Compact length (at most 5) prefixing the metadata hash.
+const METADATA_LENGTH_OVERHEAD: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct ContractRegistryPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> ContractRegistryPrecompile<Runtime>
+where
+	Runtime: pallet_evm_contract_registry::Config,
+{
+	/// The deployer is checked against the caller, so a contract must not be able to run the
+	/// precompile in its own context with DELEGATECALL or CALLCODE.
+	#[precompile::pre_check]
+	fn pre_check(handle: &mut impl PrecompileHandle) -> EvmResult {
+		if handle.code_address() != handle.context().address {
+			return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"));
+		}
+		Ok(())
+	}
+
+	/// Attach the metadata hash of `contract_address`, replacing any previous one. The caller
+	/// must be the recorded deployer of the contract.
+	#[precompile::public("setMetadata(address,bytes)")]
+	fn set_metadata(
+		handle: &mut impl PrecompileHandle,
+		contract_address: Address,
+		metadata: UnboundedBytes,
+	) -> EvmResult {
+		let metadata = Vec::from(metadata)
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("bytes").in_field("metadata"))?;
+
+		let weight = <Runtime as pallet_evm_contract_registry::Config>::WeightInfo::set_metadata();
+		handle.record_cost(Runtime::GasWeightMapping::weight_to_gas(weight))?;
+		handle.record_external_cost(None, Some(weight.proof_size()), None)?;
+
+		let contract: H160 = contract_address.into();
+		let Some(info) = Contracts::<Runtime>::get(contract) else {
+			return Err(revert("Unknown contract"));
+		};
+		if info.deployer != handle.context().caller {
+			return Err(revert("Caller is not the deployer"));
+		}
+
+		pallet_evm_contract_registry::Pallet::<Runtime>::do_set_metadata(contract, metadata);
+		Ok(())
+	}
+
+	/// Metadata hash attached to `contract_address`, empty if there is none.
+	#[precompile::public("metadataOf(address)")]
+	#[precompile::view]
+	fn metadata_of(
+		handle: &mut impl PrecompileHandle,
+		contract_address: Address,
+	) -> EvmResult<UnboundedBytes> {
+		handle.record_db_read::<Runtime>(
+			CONTRACT_KEY_SIZE
+				+ METADATA_LENGTH_OVERHEAD
+				+ <Runtime as pallet_evm_contract_registry::Config>::MaxMetadataLength::get()
+					as usize,
+		)?;
+
+		let metadata = Metadata::<Runtime>::get(H160::from(contract_address)).unwrap_or_default();
+		Ok(metadata.into_inner().into())
+	}
+}
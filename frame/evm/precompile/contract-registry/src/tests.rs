@@ -0,0 +1,128 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::mock::{register, ExtBuilder, PCall, Precompiles, PrecompilesValue, Runtime, System};
+use pallet_evm_contract_registry::{Event, Metadata};
+use precompile_utils::{prelude::*, testing::*};
+use sp_core::H160;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn set_metadata(contract: impl Into<H160>, metadata: &[u8]) -> PCall {
+	PCall::set_metadata {
+		contract_address: Address(contract.into()),
+		metadata: metadata.into(),
+	}
+}
+
+fn metadata_of(contract: impl Into<H160>) -> PCall {
+	PCall::metadata_of {
+		contract_address: Address(contract.into()),
+	}
+}
+
+#[test]
+fn modifiers() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mut tester = PrecompilesModifierTester::new(precompiles(), Alice, Precompile1);
+
+		tester.test_default_modifier(PCall::set_metadata_selectors());
+		tester.test_view_modifier(PCall::metadata_of_selectors());
+	});
+}
+
+#[test]
+fn test_solidity_interface_has_all_function_selectors_documented_and_implemented() {
+	check_precompile_implements_solidity_interfaces(
+		&["ContractRegistry.sol"],
+		PCall::supports_selector,
+	)
+}
+
+#[test]
+fn metadata_of_contract_without_metadata_is_empty() {
+	ExtBuilder::default().build().execute_with(|| {
+		register(Alice, Charlie);
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, metadata_of(Charlie))
+			.expect_no_logs()
+			.execute_returns(UnboundedBytes::from(Vec::new()));
+	});
+}
+
+#[test]
+fn deployer_sets_metadata() {
+	ExtBuilder::default().build().execute_with(|| {
+		register(Alice, Charlie);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, set_metadata(Charlie, b"cid"))
+			.execute_returns(());
+
+		assert_eq!(
+			Metadata::<Runtime>::get(H160::from(Charlie)).map(|m| m.into_inner()),
+			Some(b"cid".to_vec())
+		);
+		System::assert_last_event(
+			Event::MetadataSet {
+				contract: Charlie.into(),
+				metadata: b"cid".to_vec().try_into().unwrap(),
+			}
+			.into(),
+		);
+		precompiles()
+			.prepare_test(Bob, Precompile1, metadata_of(Charlie))
+			.execute_returns(UnboundedBytes::from(b"cid"));
+	});
+}
+
+#[test]
+fn non_deployer_cannot_set_metadata() {
+	ExtBuilder::default().build().execute_with(|| {
+		register(Alice, Charlie);
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, set_metadata(Charlie, b"cid"))
+			.execute_reverts(|output| output == b"Caller is not the deployer");
+
+		assert!(Metadata::<Runtime>::get(H160::from(Charlie)).is_none());
+	});
+}
+
+#[test]
+fn set_metadata_of_unknown_contract_reverts() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, set_metadata(Charlie, b"cid"))
+			.execute_reverts(|output| output == b"Unknown contract");
+	});
+}
+
+#[test]
+fn too_long_metadata_reverts() {
+	ExtBuilder::default().build().execute_with(|| {
+		register(Alice, Charlie);
+
+		// `MaxMetadataLength` is 8 in the mock.
+		precompiles()
+			.prepare_test(Alice, Precompile1, set_metadata(Charlie, &[0u8; 9]))
+			.execute_reverts(|output| output == b"metadata: Value is too large for bytes");
+	});
+}
@@ -0,0 +1,210 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chain extension letting ink! contracts call EVM contracts.
+//!
+//! The calling contract is the source of the EVM call, at the address the runtime
+//! `AddressMapping` derives for it, the same address its account acts as in
+//! `pallet_evm::EnsureAddressMapped`. The value of the call is paid by the contract, through the
+//! account holding the balance of that address. The EVM gas limit is charged upfront as contract
+//! weight, and the unused part is refunded once the call returns. No transaction fee is charged
+//! on top of it.
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use fp_evm::{CallInfo, ExitReason};
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::{Currency, ExistenceRequirement, Get},
+};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, Result, RetVal,
+};
+use pallet_evm::{AccountIdMapping, AddressMapping, GasWeightMapping, Runner};
+use sp_core::{H160, U256};
+use sp_runtime::{traits::UniqueSaturatedInto, DispatchError};
+
+use crate::with_cross_vm_guard;
+
+/// Id of the function calling an EVM contract, taking an [`EvmCallInput`] and returning its
+/// output and an [`EvmCallStatus`].
+pub const CALL_EVM: u16 = 1;
+
+/// Input of [`CALL_EVM`], SCALE-encoded by the calling contract.
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode)]
+pub struct EvmCallInput {
+	pub target: H160,
+	pub value: U256,
+	pub gas_limit: u64,
+	pub input: Vec<u8>,
+}
+
+/// Outcome of an EVM call, returned to the calling contract.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[repr(u32)]
+pub enum EvmCallStatus {
+	Succeeded = 0,
+	Reverted = 1,
+	Failed = 2,
+}
+
+impl From<&ExitReason> for EvmCallStatus {
+	fn from(reason: &ExitReason) -> Self {
+		match reason {
+			ExitReason::Succeed(_) => Self::Succeeded,
+			ExitReason::Revert(_) => Self::Reverted,
+			ExitReason::Error(_) | ExitReason::Fatal(_) => Self::Failed,
+		}
+	}
+}
+
+pub struct EvmChainExtension<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Default for EvmChainExtension<Runtime> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<Runtime> ChainExtension<Runtime> for EvmChainExtension<Runtime>
+where
+	Runtime: pallet_contracts::Config + pallet_evm::Config,
+	Runtime::AddressMapping: AccountIdMapping<Runtime::AccountId>,
+	pallet_evm::BalanceOf<Runtime>: TryFrom<U256>,
+{
+	fn call<E: Ext<T = Runtime>>(&mut self, env: Environment<E, InitState>) -> Result<RetVal> {
+		match env.func_id() {
+			CALL_EVM => Self::call_evm(env),
+			_ => Err(DispatchError::Other("Unknown chain extension function")),
+		}
+	}
+}
+
+impl<Runtime> EvmChainExtension<Runtime>
+where
+	Runtime: pallet_contracts::Config + pallet_evm::Config,
+	Runtime::AddressMapping: AccountIdMapping<Runtime::AccountId>,
+	pallet_evm::BalanceOf<Runtime>: TryFrom<U256>,
+{
+	fn call_evm<E: Ext<T = Runtime>>(env: Environment<E, InitState>) -> Result<RetVal> {
+		let mut env = env.buf_in_buf_out();
+		if env.ext().is_read_only() {
+			return Err(pallet_contracts::Error::<Runtime>::StateChangeDenied.into());
+		}
+
+		let len = env.in_len();
+		let EvmCallInput {
+			target,
+			value,
+			gas_limit,
+			input,
+		} = env.read_as_unbounded(len)?;
+		let transfer: pallet_evm::BalanceOf<Runtime> = value
+			.try_into()
+			.map_err(|_| DispatchError::Other("Value is too large for balance type"))?;
+
+		let weight_limit =
+			<Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(gas_limit, true);
+		let charged = env.charge_weight(weight_limit)?;
+		if !value.is_zero() {
+			env.charge_weight(
+				<Runtime as frame_system::Config>::DbWeight::get().reads_writes(2, 2),
+			)?;
+		}
+
+		let contract = env.ext().address().clone();
+		let source = Runtime::AddressMapping::into_address(&contract);
+
+		let info = with_cross_vm_guard(|| {
+			with_transaction(|| {
+				let result = Self::fund_and_call(
+					contract,
+					source,
+					target,
+					input,
+					value,
+					transfer,
+					gas_limit,
+					weight_limit,
+				);
+				// The contract only pays the value if the call succeeded.
+				if result
+					.as_ref()
+					.is_ok_and(|info| matches!(info.exit_reason, ExitReason::Succeed(_)))
+				{
+					TransactionOutcome::Commit(result)
+				} else {
+					TransactionOutcome::Rollback(result)
+				}
+			})
+		})
+		.ok_or(DispatchError::Other("Cross-VM reentrancy"))??;
+
+		let used_gas: u64 = info.used_gas.effective.unique_saturated_into();
+		env.adjust_weight(
+			charged,
+			<Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(used_gas, true),
+		);
+
+		env.write(&info.value, false, None)?;
+		Ok(RetVal::Converging(
+			EvmCallStatus::from(&info.exit_reason) as u32
+		))
+	}
+
+	/// Move `transfer` from `contract` to the account of its EVM address `source`, then call
+	/// `target` from `source`.
+	#[allow(clippy::too_many_arguments)]
+	fn fund_and_call(
+		contract: Runtime::AccountId,
+		source: H160,
+		target: H160,
+		input: Vec<u8>,
+		value: U256,
+		transfer: pallet_evm::BalanceOf<Runtime>,
+		gas_limit: u64,
+		weight_limit: frame_support::weights::Weight,
+	) -> core::result::Result<CallInfo, DispatchError> {
+		if !value.is_zero() {
+			<Runtime as pallet_evm::Config>::Currency::transfer(
+				&contract,
+				&Runtime::AddressMapping::into_account_id(source),
+				transfer,
+				ExistenceRequirement::KeepAlive,
+			)?;
+		}
+
+		<Runtime as pallet_evm::Config>::Runner::call(
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false,
+			false,
+			Some(weight_limit),
+			None,
+			<Runtime as pallet_evm::Config>::config(),
+		)
+		.map_err(|e| e.error.into())
+	}
+}
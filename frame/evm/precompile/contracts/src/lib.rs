@@ -0,0 +1,140 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cross-VM calls between `pallet-contracts` and the EVM.
+//!
+//! [`ContractsPrecompile`] lets EVM contracts call ink! contracts, and
+//! [`chain_extension::EvmChainExtension`] lets ink! contracts call EVM contracts. Only one
+//! cross-VM call can be in progress at a time, so neither VM can be re-entered through the other.
+//!
+//! Both directions use the runtime `pallet_evm::Config::AddressMapping`: an EVM caller acts as
+//! `into_account_id(caller)` in `pallet-contracts`, and an ink! contract acts as
+//! `into_address(contract)` in the EVM.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+pub mod chain_extension;
+
+use codec::Decode;
+use core::marker::PhantomData;
+use fp_evm::{ExitRevert, PrecompileFailure};
+use frame_support::traits::fungible::Inspect;
+use pallet_contracts::{CollectEvents, DebugInfo, Determinism};
+use pallet_evm::{AddressMapping, GasWeightMapping};
+use precompile_utils::{prelude::*, EvmResult};
+use sp_core::{H256, U256};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Inspect<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+environmental::thread_local_impl!(static IN_CROSS_VM_CALL: environmental::RefCell<bool> = environmental::RefCell::new(false));
+
+/// Run `f` as a cross-VM call. Returns `None` without running `f` if a cross-VM call is already
+/// in progress.
+pub fn with_cross_vm_guard<R>(f: impl FnOnce() -> R) -> Option<R> {
+	if IN_CROSS_VM_CALL.with(|in_call| in_call.replace(true)) {
+		return None;
+	}
+	let res = f();
+	// We should make sure that this line is executed whatever the execution path.
+	let _ = IN_CROSS_VM_CALL.with(|in_call| in_call.take());
+	Some(res)
+}
+
+#[derive(Debug, Clone)]
+pub struct ContractsPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> ContractsPrecompile<Runtime>
+where
+	Runtime: pallet_contracts::Config + pallet_evm::Config,
+	BalanceOf<Runtime>: TryFrom<U256>,
+{
+	/// Call the ink! contract `dest` with `data`, transferring `value` from the caller.
+	///
+	/// The remaining gas is converted to weight and used as the gas limit of the call, and the
+	/// caller pays at most `storageDepositLimit` for the storage the call creates. Returns the
+	/// output of the contract, and reverts with it if the contract reverted.
+	#[precompile::public("call(bytes32,uint256,uint256,bytes)")]
+	fn call(
+		handle: &mut impl PrecompileHandle,
+		dest: H256,
+		value: U256,
+		storage_deposit_limit: U256,
+		data: UnboundedBytes,
+	) -> EvmResult<UnboundedBytes> {
+		let dest = Runtime::AccountId::decode(&mut dest.as_bytes())
+			.map_err(|_| RevertReason::custom("Invalid contract account").in_field("dest"))?;
+		let value: BalanceOf<Runtime> = value
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").in_field("value"))?;
+		let storage_deposit_limit: BalanceOf<Runtime> =
+			storage_deposit_limit.try_into().map_err(|_| {
+				RevertReason::value_is_too_large("balance type").in_field("storageDepositLimit")
+			})?;
+		let origin = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(
+			handle.context().caller,
+		);
+		let gas_limit = <Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+			handle.remaining_gas(),
+			true,
+		);
+
+		let result = with_cross_vm_guard(|| {
+			pallet_contracts::Pallet::<Runtime>::bare_call(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				Some(storage_deposit_limit),
+				data.into(),
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+				Determinism::Enforced,
+			)
+		})
+		.ok_or_else(|| revert("Cross-VM reentrancy"))?;
+
+		handle.record_cost(
+			<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(result.gas_consumed),
+		)?;
+		handle.record_external_cost(
+			Some(result.gas_consumed.ref_time()),
+			Some(result.gas_consumed.proof_size()),
+			None,
+		)?;
+
+		match result.result {
+			Ok(output) if output.did_revert() => Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: output.data,
+			}),
+			Ok(output) => Ok(output.data.into()),
+			Err(e) => Err(revert(alloc::format!(
+				"Contract call failed: {}",
+				<&'static str>::from(e)
+			))),
+		}
+	}
+}
@@ -0,0 +1,322 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::Encode;
+use frame_support::weights::Weight;
+use pallet_contracts::{Code, CollectEvents, DebugInfo, Determinism};
+use precompile_utils::{prelude::*, solidity::revert::revert_as_bytes, testing::*};
+use sp_core::{H160, H256, U256};
+
+use crate::{
+	chain_extension::{EvmCallInput, EvmCallStatus},
+	mock::*,
+	*,
+};
+
+const ALICE: AccountId = AccountId::new([1u8; 32]);
+const GAS_LIMIT: Weight = Weight::from_parts(100_000_000_000, 3 * 1024 * 1024);
+
+/// Returns its input without its first byte, which is used as the return flags.
+const ECHO: &str = r#"
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		;; [0, 4) length of the input, [4, ..) input
+		(i32.store (i32.const 0) (i32.const 1024))
+		(call $seal_input (i32.const 4) (i32.const 0))
+		(call $seal_return
+			(i32.load8_u (i32.const 4))
+			(i32.const 5)
+			(i32.sub (i32.load (i32.const 0)) (i32.const 1))
+		)
+	)
+)
+"#;
+
+/// Calls the EVM with its input through the chain extension, and returns the status followed by
+/// the output of the call.
+const EVM_CALLER: &str = r#"
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "seal0" "call_chain_extension"
+		(func $call_chain_extension (param i32 i32 i32 i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		;; [0, 4) length of the input, [4, 8) length of the output, [8, 12) status,
+		;; [12, 4108) output, [4108, ..) input
+		(i32.store (i32.const 0) (i32.const 4096))
+		(call $seal_input (i32.const 4108) (i32.const 0))
+		(i32.store (i32.const 4) (i32.const 4096))
+		(i32.store (i32.const 8)
+			(call $call_chain_extension
+				(i32.const 1)
+				(i32.const 4108)
+				(i32.load (i32.const 0))
+				(i32.const 12)
+				(i32.const 4)
+			)
+		)
+		(call $seal_return
+			(i32.const 0)
+			(i32.const 8)
+			(i32.add (i32.load (i32.const 4)) (i32.const 4))
+		)
+	)
+)
+"#;
+
+/// Returns the caller and the value of the call.
+const EVM_CALLER_AND_VALUE: [u8; 13] = [
+	0x33, 0x60, 0x00, 0x52, 0x34, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00, 0xf3,
+];
+
+/// Reverts without data.
+const EVM_REVERT: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn dest(contract: &AccountId) -> H256 {
+	H256::from(*AsRef::<[u8; 32]>::as_ref(contract))
+}
+
+fn instantiate(wat: &str, value: Balance) -> AccountId {
+	pallet_contracts::Pallet::<Runtime>::bare_instantiate(
+		ALICE,
+		value,
+		GAS_LIMIT,
+		None,
+		Code::Upload(wat::parse_str(wat).unwrap()),
+		vec![],
+		vec![],
+		DebugInfo::Skip,
+		CollectEvents::Skip,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+fn call_evm(contract: &AccountId, input: EvmCallInput) -> (u32, Vec<u8>) {
+	let output = pallet_contracts::Pallet::<Runtime>::bare_call(
+		ALICE,
+		contract.clone(),
+		0,
+		GAS_LIMIT,
+		None,
+		input.encode(),
+		DebugInfo::Skip,
+		CollectEvents::Skip,
+		Determinism::Enforced,
+	)
+	.result
+	.unwrap();
+	assert!(!output.did_revert());
+
+	let (status, data) = output.data.split_at(4);
+	(
+		u32::from_le_bytes(status.try_into().unwrap()),
+		data.to_vec(),
+	)
+}
+
+fn free_balance(account: &AccountId) -> Balance {
+	Balances::free_balance(account)
+}
+
+#[test]
+fn cross_vm_guard_forbids_reentrancy() {
+	let res = with_cross_vm_guard(|| with_cross_vm_guard(|| ()));
+	assert_eq!(res, Some(None));
+
+	// The guard is released once the outer call returns.
+	assert_eq!(with_cross_vm_guard(|| 42), Some(42));
+}
+
+#[test]
+fn call_returns_contract_output_and_transfers_value() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 1_000_000_000), (account(Alice), 1_000)])
+		.build()
+		.execute_with(|| {
+			let echo = instantiate(ECHO, 0);
+			let balance = free_balance(&echo);
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::call {
+						dest: dest(&echo),
+						value: 100.into(),
+						storage_deposit_limit: 100.into(),
+						data: vec![0, 1, 2, 3].into(),
+					},
+				)
+				.with_target_gas(Some(10_000_000))
+				.execute_returns(UnboundedBytes::from(vec![1, 2, 3]));
+
+			assert_eq!(free_balance(&echo), balance + 100);
+			assert_eq!(free_balance(&account(Alice)), 900);
+		})
+}
+
+#[test]
+fn call_reverts_with_contract_output() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 1_000_000_000), (account(Alice), 1_000)])
+		.build()
+		.execute_with(|| {
+			let echo = instantiate(ECHO, 0);
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::call {
+						dest: dest(&echo),
+						value: 100.into(),
+						storage_deposit_limit: 100.into(),
+						data: [&[1u8][..], &revert_as_bytes("nope")].concat().into(),
+					},
+				)
+				.with_target_gas(Some(10_000_000))
+				.execute_reverts(|output| output == b"nope");
+
+			assert_eq!(free_balance(&account(Alice)), 1_000);
+		})
+}
+
+#[test]
+fn call_fails_for_unknown_contract() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::call {
+					dest: H256::repeat_byte(0x42),
+					value: 0.into(),
+					storage_deposit_limit: 0.into(),
+					data: vec![].into(),
+				},
+			)
+			.with_target_gas(Some(10_000_000))
+			.execute_reverts(|output| output == b"Contract call failed: ContractNotFound");
+	})
+}
+
+#[test]
+fn call_evm_uses_contract_address_and_transfers_value() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 1_000_000_000)])
+		.build()
+		.execute_with(|| {
+			let caller = instantiate(EVM_CALLER, 1_000);
+			let target = H160::repeat_byte(0x11);
+			EVM::create_account(target, EVM_CALLER_AND_VALUE.to_vec());
+			let balance = free_balance(&caller);
+
+			let (status, output) = call_evm(
+				&caller,
+				EvmCallInput {
+					target,
+					value: 100.into(),
+					gas_limit: 100_000,
+					input: vec![],
+				},
+			);
+
+			assert_eq!(status, EvmCallStatus::Succeeded as u32);
+			assert_eq!(
+				H160::from(H256::from_slice(&output[..32])),
+				address(&caller)
+			);
+			assert_eq!(U256::from_big_endian(&output[32..]), U256::from(100));
+			assert_eq!(free_balance(&account(target)), 100);
+			assert_eq!(free_balance(&caller), balance - 100);
+		})
+}
+
+#[test]
+fn call_evm_keeps_value_on_revert() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 1_000_000_000)])
+		.build()
+		.execute_with(|| {
+			let caller = instantiate(EVM_CALLER, 1_000);
+			let target = H160::repeat_byte(0x11);
+			EVM::create_account(target, EVM_REVERT.to_vec());
+			let balance = free_balance(&caller);
+
+			let (status, output) = call_evm(
+				&caller,
+				EvmCallInput {
+					target,
+					value: 100.into(),
+					gas_limit: 100_000,
+					input: vec![],
+				},
+			);
+
+			assert_eq!(status, EvmCallStatus::Reverted as u32);
+			assert!(output.is_empty());
+			assert_eq!(free_balance(&account(target)), 0);
+			assert_eq!(free_balance(&account(address(&caller))), 0);
+			assert_eq!(free_balance(&caller), balance);
+		})
+}
+
+#[test]
+fn call_evm_cannot_reenter_contracts() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 1_000_000_000)])
+		.build()
+		.execute_with(|| {
+			let caller = instantiate(EVM_CALLER, 1_000);
+			let echo = instantiate(ECHO, 0);
+
+			let (status, output) = call_evm(
+				&caller,
+				EvmCallInput {
+					target: Precompile1.into(),
+					value: 0.into(),
+					gas_limit: 100_000,
+					input: PCall::call {
+						dest: dest(&echo),
+						value: 0.into(),
+						storage_deposit_limit: 0.into(),
+						data: vec![0].into(),
+					}
+					.into(),
+				},
+			);
+
+			assert_eq!(status, EvmCallStatus::Reverted as u32);
+			assert_eq!(decode_revert_message(&output), b"Cross-VM reentrancy");
+		})
+}
@@ -155,10 +155,13 @@ impl pallet_evm::Config for Test {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type CreateFilter = ();
 	type FindAuthor = FindAuthorTruncated;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type GasLimitPovSizeRatio = ();
+	type GasLimitStorageGrowthRatio = ();
 	type Timestamp = Timestamp;
+	type BlockRandomness = ();
 	type WeightInfo = ();
 }
 
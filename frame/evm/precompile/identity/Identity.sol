@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity >=0.8.3;
+
+/// @dev The Identity contract's address.
+address constant IDENTITY_ADDRESS = 0x000000000000000000000000000000000000080C;
+
+/// @dev The Identity contract's instance.
+Identity constant IDENTITY_CONTRACT = Identity(IDENTITY_ADDRESS);
+
+/// @title Identity precompile
+/// @notice Manage the on-chain identity of the Substrate account mapped to the caller.
+/// @custom:address 0x000000000000000000000000000000000000080C
+interface Identity {
+    /// @dev A data field of an identity. Only raw values of up to 32 bytes can be set, fields
+    /// stored as a hash are read back with the hash as their value.
+    struct Data {
+        bool hasData;
+        bytes value;
+    }
+
+    /// @dev An additional field of an identity.
+    struct Additional {
+        Data key;
+        Data value;
+    }
+
+    /// @dev The information of an identity.
+    struct IdentityInfo {
+        Additional[] additional;
+        Data display;
+        Data legal;
+        Data web;
+        Data riot;
+        Data email;
+        bool hasPgpFingerprint;
+        bytes20 pgpFingerprint;
+        Data image;
+        Data twitter;
+    }
+
+    /// @dev A judgement given by a registrar. `judgement` is one of Unknown (0), FeePaid (1),
+    /// Reasonable (2), KnownGood (3), OutOfDate (4), LowQuality (5) and Erroneous (6).
+    /// `fee` is the fee held for a requested judgement.
+    struct Judgement {
+        uint32 registrarIndex;
+        uint8 judgement;
+        uint256 fee;
+    }
+
+    /// @dev The registration of an identity. `isValid` is false if the account has no identity.
+    struct Registration {
+        bool isValid;
+        Judgement[] judgements;
+        uint256 deposit;
+        IdentityInfo info;
+    }
+
+    /// @dev Set the identity of the caller, replacing its previous one and resetting its
+    /// judgements.
+    /// @custom:selector 8a1bc82f
+    /// @param info The identity information.
+    function setIdentity(IdentityInfo memory info) external;
+
+    /// @dev Clear the identity of the caller, unreserving its deposit.
+    /// @custom:selector 7a6a10c7
+    function clearIdentity() external;
+
+    /// @dev Request a judgement of the caller's identity from a registrar.
+    /// @custom:selector d523ceb9
+    /// @param regIndex The index of the registrar.
+    /// @param maxFee The maximum fee the caller is willing to pay.
+    function requestJudgement(uint32 regIndex, uint256 maxFee) external;
+
+    /// @dev Cancel a pending judgement request, refunding its fee.
+    /// @custom:selector c79934a5
+    /// @param regIndex The index of the registrar.
+    function cancelRequest(uint32 regIndex) external;
+
+    /// @dev Get the registration of the identity of an account.
+    /// @custom:selector f0eb5e54
+    /// @param who The address of the account.
+    /// @return The registration, with `isValid` false if the account has no identity.
+    function identity(address who) external view returns (Registration memory);
+}
@@ -0,0 +1,323 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Identity precompile. Lets EVM accounts manage the identity of their mapped Substrate account in
+//! `pallet-identity`: set and clear it, request and cancel judgements, and read the registration
+//! of any address.
+//!
+//! Only raw data fields are supported when setting an identity. Fields stored as a hash are read
+//! back with the hash as their value.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+use codec::MaxEncodedLen;
+use core::marker::PhantomData;
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	traits::{Currency, Get},
+};
+use pallet_evm::AddressMapping;
+use pallet_identity::{legacy::IdentityInfo, Data, Judgement, Registration};
+use precompile_utils::{prelude::*, EvmResult};
+use sp_core::{ConstU32, U256};
+use sp_runtime::traits::Dispatchable;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+type AccountIdOf<Runtime> = <Runtime as frame_system::Config>::AccountId;
+type BalanceOf<Runtime> =
+	<<Runtime as pallet_identity::Config>::Currency as Currency<AccountIdOf<Runtime>>>::Balance;
+
+/// Raw data fields are limited to 32 bytes.
+type GetDataLimit = ConstU32<32>;
+
+// Storage key of an identity: Twox64Concat(8) + AccountId, its value ends with an optional
+// username (1) prefixed by its compact length (at most 5).
+const IDENTITY_KEY_SIZE: usize = 8;
+const USERNAME_OVERHEAD: usize = 1 + 5;
+
+/// Data field of an identity. `has_data` is false for an empty field.
+#[derive(Debug, solidity::Codec)]
+pub struct IdentityData {
+	has_data: bool,
+	value: BoundedBytes<GetDataLimit>,
+}
+
+impl Default for IdentityData {
+	fn default() -> Self {
+		Self {
+			has_data: false,
+			value: Vec::new().into(),
+		}
+	}
+}
+
+/// Additional field of an identity.
+#[derive(Default, Debug, solidity::Codec)]
+pub struct Additional {
+	key: IdentityData,
+	value: IdentityData,
+}
+
+/// Identity information, mirroring `pallet_identity::legacy::IdentityInfo`.
+#[derive(Default, Debug, solidity::Codec)]
+pub struct IdentityFields {
+	additional: Vec<Additional>,
+	display: IdentityData,
+	legal: IdentityData,
+	web: IdentityData,
+	riot: IdentityData,
+	email: IdentityData,
+	has_pgp_fingerprint: bool,
+	pgp_fingerprint: FixedBytes<20>,
+	image: IdentityData,
+	twitter: IdentityData,
+}
+
+/// Judgement given by a registrar. `judgement` is the index of the judgement in
+/// `pallet_identity::Judgement`, `fee` is the fee held for a requested judgement.
+#[derive(Default, Debug, Eq, PartialEq, solidity::Codec)]
+pub struct JudgementInfo {
+	registrar_index: u32,
+	judgement: u8,
+	fee: U256,
+}
+
+/// Registration of an identity. `is_valid` is false if the account has no identity.
+#[derive(Default, Debug, solidity::Codec)]
+pub struct IdentityRegistration {
+	is_valid: bool,
+	judgements: Vec<JudgementInfo>,
+	deposit: U256,
+	info: IdentityFields,
+}
+
+#[derive(Debug, Clone)]
+pub struct IdentityPrecompile<Runtime, MaxAdditionalFields>(
+	PhantomData<(Runtime, MaxAdditionalFields)>,
+);
+
+#[precompile_utils::precompile]
+impl<Runtime, MaxAdditionalFields> IdentityPrecompile<Runtime, MaxAdditionalFields>
+where
+	Runtime: pallet_evm::Config
+		+ pallet_identity::Config<IdentityInformation = IdentityInfo<MaxAdditionalFields>>,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_identity::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<AccountIdOf<Runtime>>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+	MaxAdditionalFields: Get<u32> + 'static,
+{
+	/// Calls are dispatched on behalf of the caller, so a contract must not be able to run the
+	/// precompile in its own context with DELEGATECALL or CALLCODE.
+	#[precompile::pre_check]
+	fn pre_check(handle: &mut impl PrecompileHandle) -> EvmResult {
+		if handle.code_address() != handle.context().address {
+			return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"));
+		}
+		Ok(())
+	}
+
+	/// Set the identity of the caller, replacing its previous one and resetting its judgements.
+	#[precompile::public(
+		"setIdentity((((bool,bytes),(bool,bytes))[],(bool,bytes),(bool,bytes),(bool,bytes),(bool,bytes),(bool,bytes),bool,bytes20,(bool,bytes),(bool,bytes)))"
+	)]
+	fn set_identity(handle: &mut impl PrecompileHandle, info: IdentityFields) -> EvmResult {
+		let info = Self::identity_info(info)?;
+		let call = pallet_identity::Call::<Runtime>::set_identity {
+			info: Box::new(info),
+		};
+
+		Self::dispatch(handle, call)
+	}
+
+	/// Clear the identity of the caller, unreserving its deposit.
+	#[precompile::public("clearIdentity()")]
+	fn clear_identity(handle: &mut impl PrecompileHandle) -> EvmResult {
+		Self::dispatch(handle, pallet_identity::Call::<Runtime>::clear_identity {})
+	}
+
+	/// Request a judgement of the caller's identity from registrar `regIndex`, paying at most
+	/// `maxFee`.
+	#[precompile::public("requestJudgement(uint32,uint256)")]
+	fn request_judgement(
+		handle: &mut impl PrecompileHandle,
+		reg_index: u32,
+		max_fee: U256,
+	) -> EvmResult {
+		let max_fee = max_fee
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").in_field("maxFee"))?;
+		let call = pallet_identity::Call::<Runtime>::request_judgement { reg_index, max_fee };
+
+		Self::dispatch(handle, call)
+	}
+
+	/// Cancel a pending judgement request to registrar `regIndex`, refunding its fee.
+	#[precompile::public("cancelRequest(uint32)")]
+	fn cancel_request(handle: &mut impl PrecompileHandle, reg_index: u32) -> EvmResult {
+		Self::dispatch(
+			handle,
+			pallet_identity::Call::<Runtime>::cancel_request { reg_index },
+		)
+	}
+
+	/// Registration of the identity of `who`.
+	#[precompile::public("identity(address)")]
+	#[precompile::view]
+	fn identity(
+		handle: &mut impl PrecompileHandle,
+		who: Address,
+	) -> EvmResult<IdentityRegistration> {
+		handle.record_db_read::<Runtime>(
+			IDENTITY_KEY_SIZE
+				+ AccountIdOf::<Runtime>::max_encoded_len()
+				+ Registration::<
+					BalanceOf<Runtime>,
+					<Runtime as pallet_identity::Config>::MaxRegistrars,
+					IdentityInfo<MaxAdditionalFields>,
+				>::max_encoded_len()
+				+ USERNAME_OVERHEAD
+				+ <Runtime as pallet_identity::Config>::MaxUsernameLength::get() as usize,
+		)?;
+
+		let who = Runtime::AddressMapping::into_account_id(who.into());
+		let Some((registration, _)) = pallet_identity::Pallet::<Runtime>::identity(who) else {
+			return Ok(IdentityRegistration::default());
+		};
+
+		Ok(IdentityRegistration {
+			is_valid: true,
+			judgements: registration
+				.judgements
+				.into_iter()
+				.map(|(registrar_index, judgement)| {
+					Self::judgement_info(registrar_index, judgement)
+				})
+				.collect(),
+			deposit: registration.deposit.into(),
+			info: Self::identity_fields(registration.info),
+		})
+	}
+
+	fn dispatch(
+		handle: &mut impl PrecompileHandle,
+		call: pallet_identity::Call<Runtime>,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		Ok(())
+	}
+
+	fn identity_info(info: IdentityFields) -> EvmResult<IdentityInfo<MaxAdditionalFields>> {
+		let additional = info
+			.additional
+			.into_iter()
+			.map(|field| (Self::data(field.key), Self::data(field.value)))
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("array").in_field("additional"))?;
+
+		Ok(IdentityInfo {
+			additional,
+			display: Self::data(info.display),
+			legal: Self::data(info.legal),
+			web: Self::data(info.web),
+			riot: Self::data(info.riot),
+			email: Self::data(info.email),
+			pgp_fingerprint: info.has_pgp_fingerprint.then_some(info.pgp_fingerprint.0),
+			image: Self::data(info.image),
+			twitter: Self::data(info.twitter),
+		})
+	}
+
+	fn identity_fields(info: IdentityInfo<MaxAdditionalFields>) -> IdentityFields {
+		IdentityFields {
+			additional: info
+				.additional
+				.into_iter()
+				.map(|(key, value)| Additional {
+					key: Self::identity_data(key),
+					value: Self::identity_data(value),
+				})
+				.collect(),
+			display: Self::identity_data(info.display),
+			legal: Self::identity_data(info.legal),
+			web: Self::identity_data(info.web),
+			riot: Self::identity_data(info.riot),
+			email: Self::identity_data(info.email),
+			has_pgp_fingerprint: info.pgp_fingerprint.is_some(),
+			pgp_fingerprint: FixedBytes(info.pgp_fingerprint.unwrap_or_default()),
+			image: Self::identity_data(info.image),
+			twitter: Self::identity_data(info.twitter),
+		}
+	}
+
+	fn data(data: IdentityData) -> Data {
+		if !data.has_data {
+			return Data::None;
+		}
+
+		let value: Vec<u8> = data.value.into();
+		// The value is bounded to 32 bytes when decoded.
+		Data::Raw(value.try_into().unwrap_or_default())
+	}
+
+	fn identity_data(data: Data) -> IdentityData {
+		let value: Vec<u8> = match data {
+			Data::None => return IdentityData::default(),
+			Data::Raw(value) => value.into(),
+			Data::BlakeTwo256(hash)
+			| Data::Sha256(hash)
+			| Data::Keccak256(hash)
+			| Data::ShaThree256(hash) => hash.to_vec(),
+		};
+
+		IdentityData {
+			has_data: true,
+			value: value.into(),
+		}
+	}
+
+	fn judgement_info(
+		registrar_index: u32,
+		judgement: Judgement<BalanceOf<Runtime>>,
+	) -> JudgementInfo {
+		let (judgement, fee) = match judgement {
+			Judgement::Unknown => (0, U256::zero()),
+			Judgement::FeePaid(fee) => (1, fee.into()),
+			Judgement::Reasonable => (2, U256::zero()),
+			Judgement::KnownGood => (3, U256::zero()),
+			Judgement::OutOfDate => (4, U256::zero()),
+			Judgement::LowQuality => (5, U256::zero()),
+			Judgement::Erroneous => (6, U256::zero()),
+		};
+
+		JudgementInfo {
+			registrar_index,
+			judgement,
+			fee,
+		}
+	}
+}
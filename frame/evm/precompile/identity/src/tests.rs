@@ -0,0 +1,280 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	mock::{
+		account, Balances, ExtBuilder, Identity, MaxAdditionalFields, PCall, Precompiles,
+		PrecompilesValue, Runtime, RuntimeOrigin,
+	},
+	Additional, IdentityData, IdentityFields, IdentityRegistration, JudgementInfo,
+};
+use frame_support::assert_ok;
+use pallet_identity::{legacy::IdentityInfo, Judgement};
+use precompile_utils::{prelude::*, testing::*};
+use sp_core::{H160, U256};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn data(value: &str) -> IdentityData {
+	IdentityData {
+		has_data: true,
+		value: value.into(),
+	}
+}
+
+fn alice_identity() -> IdentityFields {
+	IdentityFields {
+		additional: vec![Additional {
+			key: data("matrix"),
+			value: data("@alice:argochain"),
+		}],
+		display: data("Alice"),
+		email: data("alice@argochain.io"),
+		has_pgp_fingerprint: true,
+		pgp_fingerprint: FixedBytes([7; 20]),
+		..Default::default()
+	}
+}
+
+/// `Alice` and `Bob` are funded, `Bob` is registrar 0 with a fee of 10.
+fn setup() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default()
+		.with_balances(vec![(account(Alice), 1_000), (account(Bob), 1_000)])
+		.build();
+	ext.execute_with(|| {
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::root(), account(Bob)));
+		assert_ok!(Identity::set_fee(
+			RuntimeOrigin::signed(account(Bob)),
+			0,
+			10
+		));
+	});
+	ext
+}
+
+/// Registration of the identity set by `set_alice_identity`.
+fn alice_registration(judgements: Vec<JudgementInfo>) -> IdentityRegistration {
+	let (registration, _) = Identity::identity(account(Alice)).expect("identity is set");
+	IdentityRegistration {
+		is_valid: true,
+		judgements,
+		deposit: registration.deposit.into(),
+		info: alice_identity(),
+	}
+}
+
+fn set_alice_identity() {
+	precompiles()
+		.prepare_test(
+			Alice,
+			Precompile1,
+			PCall::set_identity {
+				info: alice_identity(),
+			},
+		)
+		.execute_returns(());
+}
+
+fn expect_registration(who: impl Into<H160>, registration: IdentityRegistration) {
+	precompiles()
+		.prepare_test(
+			Bob,
+			Precompile1,
+			PCall::identity {
+				who: Address(who.into()),
+			},
+		)
+		.expect_no_logs()
+		.execute_returns(registration);
+}
+
+#[test]
+fn modifiers() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mut tester = PrecompilesModifierTester::new(precompiles(), Alice, Precompile1);
+
+		tester.test_default_modifier(PCall::set_identity_selectors());
+		tester.test_default_modifier(PCall::clear_identity_selectors());
+		tester.test_default_modifier(PCall::request_judgement_selectors());
+		tester.test_default_modifier(PCall::cancel_request_selectors());
+		tester.test_view_modifier(PCall::identity_selectors());
+	});
+}
+
+#[test]
+fn test_solidity_interface_has_all_function_selectors_documented_and_implemented() {
+	check_precompile_implements_solidity_interfaces(&["Identity.sol"], PCall::supports_selector)
+}
+
+#[test]
+fn identity_of_unknown_account_is_not_valid() {
+	setup().execute_with(|| {
+		expect_registration(Alice, IdentityRegistration::default());
+	});
+}
+
+#[test]
+fn set_identity_and_read_registration() {
+	setup().execute_with(|| {
+		set_alice_identity();
+
+		let deposit = Balances::reserved_balance(account(Alice));
+		assert!(deposit > 0);
+		expect_registration(
+			Alice,
+			IdentityRegistration {
+				is_valid: true,
+				judgements: vec![],
+				deposit: deposit.into(),
+				info: alice_identity(),
+			},
+		);
+	});
+}
+
+#[test]
+fn too_many_additional_fields_reverts() {
+	setup().execute_with(|| {
+		let info = IdentityFields {
+			additional: (0..=MaxAdditionalFields::get())
+				.map(|_| Additional {
+					key: data("key"),
+					value: data("value"),
+				})
+				.collect(),
+			..Default::default()
+		};
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::set_identity { info })
+			.execute_reverts(|output| output == b"additional: Value is too large for array");
+	});
+}
+
+#[test]
+fn clear_identity_unreserves_deposit() {
+	setup().execute_with(|| {
+		set_alice_identity();
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::clear_identity {})
+			.execute_returns(());
+
+		assert_eq!(Balances::reserved_balance(account(Alice)), 0);
+		expect_registration(Alice, IdentityRegistration::default());
+	});
+}
+
+#[test]
+fn clear_missing_identity_reverts() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::clear_identity {})
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+	});
+}
+
+#[test]
+fn request_and_cancel_judgement() {
+	setup().execute_with(|| {
+		set_alice_identity();
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::request_judgement {
+					reg_index: 0,
+					max_fee: 10.into(),
+				},
+			)
+			.execute_returns(());
+
+		expect_registration(
+			Alice,
+			alice_registration(vec![JudgementInfo {
+				registrar_index: 0,
+				judgement: 1,
+				fee: 10.into(),
+			}]),
+		);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::cancel_request { reg_index: 0 })
+			.execute_returns(());
+
+		expect_registration(Alice, alice_registration(vec![]));
+	});
+}
+
+#[test]
+fn request_judgement_above_max_fee_reverts() {
+	setup().execute_with(|| {
+		set_alice_identity();
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::request_judgement {
+					reg_index: 0,
+					max_fee: 9.into(),
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::request_judgement {
+					reg_index: 0,
+					max_fee: U256::MAX,
+				},
+			)
+			.execute_reverts(|output| output == b"maxFee: Value is too large for balance type");
+	});
+}
+
+#[test]
+fn provided_judgement_is_readable() {
+	setup().execute_with(|| {
+		set_alice_identity();
+
+		let (registration, _) = Identity::identity(account(Alice)).expect("identity is set");
+		let info: &IdentityInfo<MaxAdditionalFields> = &registration.info;
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(account(Bob)),
+			0,
+			account(Alice),
+			Judgement::KnownGood,
+			BlakeTwo256::hash_of(info),
+		));
+
+		expect_registration(
+			Alice,
+			alice_registration(vec![JudgementInfo {
+				registrar_index: 0,
+				judgement: 3,
+				fee: U256::zero(),
+			}]),
+		);
+	});
+}
@@ -0,0 +1,694 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ERC-721 precompile set for `pallet-nfts`. Each collection is exposed at its own address, made of
+//! `COLLECTION_ADDRESS_PREFIX` followed by the big endian collection id, where its items can be
+//! inspected and transferred like the tokens of an ERC-721 contract, the token id being the item id.
+//!
+//! Items are owned by Substrate accounts. The address reported for an owner is the EVM address it
+//! last received an item at through the precompile, or the address derived from the account by
+//! `AccountMapping` otherwise. Approvals given through the precompile are kept in its own storage
+//! and only apply to transfers made through it, while the delegates approved in `pallet-nfts` can
+//! still transfer the item from their mapped EVM address. The runtime must set
+//! [`ClearTokenApprovals`] as the `OnItemOwnerChange` hook of `pallet-nfts`, so that approvals are
+//! cleared when an item changes owner outside of the precompile.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use fp_evm::{Context, ExitError, ExitReason, PrecompileFailure};
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	storage::types::{OptionQuery, StorageDoubleMap, StorageMap},
+	traits::{tokens::nonfungibles_v2::Inspect, Currency, StorageInfoTrait, StorageInstance},
+	Blake2_128Concat,
+};
+use pallet_evm::{AccountIdMapping, AddressMapping};
+use pallet_nfts::OnItemOwnerChange;
+use precompile_utils::{
+	evm::costs::call_cost,
+	precompile_set::{get_address_type, AddressType},
+	prelude::*,
+	EvmResult,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::{Dispatchable, StaticLookup};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Storage prefix for the EVM addresses of the owners.
+pub struct Addresses;
+
+impl StorageInstance for Addresses {
+	fn pallet_prefix() -> &'static str {
+		"PrecompileNftsErc721"
+	}
+
+	const STORAGE_PREFIX: &'static str = "Addresses";
+}
+
+/// Storage type used to store the EVM address each account last received an item at.
+pub type AddressesStorage<Runtime> =
+	StorageMap<Addresses, Blake2_128Concat, AccountIdOf<Runtime>, H160, OptionQuery>;
+
+/// Storage prefix for the approved addresses of the items.
+pub struct TokenApprovals;
+
+impl StorageInstance for TokenApprovals {
+	fn pallet_prefix() -> &'static str {
+		"PrecompileNftsErc721"
+	}
+
+	const STORAGE_PREFIX: &'static str = "TokenApprovals";
+}
+
+/// Storage type used to store the address approved to transfer an item, along with the owner who
+/// approved it. The approval is cleared by [`ClearTokenApprovals`] once the item changes owner.
+pub type TokenApprovalsStorage<Runtime> = StorageDoubleMap<
+	TokenApprovals,
+	Blake2_128Concat,
+	CollectionIdOf<Runtime>,
+	Blake2_128Concat,
+	ItemIdOf<Runtime>,
+	(AccountIdOf<Runtime>, H160),
+	OptionQuery,
+>;
+
+/// `pallet-nfts` hook clearing the approval given through the precompile for an item once it
+/// changes owner, whether the transfer was made through the precompile or not. Without it, an
+/// approval would apply again after the item left its owner and came back.
+pub struct ClearTokenApprovals<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime: pallet_nfts::Config> OnItemOwnerChange<CollectionIdOf<Runtime>, ItemIdOf<Runtime>>
+	for ClearTokenApprovals<Runtime>
+{
+	fn on_item_owner_change(collection: &CollectionIdOf<Runtime>, item: &ItemIdOf<Runtime>) {
+		TokenApprovalsStorage::<Runtime>::remove(collection, item);
+	}
+}
+
+/// Storage prefix for the operators of the owners.
+pub struct OperatorApprovals;
+
+impl StorageInstance for OperatorApprovals {
+	fn pallet_prefix() -> &'static str {
+		"PrecompileNftsErc721"
+	}
+
+	const STORAGE_PREFIX: &'static str = "OperatorApprovals";
+}
+
+/// Storage type used to store the operators allowed to manage all the items of an owner in a
+/// collection.
+pub type OperatorApprovalsStorage<Runtime> = StorageDoubleMap<
+	OperatorApprovals,
+	Blake2_128Concat,
+	(CollectionIdOf<Runtime>, AccountIdOf<Runtime>),
+	Blake2_128Concat,
+	H160,
+	(),
+	OptionQuery,
+>;
+
+type AccountIdOf<Runtime> = <Runtime as frame_system::Config>::AccountId;
+type CollectionIdOf<Runtime> = <Runtime as pallet_nfts::Config>::CollectionId;
+type ItemIdOf<Runtime> = <Runtime as pallet_nfts::Config>::ItemId;
+type DepositBalanceOf<Runtime> =
+	<<Runtime as pallet_nfts::Config>::Currency as Currency<AccountIdOf<Runtime>>>::Balance;
+/// Encoded fields of the metadata of an item: the depositor, the deposit and the data.
+type ItemMetadataFields<Runtime> = (
+	Option<AccountIdOf<Runtime>>,
+	DepositBalanceOf<Runtime>,
+	Vec<u8>,
+);
+
+/// Prefix of the address of a collection, followed by the big endian collection id.
+pub const COLLECTION_ADDRESS_PREFIX: [u8; 16] =
+	[0xff, 0xff, 0xff, 0xfe, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address,uint256)");
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] = keccak256!("Approval(address,address,uint256)");
+pub const SELECTOR_LOG_APPROVAL_FOR_ALL: [u8; 32] =
+	keccak256!("ApprovalForAll(address,address,bool)");
+
+/// Interfaces reported by `supportsInterface`: ERC-165, ERC-721 and its metadata extension.
+pub const SUPPORTED_INTERFACES: [[u8; 4]; 3] = [
+	[0x01, 0xff, 0xc9, 0xa7],
+	[0x80, 0xac, 0x58, 0xcd],
+	[0x5b, 0x5e, 0x13, 0x9f],
+];
+
+/// Selector of `onERC721Received(address,address,uint256,bytes)`, which a receiving contract must
+/// return to accept a safe transfer.
+pub const ERC721_RECEIVED: [u8; 4] = [0x15, 0x0b, 0x7a, 0x02];
+
+/// Collection attributes holding the name and symbol of the collection.
+pub const NAME_ATTRIBUTE: &[u8] = b"name";
+pub const SYMBOL_ATTRIBUTE: &[u8] = b"symbol";
+
+pub const DATA_LIMIT: u32 = 2u32.pow(16);
+
+type GetDataLimit = sp_core::ConstU32<DATA_LIMIT>;
+
+/// Address of the precompile of `collection`.
+pub fn collection_address(collection: u32) -> H160 {
+	let mut address = [0u8; 20];
+	address[..16].copy_from_slice(&COLLECTION_ADDRESS_PREFIX);
+	address[16..].copy_from_slice(&collection.to_be_bytes());
+	address.into()
+}
+
+/// Collection whose precompile is at `address`, if `address` has the collection prefix.
+pub fn address_collection(address: H160) -> Option<u32> {
+	let (prefix, collection) = address.as_bytes().split_at(16);
+	if prefix != COLLECTION_ADDRESS_PREFIX {
+		return None;
+	}
+
+	let mut id = [0u8; 4];
+	id.copy_from_slice(collection);
+	Some(u32::from_be_bytes(id))
+}
+
+/// Maximum size of an entry of storage `S`, key included.
+fn storage_size<S: StorageInfoTrait>() -> usize {
+	S::storage_info()
+		.first()
+		.and_then(|info| info.max_size)
+		.unwrap_or_default() as usize
+}
+
+pub struct Erc721NftsPrecompileSet<Runtime, AccountMapping>(PhantomData<(Runtime, AccountMapping)>);
+
+impl<Runtime, AccountMapping> Erc721NftsPrecompileSet<Runtime, AccountMapping> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<Runtime, AccountMapping> Default for Erc721NftsPrecompileSet<Runtime, AccountMapping> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[precompile_utils::precompile]
+#[precompile::precompile_set]
+impl<Runtime, AccountMapping> Erc721NftsPrecompileSet<Runtime, AccountMapping>
+where
+	Runtime: pallet_evm::Config + pallet_nfts::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_nfts::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<AccountIdOf<Runtime>>>,
+	CollectionIdOf<Runtime>: From<u32>,
+	ItemIdOf<Runtime>: TryFrom<U256>,
+	AccountMapping: AccountIdMapping<AccountIdOf<Runtime>>,
+{
+	/// Collection of the precompile at `address`. Only existing collections have a precompile.
+	#[precompile::discriminant]
+	fn discriminant(address: H160, gas: u64) -> DiscriminantResult<CollectionIdOf<Runtime>> {
+		let Some(collection) = address_collection(address) else {
+			return DiscriminantResult::None(0);
+		};
+
+		let extra_cost = RuntimeHelper::<Runtime>::db_read_gas_cost();
+		if gas < extra_cost {
+			return DiscriminantResult::OutOfGas;
+		}
+
+		let collection = CollectionIdOf::<Runtime>::from(collection);
+		if pallet_nfts::Collection::<Runtime>::contains_key(collection) {
+			DiscriminantResult::Some(collection, extra_cost)
+		} else {
+			DiscriminantResult::None(extra_cost)
+		}
+	}
+
+	/// Transfers and approvals are made on behalf of the caller, so a contract must not be able to
+	/// run the precompile in its own context with DELEGATECALL or CALLCODE.
+	#[precompile::pre_check]
+	fn pre_check(
+		_collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult {
+		if handle.code_address() != handle.context().address {
+			return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"));
+		}
+		Ok(())
+	}
+
+	#[precompile::public("supportsInterface(bytes4)")]
+	#[precompile::view]
+	fn supports_interface(
+		_collection: CollectionIdOf<Runtime>,
+		_handle: &mut impl PrecompileHandle,
+		interface_id: FixedBytes<4>,
+	) -> EvmResult<bool> {
+		Ok(SUPPORTED_INTERFACES.contains(&interface_id.0))
+	}
+
+	/// Number of items of the collection owned by `owner`. Costs a read per item.
+	#[precompile::public("balanceOf(address)")]
+	#[precompile::view]
+	fn balance_of(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+	) -> EvmResult<U256> {
+		let owner: H160 = owner.into();
+		if owner.is_zero() {
+			return Err(revert("Zero address is not a valid owner"));
+		}
+
+		let owner = Runtime::AddressMapping::into_account_id(owner);
+		let mut balance = U256::zero();
+		handle.record_db_read::<Runtime>(storage_size::<pallet_nfts::Account<Runtime>>())?;
+		for _ in pallet_nfts::Account::<Runtime>::iter_key_prefix((owner, collection)) {
+			handle.record_db_read::<Runtime>(storage_size::<pallet_nfts::Account<Runtime>>())?;
+			balance = balance.saturating_add(U256::one());
+		}
+
+		Ok(balance)
+	}
+
+	#[precompile::public("ownerOf(uint256)")]
+	#[precompile::view]
+	fn owner_of(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		token_id: U256,
+	) -> EvmResult<Address> {
+		let owner = Self::owner(handle, &collection, token_id)?;
+
+		Ok(Address(Self::address_of(handle, &owner)?))
+	}
+
+	/// Name of the collection, from its `name` attribute.
+	#[precompile::public("name()")]
+	#[precompile::view]
+	fn name(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<UnboundedString> {
+		Self::collection_attribute(handle, &collection, NAME_ATTRIBUTE)
+	}
+
+	/// Symbol of the collection, from its `symbol` attribute.
+	#[precompile::public("symbol()")]
+	#[precompile::view]
+	fn symbol(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<UnboundedString> {
+		Self::collection_attribute(handle, &collection, SYMBOL_ATTRIBUTE)
+	}
+
+	/// Metadata of the item, empty if none was set.
+	#[precompile::public("tokenURI(uint256)")]
+	#[precompile::view]
+	fn token_uri(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		token_id: U256,
+	) -> EvmResult<UnboundedString> {
+		let item = Self::item_id(token_id)?;
+		Self::owner(handle, &collection, token_id)?;
+
+		handle.record_db_read::<Runtime>(storage_size::<pallet_nfts::ItemMetadataOf<Runtime>>())?;
+		let Some(metadata) = pallet_nfts::ItemMetadataOf::<Runtime>::get(collection, item) else {
+			return Ok(UnboundedString::from(""));
+		};
+
+		// The fields of the metadata are private to `pallet-nfts`, the data follows the deposit.
+		let (_, _, data) = ItemMetadataFields::<Runtime>::decode(&mut &metadata.encode()[..])
+			.map_err(|_| revert("Invalid item metadata"))?;
+
+		Ok(UnboundedString::from(data))
+	}
+
+	/// Address approved to transfer the item through the precompile, or zero if none.
+	#[precompile::public("getApproved(uint256)")]
+	#[precompile::view]
+	fn get_approved(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		token_id: U256,
+	) -> EvmResult<Address> {
+		let owner = Self::owner(handle, &collection, token_id)?;
+
+		Ok(Address(
+			Self::approved(handle, &collection, token_id, &owner)?.unwrap_or_default(),
+		))
+	}
+
+	#[precompile::public("isApprovedForAll(address,address)")]
+	#[precompile::view]
+	fn is_approved_for_all(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		operator: Address,
+	) -> EvmResult<bool> {
+		let owner = Runtime::AddressMapping::into_account_id(owner.into());
+
+		Self::is_operator(handle, &collection, &owner, operator.into())
+	}
+
+	/// Approve `approved` to transfer the item through the precompile, or revoke the approval if
+	/// it is the zero address. The caller must own the item or be an operator of its owner.
+	#[precompile::public("approve(address,uint256)")]
+	fn approve(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		approved: Address,
+		token_id: U256,
+	) -> EvmResult {
+		let item = Self::item_id(token_id)?;
+		let owner = Self::owner(handle, &collection, token_id)?;
+		let caller = handle.context().caller;
+		let owner_address = if Runtime::AddressMapping::into_account_id(caller) == owner {
+			caller
+		} else if Self::is_operator(handle, &collection, &owner, caller)? {
+			Self::address_of(handle, &owner)?
+		} else {
+			return Err(revert("Caller is not the owner nor an operator"));
+		};
+
+		let approved: H160 = approved.into();
+		if approved == owner_address {
+			return Err(revert("Approval to the current owner"));
+		}
+
+		let log = log4(
+			handle.context().address,
+			SELECTOR_LOG_APPROVAL,
+			owner_address,
+			approved,
+			Self::token_topic(token_id),
+			Vec::new(),
+		);
+		handle.record_log_costs(&[&log])?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+
+		if approved.is_zero() {
+			TokenApprovalsStorage::<Runtime>::remove(collection, item);
+		} else {
+			TokenApprovalsStorage::<Runtime>::insert(collection, item, (owner, approved));
+		}
+
+		log.record(handle)?;
+
+		Ok(())
+	}
+
+	/// Allow or forbid `operator` to manage all the items of the caller in the collection.
+	#[precompile::public("setApprovalForAll(address,bool)")]
+	fn set_approval_for_all(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		operator: Address,
+		approved: bool,
+	) -> EvmResult {
+		let caller = handle.context().caller;
+		let operator: H160 = operator.into();
+		if operator == caller {
+			return Err(revert("Approve to caller"));
+		}
+
+		let log = log3(
+			handle.context().address,
+			SELECTOR_LOG_APPROVAL_FOR_ALL,
+			caller,
+			operator,
+			solidity::encode_event_data(approved),
+		);
+		handle.record_log_costs(&[&log])?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+
+		let owner = Runtime::AddressMapping::into_account_id(caller);
+		if approved {
+			OperatorApprovalsStorage::<Runtime>::insert((collection, owner), operator, ());
+		} else {
+			OperatorApprovalsStorage::<Runtime>::remove((collection, owner), operator);
+		}
+
+		log.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("transferFrom(address,address,uint256)")]
+	fn transfer_from(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		token_id: U256,
+	) -> EvmResult {
+		Self::transfer(handle, collection, from.into(), to.into(), token_id)
+	}
+
+	#[precompile::public("safeTransferFrom(address,address,uint256)")]
+	fn safe_transfer_from(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		token_id: U256,
+	) -> EvmResult {
+		Self::safe_transfer(
+			handle,
+			collection,
+			from.into(),
+			to.into(),
+			token_id,
+			Vec::new(),
+		)
+	}
+
+	#[precompile::public("safeTransferFrom(address,address,uint256,bytes)")]
+	fn safe_transfer_from_with_data(
+		collection: CollectionIdOf<Runtime>,
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		token_id: U256,
+		data: BoundedBytes<GetDataLimit>,
+	) -> EvmResult {
+		Self::safe_transfer(
+			handle,
+			collection,
+			from.into(),
+			to.into(),
+			token_id,
+			data.into(),
+		)
+	}
+
+	/// Transfer the item, then make sure a receiving contract accepts it by returning
+	/// `ERC721_RECEIVED` from `onERC721Received`.
+	fn safe_transfer(
+		handle: &mut impl PrecompileHandle,
+		collection: CollectionIdOf<Runtime>,
+		from: H160,
+		to: H160,
+		token_id: U256,
+		data: Vec<u8>,
+	) -> EvmResult {
+		Self::transfer(handle, collection, from, to, token_id)?;
+
+		if get_address_type::<Runtime>(handle, to)? != AddressType::Contract {
+			return Ok(());
+		}
+
+		handle.record_cost(call_cost(
+			U256::zero(),
+			<Runtime as pallet_evm::Config>::config(),
+		))?;
+
+		let input = solidity::encode_with_selector(
+			u32::from_be_bytes(ERC721_RECEIVED),
+			(
+				Address(handle.context().caller),
+				Address(from),
+				token_id,
+				UnboundedBytes::from(data),
+			),
+		);
+		let sub_context = Context {
+			caller: handle.context().address,
+			address: to,
+			apparent_value: U256::zero(),
+		};
+		let gas = handle.remaining_gas();
+		let (reason, output) = handle.call(to, None, input, Some(gas), false, &sub_context);
+
+		match reason {
+			ExitReason::Fatal(exit_status) => Err(PrecompileFailure::Fatal { exit_status }),
+			ExitReason::Succeed(_)
+				if solidity::decode_return_value::<FixedBytes<4>>(&output)
+					.is_ok_and(|selector| selector.0 == ERC721_RECEIVED) =>
+			{
+				Ok(())
+			}
+			_ => Err(revert("Transfer to non ERC721Receiver implementer")),
+		}
+	}
+
+	/// Transfer the item from `from` to `to`. The caller must own the item, be approved for it or
+	/// be an operator of its owner, in which case the transfer is dispatched on behalf of the
+	/// owner. Otherwise it is dispatched on behalf of the caller, which `pallet-nfts` accepts if it
+	/// is a delegate of the item.
+	fn transfer(
+		handle: &mut impl PrecompileHandle,
+		collection: CollectionIdOf<Runtime>,
+		from: H160,
+		to: H160,
+		token_id: U256,
+	) -> EvmResult {
+		let item = Self::item_id(token_id)?;
+		let owner = Self::owner(handle, &collection, token_id)?;
+		if to.is_zero() {
+			return Err(revert("Transfer to the zero address"));
+		}
+		if Runtime::AddressMapping::into_account_id(from) != owner
+			&& Self::address_of(handle, &owner)? != from
+		{
+			return Err(revert("Transfer from incorrect owner"));
+		}
+
+		let caller = handle.context().caller;
+		let caller_account = Runtime::AddressMapping::into_account_id(caller);
+		let origin = if caller_account == owner
+			|| Self::approved(handle, &collection, token_id, &owner)? == Some(caller)
+			|| Self::is_operator(handle, &collection, &owner, caller)?
+		{
+			owner
+		} else {
+			caller_account
+		};
+
+		let log = log4(
+			handle.context().address,
+			SELECTOR_LOG_TRANSFER,
+			from,
+			to,
+			Self::token_topic(token_id),
+			Vec::new(),
+		);
+		handle.record_log_costs(&[&log])?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(2))?;
+
+		let to_account = Runtime::AddressMapping::into_account_id(to);
+		let call = pallet_nfts::Call::<Runtime>::transfer {
+			collection,
+			item,
+			dest: Runtime::Lookup::unlookup(to_account.clone()),
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		AddressesStorage::<Runtime>::insert(to_account, to);
+
+		log.record(handle)?;
+
+		Ok(())
+	}
+
+	fn item_id(token_id: U256) -> EvmResult<ItemIdOf<Runtime>> {
+		ItemIdOf::<Runtime>::try_from(token_id).map_err(|_| revert("Unknown token"))
+	}
+
+	fn token_topic(token_id: U256) -> H256 {
+		let mut topic = H256::zero();
+		token_id.to_big_endian(topic.as_bytes_mut());
+		topic
+	}
+
+	/// Owner of the item, reverting if it doesn't exist.
+	fn owner(
+		handle: &mut impl PrecompileHandle,
+		collection: &CollectionIdOf<Runtime>,
+		token_id: U256,
+	) -> EvmResult<AccountIdOf<Runtime>> {
+		let item = Self::item_id(token_id)?;
+		handle.record_db_read::<Runtime>(storage_size::<pallet_nfts::Item<Runtime>>())?;
+
+		<pallet_nfts::Pallet<Runtime> as Inspect<AccountIdOf<Runtime>>>::owner(collection, &item)
+			.ok_or_else(|| revert("Unknown token"))
+	}
+
+	/// EVM address of `account`.
+	fn address_of(
+		handle: &mut impl PrecompileHandle,
+		account: &AccountIdOf<Runtime>,
+	) -> EvmResult<H160> {
+		handle.record_db_read::<Runtime>(storage_size::<AddressesStorage<Runtime>>())?;
+
+		Ok(AddressesStorage::<Runtime>::get(account)
+			.unwrap_or_else(|| AccountMapping::into_address(account)))
+	}
+
+	/// Address approved for the item by its current owner, if any.
+	fn approved(
+		handle: &mut impl PrecompileHandle,
+		collection: &CollectionIdOf<Runtime>,
+		token_id: U256,
+		owner: &AccountIdOf<Runtime>,
+	) -> EvmResult<Option<H160>> {
+		let item = Self::item_id(token_id)?;
+		handle.record_db_read::<Runtime>(storage_size::<TokenApprovalsStorage<Runtime>>())?;
+
+		Ok(TokenApprovalsStorage::<Runtime>::get(collection, item)
+			.filter(|(approver, _)| approver == owner)
+			.map(|(_, approved)| approved))
+	}
+
+	fn is_operator(
+		handle: &mut impl PrecompileHandle,
+		collection: &CollectionIdOf<Runtime>,
+		owner: &AccountIdOf<Runtime>,
+		operator: H160,
+	) -> EvmResult<bool> {
+		handle.record_db_read::<Runtime>(storage_size::<OperatorApprovalsStorage<Runtime>>())?;
+
+		Ok(OperatorApprovalsStorage::<Runtime>::contains_key(
+			(*collection, owner.clone()),
+			operator,
+		))
+	}
+
+	fn collection_attribute(
+		handle: &mut impl PrecompileHandle,
+		collection: &CollectionIdOf<Runtime>,
+		key: &[u8],
+	) -> EvmResult<UnboundedString> {
+		handle.record_db_read::<Runtime>(storage_size::<pallet_nfts::Attribute<Runtime>>())?;
+
+		let value =
+			<pallet_nfts::Pallet<Runtime> as Inspect<AccountIdOf<Runtime>>>::collection_attribute(
+				collection, key,
+			);
+		Ok(UnboundedString::from(value.unwrap_or_default()))
+	}
+}
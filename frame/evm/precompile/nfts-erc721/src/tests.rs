@@ -0,0 +1,510 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	collection_address,
+	mock::{
+		account, AccountId, AccountMapping, ExtBuilder, Nfts, PCall, Precompiles, PrecompilesValue,
+		Runtime, RuntimeOrigin,
+	},
+	TokenApprovalsStorage, ERC721_RECEIVED, SELECTOR_LOG_APPROVAL, SELECTOR_LOG_APPROVAL_FOR_ALL,
+	SELECTOR_LOG_TRANSFER,
+};
+use fp_evm::{IsPrecompileResult, PrecompileSet};
+use frame_support::{assert_ok, traits::tokens::nonfungibles_v2::Inspect};
+use pallet_evm::AccountIdMapping;
+use pallet_nfts::{
+	AttributeNamespace, CollectionConfig, CollectionSettings, ItemConfig, ItemSettings,
+	MintSettings,
+};
+use precompile_utils::{prelude::*, testing::*};
+use sp_core::{H160, H256, U256};
+
+fn precompiles() -> Precompiles {
+	PrecompilesValue::get()
+}
+
+fn token_topic(token_id: u32) -> H256 {
+	H256::from_low_u64_be(token_id.into())
+}
+
+/// Collection 0 owned by `Alice`, holding items 0 and 1 minted to her.
+fn setup() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default()
+		.with_balances(vec![(account(Alice), 1_000), (account(Bob), 1_000)])
+		.build();
+	ext.execute_with(|| {
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			account(Alice),
+			CollectionConfig {
+				settings: CollectionSettings::all_enabled(),
+				max_supply: None,
+				mint_settings: MintSettings::default(),
+			},
+		));
+		for item in 0..2 {
+			assert_ok!(Nfts::force_mint(
+				RuntimeOrigin::root(),
+				0,
+				item,
+				account(Alice),
+				ItemConfig {
+					settings: ItemSettings::all_enabled(),
+				},
+			));
+		}
+	});
+	ext
+}
+
+fn owner(token_id: u32) -> Option<AccountId> {
+	<Nfts as Inspect<AccountId>>::owner(&0, &token_id)
+}
+
+#[test]
+fn only_existing_collections_are_precompiles() {
+	setup().execute_with(|| {
+		let is_precompile = |address: H160| match precompiles().is_precompile(address, u64::MAX) {
+			IsPrecompileResult::Answer { is_precompile, .. } => is_precompile,
+			IsPrecompileResult::OutOfGas => panic!("enough gas is provided"),
+		};
+
+		assert!(is_precompile(collection_address(0)));
+		assert!(!is_precompile(collection_address(1)));
+		assert!(!is_precompile(Alice.into()));
+
+		precompiles()
+			.prepare_test(Alice, collection_address(1), PCall::name {})
+			.execute_none();
+	})
+}
+
+#[test]
+fn supports_erc165_and_erc721_interfaces() {
+	setup().execute_with(|| {
+		for (interface_id, supported) in [
+			([0x01, 0xff, 0xc9, 0xa7], true),
+			([0x80, 0xac, 0x58, 0xcd], true),
+			([0x5b, 0x5e, 0x13, 0x9f], true),
+			([0xff, 0xff, 0xff, 0xff], false),
+		] {
+			precompiles()
+				.prepare_test(
+					Alice,
+					collection_address(0),
+					PCall::supports_interface {
+						interface_id: interface_id.into(),
+					},
+				)
+				.expect_no_logs()
+				.execute_returns(supported);
+		}
+	})
+}
+
+#[test]
+fn owner_of_and_balance_of() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::balance_of {
+					owner: Address(Alice.into()),
+				},
+			)
+			.expect_no_logs()
+			.execute_returns(U256::from(2));
+
+		// The item was minted outside of the EVM, so its owner has its derived address.
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::owner_of {
+					token_id: U256::one(),
+				},
+			)
+			.expect_no_logs()
+			.execute_returns(Address(AccountMapping::into_address(&account(Alice))));
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::owner_of {
+					token_id: U256::from(2),
+				},
+			)
+			.execute_reverts(|output| output == b"Unknown token");
+	})
+}
+
+#[test]
+fn token_uri_name_and_symbol() {
+	setup().execute_with(|| {
+		assert_ok!(Nfts::set_metadata(
+			RuntimeOrigin::signed(account(Alice)),
+			0,
+			0,
+			b"ipfs://item".to_vec().try_into().unwrap(),
+		));
+		for (key, value) in [(&b"name"[..], &b"Argonauts"[..]), (b"symbol", b"ARGO")] {
+			assert_ok!(Nfts::set_attribute(
+				RuntimeOrigin::signed(account(Alice)),
+				0,
+				None,
+				AttributeNamespace::CollectionOwner,
+				key.to_vec().try_into().unwrap(),
+				value.to_vec().try_into().unwrap(),
+			));
+		}
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::token_uri {
+					token_id: U256::zero(),
+				},
+			)
+			.execute_returns(UnboundedString::from("ipfs://item"));
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::token_uri {
+					token_id: U256::one(),
+				},
+			)
+			.execute_returns(UnboundedString::from(""));
+		precompiles()
+			.prepare_test(Bob, collection_address(0), PCall::name {})
+			.execute_returns(UnboundedString::from("Argonauts"));
+		precompiles()
+			.prepare_test(Bob, collection_address(0), PCall::symbol {})
+			.execute_returns(UnboundedString::from("ARGO"));
+	})
+}
+
+#[test]
+fn owner_can_transfer() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				collection_address(0),
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Bob.into()),
+					token_id: U256::zero(),
+				},
+			)
+			.expect_log(log4(
+				collection_address(0),
+				SELECTOR_LOG_TRANSFER,
+				H160::from(Alice),
+				H160::from(Bob),
+				token_topic(0),
+				vec![],
+			))
+			.execute_returns(());
+
+		assert_eq!(owner(0), Some(account(Bob)));
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::owner_of {
+					token_id: U256::zero(),
+				},
+			)
+			.execute_returns(Address(Bob.into()));
+	})
+}
+
+#[test]
+fn approved_address_can_transfer_once() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				collection_address(0),
+				PCall::approve {
+					approved: Address(Charlie.into()),
+					token_id: U256::zero(),
+				},
+			)
+			.expect_log(log4(
+				collection_address(0),
+				SELECTOR_LOG_APPROVAL,
+				H160::from(Alice),
+				H160::from(Charlie),
+				token_topic(0),
+				vec![],
+			))
+			.execute_returns(());
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::get_approved {
+					token_id: U256::zero(),
+				},
+			)
+			.execute_returns(Address(Charlie.into()));
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				collection_address(0),
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Bob.into()),
+					token_id: U256::zero(),
+				},
+			)
+			.execute_returns(());
+		assert_eq!(owner(0), Some(account(Bob)));
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::get_approved {
+					token_id: U256::zero(),
+				},
+			)
+			.execute_returns(Address(H160::zero()));
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				collection_address(0),
+				PCall::transfer_from {
+					from: Address(Bob.into()),
+					to: Address(Charlie.into()),
+					token_id: U256::zero(),
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+	})
+}
+
+#[test]
+fn approval_is_cleared_by_substrate_transfers() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				collection_address(0),
+				PCall::approve {
+					approved: Address(Charlie.into()),
+					token_id: U256::zero(),
+				},
+			)
+			.execute_returns(());
+
+		// The item leaves `Alice` and comes back, outside of the precompile.
+		assert_ok!(Nfts::transfer(
+			RuntimeOrigin::signed(account(Alice)),
+			0,
+			0,
+			account(Bob)
+		));
+		assert_ok!(Nfts::transfer(
+			RuntimeOrigin::signed(account(Bob)),
+			0,
+			0,
+			account(Alice)
+		));
+		assert!(TokenApprovalsStorage::<Runtime>::get(0, 0).is_none());
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::get_approved {
+					token_id: U256::zero(),
+				},
+			)
+			.execute_returns(Address(H160::zero()));
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				collection_address(0),
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Charlie.into()),
+					token_id: U256::zero(),
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+		assert_eq!(owner(0), Some(account(Alice)));
+	})
+}
+
+#[test]
+fn operator_can_transfer_all_items() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				collection_address(0),
+				PCall::set_approval_for_all {
+					operator: Address(Charlie.into()),
+					approved: true,
+				},
+			)
+			.expect_log(log3(
+				collection_address(0),
+				SELECTOR_LOG_APPROVAL_FOR_ALL,
+				H160::from(Alice),
+				H160::from(Charlie),
+				solidity::encode_event_data(true),
+			))
+			.execute_returns(());
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::is_approved_for_all {
+					owner: Address(Alice.into()),
+					operator: Address(Charlie.into()),
+				},
+			)
+			.execute_returns(true);
+
+		for token_id in 0..2 {
+			precompiles()
+				.prepare_test(
+					Charlie,
+					collection_address(0),
+					PCall::transfer_from {
+						from: Address(Alice.into()),
+						to: Address(Bob.into()),
+						token_id: token_id.into(),
+					},
+				)
+				.execute_returns(());
+			assert_eq!(owner(token_id), Some(account(Bob)));
+		}
+	})
+}
+
+#[test]
+fn unauthorized_transfers_revert() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Bob.into()),
+					token_id: U256::zero(),
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				collection_address(0),
+				PCall::transfer_from {
+					from: Address(Bob.into()),
+					to: Address(Charlie.into()),
+					token_id: U256::zero(),
+				},
+			)
+			.execute_reverts(|output| output == b"Transfer from incorrect owner");
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				collection_address(0),
+				PCall::approve {
+					approved: Address(Bob.into()),
+					token_id: U256::zero(),
+				},
+			)
+			.execute_reverts(|output| output == b"Caller is not the owner nor an operator");
+
+		assert_eq!(owner(0), Some(account(Alice)));
+	})
+}
+
+#[test]
+fn safe_transfer_to_contract_requires_acceptance() {
+	setup().execute_with(|| {
+		pallet_evm::AccountCodes::<Runtime>::insert(
+			H160::from(Bob),
+			vec![0x60, 0x00, 0x60, 0x00, 0x00, 0x00],
+		);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				collection_address(0),
+				PCall::safe_transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Bob.into()),
+					token_id: U256::one(),
+				},
+			)
+			.with_subcall_handle(|_| SubcallOutput {
+				output: solidity::encode_return_value(FixedBytes([0u8; 4])),
+				..SubcallOutput::succeed()
+			})
+			.execute_reverts(|output| output == b"Transfer to non ERC721Receiver implementer");
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				collection_address(0),
+				PCall::safe_transfer_from_with_data {
+					from: Address(Alice.into()),
+					to: Address(Bob.into()),
+					token_id: U256::zero(),
+					data: b"data".to_vec().into(),
+				},
+			)
+			.with_subcall_handle(|subcall| {
+				let Subcall {
+					address,
+					input,
+					context,
+					..
+				} = subcall;
+
+				assert_eq!(address, Bob.into());
+				assert_eq!(context.caller, collection_address(0));
+				assert_eq!(&input[..4], &ERC721_RECEIVED);
+
+				SubcallOutput {
+					output: solidity::encode_return_value(FixedBytes(ERC721_RECEIVED)),
+					..SubcallOutput::succeed()
+				}
+			})
+			.execute_returns(());
+	})
+}
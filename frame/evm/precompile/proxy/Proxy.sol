@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity >=0.8.3;
+
+/// @dev The Proxy contract's address.
+address constant PROXY_ADDRESS = 0x000000000000000000000000000000000000080D;
+
+/// @dev The Proxy contract's instance.
+Proxy constant PROXY_CONTRACT = Proxy(PROXY_ADDRESS);
+
+/// @title Proxy precompile
+/// @notice Manage the proxies of the Substrate account mapped to the caller, and call on behalf of
+/// an account the caller is a proxy of. Proxy types are the SCALE indices of the runtime
+/// `ProxyType`: Any (0), NonTransfer (1), Governance (2) and Staking (3).
+/// @custom:address 0x000000000000000000000000000000000000080D
+interface Proxy {
+    /// @dev Register a proxy of the caller. An address can only be a single proxy of the caller.
+    /// @custom:selector 74a34dd3
+    /// @param delegate The address of the proxy.
+    /// @param proxyType The type of calls the proxy is allowed to make.
+    /// @param delay The number of blocks a call must be announced before being made.
+    function addProxy(address delegate, uint8 proxyType, uint32 delay) external;
+
+    /// @dev Unregister a proxy of the caller.
+    /// @custom:selector fef3f708
+    /// @param delegate The address of the proxy.
+    /// @param proxyType The type of the proxy.
+    /// @param delay The announcement delay of the proxy.
+    function removeProxy(address delegate, uint8 proxyType, uint32 delay) external;
+
+    /// @dev Unregister all the proxies of the caller.
+    /// @custom:selector 14a5b5fa
+    function removeProxies() external;
+
+    /// @dev Call a contract on behalf of an account the caller is a proxy of, without delay.
+    /// The call must be allowed by the type of the proxy.
+    /// @custom:selector 9ddba085
+    /// @param real The account the caller is a proxy of.
+    /// @param callTo The address to call.
+    /// @param value The value transferred from `real`.
+    /// @param callData The data of the call.
+    /// @return The output of the call.
+    function proxy(
+        address real,
+        address callTo,
+        uint256 value,
+        bytes memory callData
+    ) external returns (bytes memory);
+
+    /// @dev Check whether an address is a proxy of an account.
+    /// @custom:selector e26d38ed
+    /// @param real The account delegating.
+    /// @param delegate The address of the proxy.
+    /// @param proxyType The type of the proxy.
+    /// @param delay The announcement delay of the proxy.
+    /// @return Whether `delegate` is a proxy of `real` with the given type and delay.
+    function isProxy(
+        address real,
+        address delegate,
+        uint8 proxyType,
+        uint32 delay
+    ) external view returns (bool);
+}
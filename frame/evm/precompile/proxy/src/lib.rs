@@ -0,0 +1,259 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Proxy precompile. Lets EVM accounts manage the proxies of their mapped Substrate account in
+//! `pallet-proxy`, and lets a proxy make EVM calls on behalf of the account it is a proxy of.
+//!
+//! EVM calls made through a proxy are filtered by its proxy type with [`EvmProxyCallFilter`],
+//! since the runtime `InstanceFilter` only applies to runtime calls.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Decode;
+use core::marker::PhantomData;
+use fp_evm::{Context, ExitReason, ExitRevert, PrecompileFailure, Transfer};
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	traits::StorageInfoTrait,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_evm::AddressMapping;
+use precompile_utils::{evm::costs::call_cost, prelude::*, EvmResult};
+use sp_core::{H160, U256};
+use sp_runtime::traits::{ConstU32, Dispatchable, StaticLookup, Zero};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+
+type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
+
+type AccountIdOf<Runtime> = <Runtime as frame_system::Config>::AccountId;
+
+/// EVM call made by a proxy on behalf of the account it is a proxy of.
+pub struct EvmSubCall {
+	pub to: H160,
+	pub value: U256,
+	pub call_data: Vec<u8>,
+}
+
+/// Filters the EVM calls a proxy type is allowed to make through the precompile.
+pub trait EvmProxyCallFilter {
+	/// Whether a proxy of this type is allowed to make `call`.
+	fn is_evm_proxy_call_allowed(&self, call: &EvmSubCall) -> bool;
+}
+
+/// Maximum size of an entry of storage `S`, key included.
+fn storage_size<S: StorageInfoTrait>() -> usize {
+	S::storage_info()
+		.first()
+		.and_then(|info| info.max_size)
+		.unwrap_or_default() as usize
+}
+
+#[derive(Debug, Clone)]
+pub struct ProxyPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> ProxyPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config + pallet_proxy::Config,
+	<Runtime as frame_system::Config>::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_proxy::Call<Runtime>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<AccountIdOf<Runtime>>>,
+	<Runtime as pallet_proxy::Config>::ProxyType: Decode + EvmProxyCallFilter,
+	BlockNumberFor<Runtime>: From<u32>,
+{
+	/// Calls are made on behalf of the caller, so a contract must not be able to run the
+	/// precompile in its own context with DELEGATECALL or CALLCODE.
+	#[precompile::pre_check]
+	fn pre_check(handle: &mut impl PrecompileHandle) -> EvmResult {
+		if handle.code_address() != handle.context().address {
+			return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"));
+		}
+		Ok(())
+	}
+
+	/// Register `delegate` as a proxy of the caller. An address can only be a single proxy of
+	/// the caller, so that the proxy used by `proxy` is never ambiguous.
+	#[precompile::public("addProxy(address,uint8,uint32)")]
+	fn add_proxy(
+		handle: &mut impl PrecompileHandle,
+		delegate: Address,
+		proxy_type: u8,
+		delay: u32,
+	) -> EvmResult {
+		let proxy_type = Self::proxy_type(proxy_type)?;
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let delegate = Runtime::AddressMapping::into_account_id(delegate.into());
+
+		handle.record_db_read::<Runtime>(storage_size::<pallet_proxy::Proxies<Runtime>>())?;
+		if pallet_proxy::Pallet::<Runtime>::find_proxy(&origin, &delegate, None).is_ok() {
+			return Err(revert("Cannot add more than one proxy"));
+		}
+
+		let call = pallet_proxy::Call::<Runtime>::add_proxy {
+			delegate: Runtime::Lookup::unlookup(delegate),
+			proxy_type,
+			delay: delay.into(),
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		Ok(())
+	}
+
+	/// Unregister `delegate` as a proxy of the caller.
+	#[precompile::public("removeProxy(address,uint8,uint32)")]
+	fn remove_proxy(
+		handle: &mut impl PrecompileHandle,
+		delegate: Address,
+		proxy_type: u8,
+		delay: u32,
+	) -> EvmResult {
+		let proxy_type = Self::proxy_type(proxy_type)?;
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let delegate = Runtime::AddressMapping::into_account_id(delegate.into());
+
+		let call = pallet_proxy::Call::<Runtime>::remove_proxy {
+			delegate: Runtime::Lookup::unlookup(delegate),
+			proxy_type,
+			delay: delay.into(),
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		Ok(())
+	}
+
+	/// Unregister all the proxies of the caller.
+	#[precompile::public("removeProxies()")]
+	fn remove_proxies(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_proxy::Call::<Runtime>::remove_proxies {};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		Ok(())
+	}
+
+	/// Call `callTo` on behalf of `real`, of which the caller must be a proxy without delay.
+	/// `value` is transferred from `real`, and the call must be allowed by the proxy type.
+	#[precompile::public("proxy(address,address,uint256,bytes)")]
+	fn proxy(
+		handle: &mut impl PrecompileHandle,
+		real: Address,
+		call_to: Address,
+		value: U256,
+		call_data: BoundedBytes<GetCallDataLimit>,
+	) -> EvmResult<UnboundedBytes> {
+		let real: H160 = real.into();
+		let call_to: H160 = call_to.into();
+		let caller = handle.context().caller;
+
+		handle.record_db_read::<Runtime>(storage_size::<pallet_proxy::Proxies<Runtime>>())?;
+		let proxy = pallet_proxy::Pallet::<Runtime>::find_proxy(
+			&Runtime::AddressMapping::into_account_id(real),
+			&Runtime::AddressMapping::into_account_id(caller),
+			None,
+		)
+		.map_err(|_| revert("Not a proxy"))?;
+		if !proxy.delay.is_zero() {
+			return Err(revert("Cannot call through a proxy with a delay"));
+		}
+
+		let sub_call = EvmSubCall {
+			to: call_to,
+			value,
+			call_data: call_data.into(),
+		};
+		if !proxy.proxy_type.is_evm_proxy_call_allowed(&sub_call) {
+			return Err(revert("Call not allowed by the proxy type"));
+		}
+
+		handle.record_cost(call_cost(value, <Runtime as pallet_evm::Config>::config()))?;
+
+		let sub_context = Context {
+			caller: real,
+			address: call_to,
+			apparent_value: value,
+		};
+		let transfer = if value.is_zero() {
+			None
+		} else {
+			Some(Transfer {
+				source: real,
+				target: call_to,
+				value,
+			})
+		};
+		let gas_limit = handle.remaining_gas();
+		let (reason, output) = handle.call(
+			call_to,
+			transfer,
+			sub_call.call_data,
+			Some(gas_limit),
+			false,
+			&sub_context,
+		);
+
+		match reason {
+			ExitReason::Error(exit_status) => Err(PrecompileFailure::Error { exit_status }),
+			ExitReason::Fatal(exit_status) => Err(PrecompileFailure::Fatal { exit_status }),
+			ExitReason::Revert(_) => Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output,
+			}),
+			ExitReason::Succeed(_) => Ok(output.into()),
+		}
+	}
+
+	/// Whether `delegate` is a proxy of `real` with the given type and delay.
+	#[precompile::public("isProxy(address,address,uint8,uint32)")]
+	#[precompile::view]
+	fn is_proxy(
+		handle: &mut impl PrecompileHandle,
+		real: Address,
+		delegate: Address,
+		proxy_type: u8,
+		delay: u32,
+	) -> EvmResult<bool> {
+		let proxy_type = Self::proxy_type(proxy_type)?;
+		let real = Runtime::AddressMapping::into_account_id(real.into());
+		let delegate = Runtime::AddressMapping::into_account_id(delegate.into());
+		let delay: BlockNumberFor<Runtime> = delay.into();
+
+		handle.record_db_read::<Runtime>(storage_size::<pallet_proxy::Proxies<Runtime>>())?;
+		let (proxies, _) = pallet_proxy::Proxies::<Runtime>::get(real);
+
+		Ok(proxies.iter().any(|proxy| {
+			proxy.delegate == delegate && proxy.proxy_type == proxy_type && proxy.delay == delay
+		}))
+	}
+
+	fn proxy_type(proxy_type: u8) -> EvmResult<<Runtime as pallet_proxy::Config>::ProxyType> {
+		<Runtime as pallet_proxy::Config>::ProxyType::decode(&mut &[proxy_type][..]).map_err(|_| {
+			RevertReason::custom("Failed decoding value to ProxyType")
+				.in_field("proxyType")
+				.into()
+		})
+	}
+}
@@ -0,0 +1,318 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::mock::{ExtBuilder, PCall, Precompiles, PrecompilesValue, Proxy, ProxyType, Runtime};
+use precompile_utils::{prelude::*, testing::*};
+use sp_core::{H160, U256};
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn setup() -> sp_io::TestExternalities {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 1_000), (Bob.into(), 1_000)])
+		.build()
+}
+
+fn add_proxy(real: impl Into<H160>, delegate: impl Into<H160>, proxy_type: ProxyType, delay: u32) {
+	precompiles()
+		.prepare_test(
+			real,
+			Precompile1,
+			PCall::add_proxy {
+				delegate: Address(delegate.into()),
+				proxy_type: proxy_type as u8,
+				delay,
+			},
+		)
+		.execute_returns(());
+}
+
+fn is_proxy(real: impl Into<H160>, delegate: impl Into<H160>, proxy_type: ProxyType) -> bool {
+	let real: H160 = real.into();
+	let delegate: H160 = delegate.into();
+	Proxy::find_proxy(&real.into(), &delegate.into(), Some(proxy_type)).is_ok()
+}
+
+#[test]
+fn modifiers() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mut tester = PrecompilesModifierTester::new(precompiles(), Alice, Precompile1);
+
+		tester.test_default_modifier(PCall::add_proxy_selectors());
+		tester.test_default_modifier(PCall::remove_proxy_selectors());
+		tester.test_default_modifier(PCall::remove_proxies_selectors());
+		tester.test_default_modifier(PCall::proxy_selectors());
+		tester.test_view_modifier(PCall::is_proxy_selectors());
+	});
+}
+
+#[test]
+fn test_solidity_interface_has_all_function_selectors_documented_and_implemented() {
+	check_precompile_implements_solidity_interfaces(&["Proxy.sol"], PCall::supports_selector)
+}
+
+#[test]
+fn add_proxy_registers_delegate() {
+	setup().execute_with(|| {
+		add_proxy(Alice, Bob, ProxyType::NoValue, 0);
+
+		assert!(is_proxy(Alice, Bob, ProxyType::NoValue));
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::is_proxy {
+					real: Address(Alice.into()),
+					delegate: Address(Bob.into()),
+					proxy_type: ProxyType::NoValue as u8,
+					delay: 0,
+				},
+			)
+			.expect_no_logs()
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::is_proxy {
+					real: Address(Alice.into()),
+					delegate: Address(Bob.into()),
+					proxy_type: ProxyType::Any as u8,
+					delay: 0,
+				},
+			)
+			.execute_returns(false);
+	});
+}
+
+#[test]
+fn add_proxy_twice_reverts() {
+	setup().execute_with(|| {
+		add_proxy(Alice, Bob, ProxyType::NoValue, 0);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::add_proxy {
+					delegate: Address(Bob.into()),
+					proxy_type: ProxyType::Any as u8,
+					delay: 0,
+				},
+			)
+			.execute_reverts(|output| output == b"Cannot add more than one proxy");
+	});
+}
+
+#[test]
+fn unknown_proxy_type_reverts() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::add_proxy {
+					delegate: Address(Bob.into()),
+					proxy_type: 7,
+					delay: 0,
+				},
+			)
+			.execute_reverts(|output| output == b"proxyType: Failed decoding value to ProxyType");
+	});
+}
+
+#[test]
+fn remove_proxy_and_proxies() {
+	setup().execute_with(|| {
+		add_proxy(Alice, Bob, ProxyType::Any, 0);
+		add_proxy(Alice, Charlie, ProxyType::NoValue, 0);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::remove_proxy {
+					delegate: Address(Bob.into()),
+					proxy_type: ProxyType::Any as u8,
+					delay: 0,
+				},
+			)
+			.execute_returns(());
+		assert!(!is_proxy(Alice, Bob, ProxyType::Any));
+		assert!(is_proxy(Alice, Charlie, ProxyType::NoValue));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::remove_proxies {})
+			.execute_returns(());
+		assert!(!is_proxy(Alice, Charlie, ProxyType::NoValue));
+	});
+}
+
+#[test]
+fn proxy_calls_on_behalf_of_real() {
+	setup().execute_with(|| {
+		add_proxy(Alice, Bob, ProxyType::Any, 0);
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::proxy {
+					real: Address(Alice.into()),
+					call_to: Address(Charlie.into()),
+					value: 42.into(),
+					call_data: b"input".into(),
+				},
+			)
+			.with_subcall_handle(|subcall| {
+				let Subcall {
+					address,
+					transfer,
+					input,
+					is_static,
+					context,
+					..
+				} = subcall;
+
+				assert_eq!(address, Charlie.into());
+				assert_eq!(input, b"input".to_vec());
+				assert!(!is_static);
+				assert_eq!(context.caller, Alice.into());
+				assert_eq!(context.apparent_value, U256::from(42));
+
+				let transfer = transfer.expect("value is transferred");
+				assert_eq!(transfer.source, Alice.into());
+				assert_eq!(transfer.value, U256::from(42));
+
+				SubcallOutput {
+					output: b"output".to_vec(),
+					..SubcallOutput::succeed()
+				}
+			})
+			.execute_returns(UnboundedBytes::from(b"output".to_vec()));
+	});
+}
+
+#[test]
+fn proxy_reverts_if_caller_is_not_a_proxy() {
+	setup().execute_with(|| {
+		add_proxy(Alice, Bob, ProxyType::Any, 0);
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::proxy {
+					real: Address(Alice.into()),
+					call_to: Address(Charlie.into()),
+					value: U256::zero(),
+					call_data: b"input".into(),
+				},
+			)
+			.with_subcall_handle(|_| panic!("subcall should not be made"))
+			.execute_reverts(|output| output == b"Not a proxy");
+	});
+}
+
+#[test]
+fn proxy_with_delay_reverts() {
+	setup().execute_with(|| {
+		add_proxy(Alice, Bob, ProxyType::Any, 1);
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::proxy {
+					real: Address(Alice.into()),
+					call_to: Address(Charlie.into()),
+					value: U256::zero(),
+					call_data: b"input".into(),
+				},
+			)
+			.with_subcall_handle(|_| panic!("subcall should not be made"))
+			.execute_reverts(|output| output == b"Cannot call through a proxy with a delay");
+	});
+}
+
+#[test]
+fn proxy_type_filters_calls() {
+	setup().execute_with(|| {
+		add_proxy(Alice, Bob, ProxyType::NoValue, 0);
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::proxy {
+					real: Address(Alice.into()),
+					call_to: Address(Charlie.into()),
+					value: 1.into(),
+					call_data: b"input".into(),
+				},
+			)
+			.with_subcall_handle(|_| panic!("subcall should not be made"))
+			.execute_reverts(|output| output == b"Call not allowed by the proxy type");
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::proxy {
+					real: Address(Alice.into()),
+					call_to: Address(Charlie.into()),
+					value: U256::zero(),
+					call_data: b"input".into(),
+				},
+			)
+			.with_subcall_handle(|subcall| {
+				assert!(subcall.transfer.is_none());
+				SubcallOutput::succeed()
+			})
+			.execute_returns(UnboundedBytes::from(vec![]));
+	});
+}
+
+#[test]
+fn reverted_subcall_reverts() {
+	setup().execute_with(|| {
+		add_proxy(Alice, Bob, ProxyType::Any, 0);
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::proxy {
+					real: Address(Alice.into()),
+					call_to: Address(Charlie.into()),
+					value: U256::zero(),
+					call_data: b"input".into(),
+				},
+			)
+			.with_subcall_handle(|_| SubcallOutput {
+				// Encoded `Error(string)` revert reason.
+				output: solidity::encode_with_selector(
+					0x08c379a0u32,
+					(UnboundedString::from("reverted"),),
+				),
+				..SubcallOutput::revert()
+			})
+			.execute_reverts(|output| output == b"reverted");
+	});
+}
@@ -0,0 +1,161 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Randomness precompile. Exposes the randomness of `pallet-evm-randomness` to contracts: the
+//! VRF randomness of the parent block and the epoch randomness can be read directly, and the
+//! randomness of a future block can be requested against a deposit, then fulfilled once that
+//! block has been produced. Requests that expired before being fulfilled can be purged by anyone.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::MaxEncodedLen;
+use core::marker::PhantomData;
+use fp_evm::ACCOUNT_BASIC_PROOF_SIZE;
+use frame_support::traits::Get;
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_evm_randomness::{BlockRandomness, RandomnessRequest, RequestId};
+use precompile_utils::{prelude::*, EvmResult};
+use sp_core::{H256, U256};
+use sp_runtime::traits::UniqueSaturatedInto;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// Storage key of a request: Twox64Concat(8) + RequestId(8).
+const REQUEST_KEY_SIZE: usize = 8 + 8;
+// Storage key of a block randomness: Twox64Concat(8), followed by the block number.
+const RESULT_KEY_SIZE: usize = 8;
+// Storage value of the next request id.
+const REQUEST_ID_SIZE: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct RandomnessPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> RandomnessPrecompile<Runtime>
+where
+	Runtime: pallet_evm_randomness::Config,
+{
+	fn request_storage_size() -> usize {
+		REQUEST_KEY_SIZE + RandomnessRequest::<Runtime>::max_encoded_len()
+	}
+
+	fn result_storage_size() -> usize {
+		RESULT_KEY_SIZE
+			+ BlockNumberFor::<Runtime>::max_encoded_len()
+			+ BlockRandomness::max_encoded_len()
+	}
+
+	/// Randomness derived from the VRF output of the parent block. Reverts if the parent block
+	/// carries no VRF output.
+	#[precompile::public("blockRandomness()")]
+	#[precompile::view]
+	fn block_randomness(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		handle.record_db_read::<Runtime>(H256::max_encoded_len() + 1)?;
+
+		pallet_evm_randomness::Pallet::<Runtime>::vrf_randomness()
+			.ok_or_else(|| revert("No VRF output for the parent block"))
+	}
+
+	/// Randomness of the current epoch.
+	#[precompile::public("epochRandomness()")]
+	#[precompile::view]
+	fn epoch_randomness(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		handle.record_db_read::<Runtime>(H256::max_encoded_len())?;
+
+		Ok(pallet_evm_randomness::Pallet::<Runtime>::epoch_randomness())
+	}
+
+	/// Deposit locked by each request until it is fulfilled or purged.
+	#[precompile::public("requestDeposit()")]
+	#[precompile::view]
+	fn request_deposit(_handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		let deposit: u128 =
+			<Runtime as pallet_evm_randomness::Config>::Deposit::get().unique_saturated_into();
+		Ok(deposit.into())
+	}
+
+	/// Request the randomness of the block `delay` blocks from now, locking the deposit of the
+	/// caller. Returns the id of the request.
+	#[precompile::public("requestRandomness(bytes32,uint32)")]
+	fn request_randomness(
+		handle: &mut impl PrecompileHandle,
+		salt: H256,
+		delay: u32,
+	) -> EvmResult<RequestId> {
+		handle.record_db_read::<Runtime>(REQUEST_ID_SIZE)?;
+		handle.record_db_read::<Runtime>(Self::result_storage_size())?;
+		handle.record_db_read::<Runtime>(ACCOUNT_BASIC_PROOF_SIZE as usize)?;
+		handle.record_db_read::<Runtime>(ACCOUNT_BASIC_PROOF_SIZE as usize)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(5))?;
+
+		let requester = handle.context().caller;
+		pallet_evm_randomness::Pallet::<Runtime>::request_randomness(requester, salt, delay)
+			.map_err(|e| revert(<&'static str>::from(e)))
+	}
+
+	/// Whether request `request_id` can be fulfilled.
+	#[precompile::public("isRequestReady(uint64)")]
+	#[precompile::view]
+	fn is_request_ready(
+		handle: &mut impl PrecompileHandle,
+		request_id: RequestId,
+	) -> EvmResult<bool> {
+		handle.record_db_read::<Runtime>(Self::request_storage_size())?;
+		handle.record_db_read::<Runtime>(Self::result_storage_size())?;
+
+		Ok(pallet_evm_randomness::Pallet::<Runtime>::is_ready(
+			request_id,
+		))
+	}
+
+	/// Fulfil request `request_id` of the caller, returning its result and refunding its deposit.
+	#[precompile::public("fulfilRandomness(uint64)")]
+	fn fulfil_randomness(
+		handle: &mut impl PrecompileHandle,
+		request_id: RequestId,
+	) -> EvmResult<H256> {
+		handle.record_db_read::<Runtime>(Self::request_storage_size())?;
+		handle.record_db_read::<Runtime>(Self::result_storage_size())?;
+		handle.record_db_read::<Runtime>(ACCOUNT_BASIC_PROOF_SIZE as usize)?;
+		handle.record_db_read::<Runtime>(ACCOUNT_BASIC_PROOF_SIZE as usize)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(4))?;
+
+		let requester = handle.context().caller;
+		pallet_evm_randomness::Pallet::<Runtime>::fulfil_randomness(requester, request_id)
+			.map_err(|e| revert(<&'static str>::from(e)))
+	}
+
+	/// Purge expired request `request_id`, refunding its deposit to its requester. Can be called
+	/// by anyone.
+	#[precompile::public("purgeExpiredRequest(uint64)")]
+	fn purge_expired_request(
+		handle: &mut impl PrecompileHandle,
+		request_id: RequestId,
+	) -> EvmResult {
+		handle.record_db_read::<Runtime>(Self::request_storage_size())?;
+		handle.record_db_read::<Runtime>(Self::result_storage_size())?;
+		handle.record_db_read::<Runtime>(ACCOUNT_BASIC_PROOF_SIZE as usize)?;
+		handle.record_db_read::<Runtime>(ACCOUNT_BASIC_PROOF_SIZE as usize)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(4))?;
+
+		pallet_evm_randomness::Pallet::<Runtime>::purge_expired_request(request_id)
+			.map_err(|e| revert(<&'static str>::from(e)))
+	}
+}
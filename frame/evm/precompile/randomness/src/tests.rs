@@ -0,0 +1,169 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::mock::{
+	run_to_block, AccountId, Balances, ExtBuilder, PCall, Precompiles, PrecompilesValue, Runtime,
+};
+use codec::Encode;
+use precompile_utils::testing::*;
+use sp_core::{H256, U256};
+
+const SALT: H256 = H256::repeat_byte(0x5a);
+const VRF_OUTPUT: H256 = H256::repeat_byte(0x42);
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn setup() -> sp_io::TestExternalities {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 1_000_000)])
+		.build()
+}
+
+fn request(delay: u32) -> PCall {
+	PCall::request_randomness { salt: SALT, delay }
+}
+
+#[test]
+fn block_and_epoch_randomness() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::block_randomness {})
+			.execute_reverts(|output| output == b"No VRF output for the parent block");
+
+		run_to_block(2, Some(VRF_OUTPUT));
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::block_randomness {})
+			.expect_no_logs()
+			.execute_returns(VRF_OUTPUT);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::epoch_randomness {})
+			.expect_no_logs()
+			.execute_returns(H256::repeat_byte(0xee));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::request_deposit {})
+			.execute_returns(U256::from(1_000));
+	})
+}
+
+#[test]
+fn request_and_fulfil_randomness() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, request(2))
+			.execute_returns(0u64);
+		assert_eq!(Balances::free_balance(AccountId::from(Alice)), 999_000);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::is_request_ready { request_id: 0 },
+			)
+			.execute_returns(false);
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::fulfil_randomness { request_id: 0 },
+			)
+			.execute_reverts(|output| output == b"RandomnessNotReady");
+
+		run_to_block(4, Some(VRF_OUTPUT));
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::is_request_ready { request_id: 0 },
+			)
+			.execute_returns(true);
+
+		let expected: H256 = sp_io::hashing::keccak_256(&(VRF_OUTPUT, SALT, 0u64).encode()).into();
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::fulfil_randomness { request_id: 0 },
+			)
+			.execute_returns(expected);
+		assert_eq!(Balances::free_balance(AccountId::from(Alice)), 1_000_000);
+	})
+}
+
+#[test]
+fn only_requester_can_fulfil() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, request(2))
+			.execute_returns(0u64);
+		run_to_block(4, Some(VRF_OUTPUT));
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, PCall::fulfil_randomness { request_id: 0 })
+			.execute_reverts(|output| output == b"NotRequester");
+	})
+}
+
+#[test]
+fn request_delay_is_bounded() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, request(1))
+			.execute_reverts(|output| output == b"DelayTooShort");
+		precompiles()
+			.prepare_test(Alice, Precompile1, request(11))
+			.execute_reverts(|output| output == b"DelayTooLong");
+	})
+}
+
+#[test]
+fn anyone_can_purge_expired_request() {
+	setup().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, request(2))
+			.execute_returns(0u64);
+
+		run_to_block(8, Some(VRF_OUTPUT));
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::purge_expired_request { request_id: 0 },
+			)
+			.execute_reverts(|output| output == b"RequestNotExpired");
+
+		run_to_block(9, Some(VRF_OUTPUT));
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::fulfil_randomness { request_id: 0 },
+			)
+			.execute_reverts(|output| output == b"RequestExpired");
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::purge_expired_request { request_id: 0 },
+			)
+			.execute_returns(());
+		assert_eq!(Balances::free_balance(AccountId::from(Alice)), 1_000_000);
+	})
+}
@@ -141,8 +141,11 @@ impl pallet_evm::Config for Runtime {
 	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
 	type FindAuthor = ();
 	type OnCreate = ();
+	type CreateFilter = ();
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = ();
 	type Timestamp = Timestamp;
+	type BlockRandomness = ();
 	type WeightInfo = ();
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 }
@@ -15,7 +15,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::mock::{ExtBuilder, PCall, Precompiles, PrecompilesValue, Runtime};
+use crate::mock::{ExtBuilder, PCall, Precompiles, PrecompilesValue, Runtime, System};
+use fp_evm::{ACCOUNT_BASIC_PROOF_SIZE, ACCOUNT_STORAGE_PROOF_SIZE};
+use frame_support::{traits::Hooks, weights::Weight};
 use pallet_evm::AddressMapping;
 use precompile_utils::{solidity::codec::Address, testing::*};
 use rlp::RlpStream;
@@ -334,3 +336,68 @@ fn test_clear_suicided_contract_limit_respected() {
 			));
 		})
 }
+
+// Test that `on_idle` resumes after the entries the precompile removed in the same block
+#[test]
+fn test_on_idle_resumes_after_precompile_in_same_block() {
+	let mut ext = ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 10000000000000000000)])
+		.build();
+	let suicided_address = ext.execute_with(|| {
+		let suicided_address = mock_contracts([10])[0].0;
+		pallet_evm::Suicided::<Runtime>::insert(suicided_address, ());
+		suicided_address
+	});
+	ext.commit_all().unwrap();
+
+	// Enough proof size to read the contract, remove three entries and clear the contract.
+	let idle_weight = Weight::from_parts(
+		0,
+		36 + 3 * ACCOUNT_STORAGE_PROOF_SIZE + ACCOUNT_BASIC_PROOF_SIZE,
+	);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::clear_suicided_storage {
+					addresses: vec![suicided_address.into()].into(),
+					limit: 5,
+				},
+			)
+			.execute_returns(());
+		assert_eq!(
+			pallet_evm::AccountStorages::<Runtime>::iter_prefix(suicided_address).count(),
+			6
+		);
+
+		pallet_evm::Pallet::<Runtime>::on_idle(1, idle_weight);
+
+		assert_eq!(
+			pallet_evm::AccountStorages::<Runtime>::iter_prefix(suicided_address).count(),
+			3
+		);
+		System::assert_last_event(
+			pallet_evm::Event::SuicidedStorageRemoved {
+				address: suicided_address,
+				entries: 3,
+			}
+			.into(),
+		);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		System::set_block_number(2);
+		pallet_evm::Pallet::<Runtime>::on_idle(2, Weight::MAX);
+
+		assert_eq!(
+			pallet_evm::AccountStorages::<Runtime>::iter_prefix(suicided_address).count(),
+			0
+		);
+		assert!(!pallet_evm::Suicided::<Runtime>::contains_key(
+			suicided_address
+		));
+	});
+}
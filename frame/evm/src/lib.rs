@@ -165,21 +165,31 @@ pub mod pallet {
 		/// Similar to `OnChargeTransaction` of `pallet_transaction_payment`
 		type OnChargeTransaction: OnChargeEVMTransaction<Self>;
 
-		/// Called on create calls, used to record owner
+		/// Called after a successful create call, used to record owner
 		type OnCreate: OnCreate<Self>;
 
+		/// Filter for contract deployments, both from transactions and from contracts.
+		type CreateFilter: CreateFilter;
+
 		/// Find author for the current block.
 		type FindAuthor: FindAuthor<H160>;
 
 		/// Gas limit Pov size ratio.
 		type GasLimitPovSizeRatio: Get<u64>;
 
+		/// Gas limit storage growth ratio: the gas charged for every byte an execution adds to
+		/// the state (new storage slots, contract code and its metadata). Set to 0 to disable it.
+		type GasLimitStorageGrowthRatio: Get<u64>;
+
 		/// Define the quick clear limit of storage clearing when a contract suicides. Set to 0 to disable it.
 		type SuicideQuickClearLimit: Get<u32>;
 
 		/// Get the timestamp for the current block.
 		type Timestamp: Time;
 
+		/// Randomness of the current block, exposed to contracts through `PREVRANDAO`.
+		type BlockRandomness: Get<Option<H256>>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -189,6 +199,13 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::clear_suicided_storage(remaining_weight)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Withdraw balance from EVM into currency/balances pallet.
@@ -477,6 +494,10 @@ pub mod pallet {
 		Executed { address: H160 },
 		/// A contract has been executed with errors. States are reverted with only gas fees applied.
 		ExecutedFailed { address: H160 },
+		/// Storage entries of a self-destructed contract have been removed.
+		SuicidedStorageRemoved { address: H160, entries: u32 },
+		/// All the storage of a self-destructed contract has been removed.
+		SuicidedContractCleared { address: H160 },
 	}
 
 	#[pallet::error]
@@ -507,6 +528,10 @@ pub mod pallet {
 		TransactionMustComeFromEOA,
 		/// Undefined error.
 		Undefined,
+		/// The source is not allowed to deploy contracts.
+		DeploymentNotAllowed,
+		/// The fee could be charged neither to the sponsor of the transaction nor to its source.
+		FeeSponsorshipUnavailable,
 	}
 
 	impl<T> From<TransactionValidationError> for Error<T> {
@@ -523,6 +548,9 @@ pub mod pallet {
 				TransactionValidationError::InvalidChainId => Error::<T>::InvalidChainId,
 				TransactionValidationError::InvalidSignature => Error::<T>::InvalidSignature,
 				TransactionValidationError::UnknownError => Error::<T>::Undefined,
+				TransactionValidationError::DeploymentNotAllowed => {
+					Error::<T>::DeploymentNotAllowed
+				}
 			}
 		}
 	}
@@ -634,6 +662,12 @@ pub trait EnsureAddressOrigin<OuterOrigin> {
 		address: &H160,
 		origin: OuterOrigin,
 	) -> Result<Self::Success, OuterOrigin>;
+
+	/// Returns an address and an origin which passes the check for it, if there is one.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<(H160, OuterOrigin), ()> {
+		Err(())
+	}
 }
 
 /// Ensure that the EVM address is the same as the Substrate address. This only works if the account
@@ -652,6 +686,12 @@ where
 			r => Err(OuterOrigin::from(r)),
 		})
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<(H160, OuterOrigin), ()> {
+		let address = H160::repeat_byte(0x01);
+		Ok((address, OuterOrigin::from(RawOrigin::Signed(address))))
+	}
 }
 
 /// Ensure that the origin is root.
@@ -669,6 +709,11 @@ where
 			r => Err(OuterOrigin::from(r)),
 		})
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<(H160, OuterOrigin), ()> {
+		Ok((H160::zero(), OuterOrigin::from(RawOrigin::Root)))
+	}
 }
 
 /// Ensure that the origin never happens.
@@ -700,6 +745,13 @@ where
 			r => Err(OuterOrigin::from(r)),
 		})
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<(H160, OuterOrigin), ()> {
+		let who = AccountId32::new([0x01; 32]);
+		let address = H160::from_slice(&AsRef::<[u8; 32]>::as_ref(&who)[0..20]);
+		Ok((address, OuterOrigin::from(RawOrigin::Signed(who))))
+	}
 }
 
 /// Ensure that the address is AccountId20.
@@ -718,6 +770,42 @@ where
 			r => Err(OuterOrigin::from(r)),
 		})
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<(H160, OuterOrigin), ()> {
+		let address = H160::repeat_byte(0x01);
+		let who = AccountId20::from(address);
+		Ok((address, OuterOrigin::from(RawOrigin::Signed(who))))
+	}
+}
+
+/// Ensure that the address is the one derived from the account of a signed origin by `M`. Any
+/// signed origin, including multisig and proxied accounts, can then act as an EVM sender.
+pub struct EnsureAddressMapped<AccountId, M>(core::marker::PhantomData<(AccountId, M)>);
+
+impl<OuterOrigin, AccountId, M> EnsureAddressOrigin<OuterOrigin>
+	for EnsureAddressMapped<AccountId, M>
+where
+	OuterOrigin: Into<Result<RawOrigin<AccountId>, OuterOrigin>> + From<RawOrigin<AccountId>>,
+	AccountId: Decode,
+	M: AccountIdMapping<AccountId>,
+{
+	type Success = AccountId;
+
+	fn try_address_origin(address: &H160, origin: OuterOrigin) -> Result<AccountId, OuterOrigin> {
+		origin.into().and_then(|o| match o {
+			RawOrigin::Signed(who) if &M::into_address(&who) == address => Ok(who),
+			r => Err(OuterOrigin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<(H160, OuterOrigin), ()> {
+		let who = AccountId::decode(&mut sp_runtime::traits::TrailingZeroInput::zeroes())
+			.map_err(|_| ())?;
+		let address = M::into_address(&who);
+		Ok((address, OuterOrigin::from(RawOrigin::Signed(who))))
+	}
 }
 
 /// Trait to be implemented for evm address mapping.
@@ -748,6 +836,30 @@ impl<H: Hasher<Out = H256>> AddressMapping<AccountId32> for HashedAddressMapping
 	}
 }
 
+/// Trait to be implemented for deriving the evm address an account acts as.
+pub trait AccountIdMapping<A> {
+	fn into_address(account_id: &A) -> H160;
+}
+
+impl<T: Clone + Into<H160>> AccountIdMapping<T> for IdentityAddressMapping {
+	fn into_address(account_id: &T) -> H160 {
+		account_id.clone().into()
+	}
+}
+
+/// The address is the first 20 bytes of the hash of the account, so it is not mapped back to
+/// the same account: its balance is held by the account `into_account_id` maps it to.
+impl<H: Hasher<Out = H256>> AccountIdMapping<AccountId32> for HashedAddressMapping<H> {
+	fn into_address(account_id: &AccountId32) -> H160 {
+		let mut data = [0u8; 36];
+		data[0..4].copy_from_slice(b"sub:");
+		data[4..36].copy_from_slice(AsRef::<[u8; 32]>::as_ref(account_id));
+		let hash = H::hash(&data);
+
+		H160::from_slice(&hash[0..20])
+	}
+}
+
 /// A trait for getting a block hash by number.
 pub trait BlockHashMapping {
 	fn block_hash(number: u32) -> H256;
@@ -850,6 +962,79 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Remove the storage of self-destructed contracts within `limit`, returning the consumed
+	/// weight.
+	///
+	/// Contracts are drained one at a time. Once all the storage of a contract has been removed,
+	/// it is no longer considered suicided and its sufficient reference is released.
+	pub fn clear_suicided_storage(limit: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// Suicided: Blake2_128(16) + H160(20)
+		let read_suicided = db_weight.reads(1).saturating_add(Weight::from_parts(0, 36));
+		let remove_entry = db_weight
+			.reads_writes(1, 1)
+			.saturating_add(Weight::from_parts(0, fp_evm::ACCOUNT_STORAGE_PROOF_SIZE));
+		let clear_contract = db_weight
+			.reads_writes(1, 2)
+			.saturating_add(Weight::from_parts(0, fp_evm::ACCOUNT_BASIC_PROOF_SIZE));
+
+		let mut consumed = Weight::zero();
+		loop {
+			// Keep enough weight to read the next contract and clear it once its storage is gone.
+			let reserved = read_suicided.saturating_add(clear_contract);
+			let Some(available) = limit.checked_sub(&consumed.saturating_add(reserved)) else {
+				break;
+			};
+			let max_entries = available
+				.ref_time()
+				.checked_div(remove_entry.ref_time())
+				.unwrap_or(u64::MAX)
+				.min(
+					available
+						.proof_size()
+						.checked_div(remove_entry.proof_size())
+						.unwrap_or(u64::MAX),
+				)
+				.min(u32::MAX.into()) as u32;
+			if max_entries == 0 {
+				break;
+			}
+
+			consumed.saturating_accrue(read_suicided);
+			let Some(address) = <Suicided<T>>::iter_keys().next() else {
+				break;
+			};
+
+			// `clear_prefix` does not resume from a cursor and would spend its limit on the entries
+			// the storage cleaner precompile already removed in this block, while iterating the
+			// keys only yields the remaining ones.
+			let entries: Vec<H256> = <AccountStorages<T>>::iter_key_prefix(address)
+				.take(max_entries as usize)
+				.collect();
+			consumed.saturating_accrue(remove_entry.saturating_mul(entries.len() as u64));
+			for index in &entries {
+				<AccountStorages<T>>::remove(address, index);
+			}
+			if !entries.is_empty() {
+				Self::deposit_event(Event::SuicidedStorageRemoved {
+					address,
+					entries: entries.len() as u32,
+				});
+			}
+			if entries.len() as u32 == max_entries {
+				break;
+			}
+
+			consumed.saturating_accrue(clear_contract);
+			<Suicided<T>>::remove(address);
+			let account_id = T::AddressMapping::into_account_id(address);
+			let _ = frame_system::Pallet::<T>::dec_sufficients(&account_id);
+			Self::deposit_event(Event::SuicidedContractCleared { address });
+		}
+
+		consumed
+	}
+
 	/// Create an account.
 	pub fn create_account(address: H160, code: Vec<u8>) {
 		if <Suicided<T>>::contains_key(address) {
@@ -983,6 +1168,23 @@ pub trait OnChargeEVMTransaction<T: Config> {
 
 	/// Introduced in EIP1559 to handle the priority tip.
 	fn pay_priority_fee(tip: Self::LiquidityInfo);
+
+	/// Same as `withdraw_fee`, but with the transaction destination and input available, so
+	/// that the fee can be charged to an account other than `who`. Defaults to `withdraw_fee`.
+	fn withdraw_fee_for_call(
+		who: &H160,
+		_target: Option<H160>,
+		_input: &[u8],
+		fee: U256,
+	) -> Result<Self::LiquidityInfo, Error<T>> {
+		Self::withdraw_fee(who, fee)
+	}
+
+	/// Whether the fee of a transaction from `who` is paid by an account other than `who`.
+	/// Used during validation, where only the transferred value is then required from `who`.
+	fn is_fee_sponsored(_who: &H160, _target: Option<H160>, _input: &[u8], _fee: U256) -> bool {
+		false
+	}
 }
 
 /// Implements the transaction payment for a pallet implementing the `Currency`
@@ -1192,19 +1394,37 @@ where
 	}
 }
 
+/// Hook run after a contract has been deployed by a `create` or `create2` call.
+///
+/// Contracts deployed by other contracts through the `CREATE`/`CREATE2` opcodes are not
+/// reported.
 pub trait OnCreate<T> {
-	fn on_create(owner: H160, contract: H160);
+	/// `init_code_hash` is the keccak-256 hash of the init code used for the deployment.
+	fn on_create(owner: H160, contract: H160, init_code_hash: H256);
 }
 
 impl<T> OnCreate<T> for () {
-	fn on_create(_owner: H160, _contract: H160) {}
+	fn on_create(_owner: H160, _contract: H160, _init_code_hash: H256) {}
 }
 
 #[impl_for_tuples(1, 12)]
 impl<T> OnCreate<T> for Tuple {
-	fn on_create(owner: H160, contract: H160) {
+	fn on_create(owner: H160, contract: H160, init_code_hash: H256) {
 		for_tuples!(#(
-			Tuple::on_create(owner, contract);
+			Tuple::on_create(owner, contract, init_code_hash);
 		)*)
 	}
 }
+
+/// Decides which addresses may deploy contracts.
+pub trait CreateFilter {
+	/// Whether `deployer` may deploy a contract. `init_code_hash` is the hash of the creation
+	/// code, which is only known for contracts deployed by a transaction.
+	fn can_create(deployer: &H160, init_code_hash: Option<H256>) -> bool;
+}
+
+impl CreateFilter for () {
+	fn can_create(_deployer: &H160, _init_code_hash: Option<H256>) -> bool {
+		true
+	}
+}
@@ -0,0 +1,168 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage growth metering.
+
+use evm::{
+	gasometer::{GasCost, StorageTarget},
+	Opcode,
+};
+use fp_evm::{ACCOUNT_CODES_METADATA_PROOF_SIZE, ACCOUNT_STORAGE_PROOF_SIZE};
+use sp_core::{H256, U256};
+
+/// An error that is returned when the storage limit is exceeded.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MeterError {
+	LimitExceeded,
+}
+
+/// A meter for the number of bytes an EVM execution adds to the state.
+///
+/// The limit is derived from the gas limit of the execution through
+/// `GasLimitStorageGrowthRatio`, so that every byte of new storage is paid for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageMeter {
+	usage: u64,
+	limit: u64,
+}
+
+impl StorageMeter {
+	/// Creates a new storage meter with the given limit.
+	pub fn new(limit: u64) -> Self {
+		Self { usage: 0, limit }
+	}
+
+	/// Records the given amount of storage growth, failing if it exceeds the limit.
+	pub fn record(&mut self, amount: u64) -> Result<(), MeterError> {
+		let usage = self
+			.usage
+			.checked_add(amount)
+			.ok_or(MeterError::LimitExceeded)?;
+		if usage > self.limit {
+			return Err(MeterError::LimitExceeded);
+		}
+		self.usage = usage;
+		Ok(())
+	}
+
+	/// Records the storage growth of a code deposit of `len` bytes, including its metadata.
+	pub fn record_code_deposit(&mut self, len: U256) -> Result<(), MeterError> {
+		if len > U256::from(u64::MAX) {
+			return Err(MeterError::LimitExceeded);
+		}
+		self.record(ACCOUNT_CODES_METADATA_PROOF_SIZE.saturating_add(len.low_u64()))
+	}
+
+	/// Records the storage growth of a dynamic opcode.
+	///
+	/// Only an `SSTORE` that writes a non-zero value to a slot that is empty both in the
+	/// state and in the current transaction creates a new `AccountStorages` entry.
+	pub fn record_dynamic_opcode_cost(
+		&mut self,
+		opcode: Opcode,
+		gas_cost: GasCost,
+		target: StorageTarget,
+	) -> Result<(), MeterError> {
+		match (opcode, gas_cost, target) {
+			(
+				Opcode::SSTORE,
+				GasCost::SStore {
+					original,
+					current,
+					new,
+					..
+				},
+				StorageTarget::Slot(_, _),
+			) if original == H256::zero() && current == H256::zero() && new != H256::zero() => {
+				self.record(ACCOUNT_STORAGE_PROOF_SIZE)
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Returns the storage growth recorded so far, in bytes.
+	pub fn usage(&self) -> u64 {
+		self.usage
+	}
+
+	/// Returns the storage growth that can still be recorded, in bytes.
+	pub fn available(&self) -> u64 {
+		self.limit.saturating_sub(self.usage)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H160;
+
+	fn sstore(original: u64, current: u64, new: u64) -> GasCost {
+		GasCost::SStore {
+			original: H256::from_low_u64_be(original),
+			current: H256::from_low_u64_be(current),
+			new: H256::from_low_u64_be(new),
+			target_is_cold: false,
+		}
+	}
+
+	#[test]
+	fn record_fails_above_limit() {
+		let mut meter = StorageMeter::new(100);
+		assert_eq!(meter.record(60), Ok(()));
+		assert_eq!(meter.record(50), Err(MeterError::LimitExceeded));
+		assert_eq!(meter.usage(), 60);
+		assert_eq!(meter.available(), 40);
+		assert_eq!(meter.record(40), Ok(()));
+		assert_eq!(meter.available(), 0);
+	}
+
+	#[test]
+	fn only_new_slots_are_recorded() {
+		let mut meter = StorageMeter::new(1_000);
+		let target = StorageTarget::Slot(H160::default(), H256::default());
+
+		for gas_cost in [
+			sstore(0, 1, 2),
+			sstore(1, 0, 2),
+			sstore(0, 0, 0),
+			sstore(1, 1, 0),
+		] {
+			assert_eq!(
+				meter.record_dynamic_opcode_cost(Opcode::SSTORE, gas_cost, target),
+				Ok(())
+			);
+		}
+		assert_eq!(meter.usage(), 0);
+
+		assert_eq!(
+			meter.record_dynamic_opcode_cost(Opcode::SSTORE, sstore(0, 0, 1), target),
+			Ok(())
+		);
+		assert_eq!(meter.usage(), ACCOUNT_STORAGE_PROOF_SIZE);
+	}
+
+	#[test]
+	fn code_deposit_includes_metadata() {
+		let mut meter = StorageMeter::new(1_000);
+		assert_eq!(meter.record_code_deposit(U256::from(100)), Ok(()));
+		assert_eq!(meter.usage(), 100 + ACCOUNT_CODES_METADATA_PROOF_SIZE);
+		assert_eq!(
+			meter.record_code_deposit(U256::from(u64::MAX) + 1),
+			Err(MeterError::LimitExceeded)
+		);
+	}
+}
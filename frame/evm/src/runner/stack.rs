@@ -47,14 +47,72 @@ use fp_evm::{
 };
 
 use crate::{
-	runner::Runner as RunnerT, AccountCodes, AccountCodesMetadata, AccountStorages, AddressMapping,
-	BalanceOf, BlockHashMapping, Config, Error, Event, FeeCalculator, OnChargeEVMTransaction,
+	runner::{meter::StorageMeter, Runner as RunnerT},
+	AccountCodes, AccountCodesMetadata, AccountStorages, AddressMapping, BalanceOf,
+	BlockHashMapping, Config, CreateFilter, Error, Event, FeeCalculator, OnChargeEVMTransaction,
 	OnCreate, Pallet, RunnerError,
 };
 
 #[cfg(feature = "forbid-evm-reentrancy")]
 environmental::thread_local_impl!(static IN_EVM: environmental::RefCell<bool> = environmental::RefCell::new(false));
 
+/// Accounts and storage slots recorded by [`with_access_recorder`].
+#[derive(Default)]
+struct AccessRecorder {
+	accessed: BTreeMap<H160, BTreeSet<H256>>,
+	/// Contracts created by the recorded executions, warm like a call target.
+	created: BTreeSet<H160>,
+}
+
+environmental::thread_local_impl!(static ACCESS_RECORDER: environmental::GlobalInner<AccessRecorder> = Default::default());
+
+/// An EIP-2930 access list, as recorded by [`with_access_recorder`].
+pub type AccessList = Vec<(H160, Vec<H256>)>;
+
+/// Run `f`, recording every account and storage slot accessed by the EVM executions it performs.
+///
+/// The transaction source, the call target or created contract, the precompiles and, from
+/// Shanghai, the coinbase are warm regardless of the access list, so they are only included if
+/// some of their slots are touched.
+pub fn with_access_recorder<R>(f: impl FnOnce() -> R) -> (R, AccessList) {
+	let mut recorder = AccessRecorder::default();
+	let res = environmental::using(&ACCESS_RECORDER, &mut recorder, f);
+	let access_list = recorder
+		.accessed
+		.into_iter()
+		.map(|(address, slots)| (address, slots.into_iter().collect()))
+		.collect();
+	(res, access_list)
+}
+
+environmental::thread_local_impl!(static TRANSFER_LOGS: environmental::GlobalInner<()> = Default::default());
+
+/// Address emitting the synthetic logs of [`with_transfer_logs`].
+pub const TRANSFER_LOG_ADDRESS: H160 = H160([0xee; 20]);
+
+/// Topic of the synthetic logs of [`with_transfer_logs`], `Transfer(address,address,uint256)`.
+pub const TRANSFER_LOG_TOPIC: H256 = H256(hex_literal::hex!(
+	"ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+));
+
+/// Run `f`, emitting an ERC-20 like `Transfer` log from [`TRANSFER_LOG_ADDRESS`] for every
+/// non-zero native value transfer performed by the EVM executions it performs.
+pub fn with_transfer_logs<R>(f: impl FnOnce() -> R) -> R {
+	environmental::using(&TRANSFER_LOGS, &mut (), f)
+}
+
+environmental::thread_local_impl!(static CREATE_OUTPUT: environmental::GlobalInner<Vec<u8>> = Default::default());
+
+/// Run `f`, returning the output of the last contract creation it performs along with its result.
+///
+/// [`CreateInfo`] only carries the address of the contract, this keeps the revert data of a
+/// reverted creation. The output of a successful creation is empty.
+pub fn with_create_output<R>(f: impl FnOnce() -> R) -> (R, Vec<u8>) {
+	let mut output = Vec::new();
+	let res = environmental::using(&CREATE_OUTPUT, &mut output, f);
+	(res, output)
+}
+
 #[derive(Default)]
 pub struct Runner<T: Config> {
 	_marker: PhantomData<T>,
@@ -68,6 +126,8 @@ where
 	/// Execute an already validated EVM operation.
 	fn execute<'config, 'precompiles, F, R>(
 		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
 		value: U256,
 		gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
@@ -87,6 +147,7 @@ where
 				SubstrateStackState<'_, 'config, T>,
 				T::PrecompilesType,
 			>,
+			Vec<u8>,
 		) -> (ExitReason, R),
 		R: Default,
 	{
@@ -102,6 +163,8 @@ where
 
 		let res = Self::execute_inner(
 			source,
+			target,
+			input,
 			value,
 			gas_limit,
 			max_fee_per_gas,
@@ -127,6 +190,8 @@ where
 	// Execute an already validated EVM operation.
 	fn execute_inner<'config, 'precompiles, F, R>(
 		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
 		value: U256,
 		mut gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
@@ -148,6 +213,7 @@ where
 				SubstrateStackState<'_, 'config, T>,
 				T::PrecompilesType,
 			>,
+			Vec<u8>,
 		) -> (ExitReason, R),
 		R: Default,
 	{
@@ -192,6 +258,18 @@ where
 			});
 		}
 
+		// Deployments must be allowed by the `CreateFilter`. Those from contracts are checked
+		// during execution, see `SubstrateStackState::inc_nonce`.
+		if target.is_none() {
+			let init_code_hash = H256::from(sp_io::hashing::keccak_256(&input));
+			if !T::CreateFilter::can_create(&source, Some(init_code_hash)) {
+				return Err(RunnerError {
+					error: Error::<T>::DeploymentNotAllowed,
+					weight,
+				});
+			}
+		}
+
 		let total_fee_per_gas = if is_transactional {
 			match (max_fee_per_gas, max_priority_fee_per_gas) {
 				// Zero max_fee_per_gas for validated transactional calls exist in XCM -> EVM
@@ -229,46 +307,93 @@ where
 					weight,
 				})?;
 
-		// Deduct fee from the `source` account. Returns `None` if `total_fee` is Zero.
-		let fee = T::OnChargeTransaction::withdraw_fee(&source, total_fee)
+		// Deduct fee from the `source` account, or from whoever the `OnChargeTransaction`
+		// implementation charges for this destination and input. Returns `None` if `total_fee`
+		// is Zero.
+		let fee = T::OnChargeTransaction::withdraw_fee_for_call(&source, target, &input, total_fee)
 			.map_err(|e| RunnerError { error: e, weight })?;
 
 		// Execute the EVM call.
 		let vicinity = Vicinity {
 			gas_price: base_fee,
 			origin: source,
+			block_randomness: T::BlockRandomness::get(),
 		};
 
+		// Storage growth is only metered when the runtime sets a non-zero ratio.
+		let storage_growth_ratio = T::GasLimitStorageGrowthRatio::get();
+		let storage_meter = (storage_growth_ratio > 0)
+			.then(|| StorageMeter::new(gas_limit.saturating_div(storage_growth_ratio)));
+
 		let metadata = StackSubstateMetadata::new(gas_limit, config);
-		let state = SubstrateStackState::new(&vicinity, metadata, maybe_weight_info);
+		let state = SubstrateStackState::new(&vicinity, metadata, maybe_weight_info, storage_meter);
 		let mut executor = StackExecutor::new_with_precompiles(state, config, precompiles);
 
-		let (reason, retv) = f(&mut executor);
+		let (reason, retv) = f(&mut executor, input);
+
+		environmental::with(&ACCESS_RECORDER, |recorder| {
+			let Some(accessed) = executor.state().metadata().accessed() else {
+				return;
+			};
+			let AccessRecorder {
+				accessed: recorded,
+				created,
+			} = recorder;
+			// EIP-3651: the coinbase is warm from the start of the transaction.
+			let coinbase = config
+				.warm_coinbase_address
+				.then(|| executor.state().block_coinbase());
+			let is_warm = |address: &H160| {
+				*address == source
+					|| Some(*address) == target
+					|| Some(*address) == coinbase
+					|| created.contains(address)
+					|| matches!(
+						precompiles.is_precompile(*address, u64::MAX),
+						IsPrecompileResult::Answer {
+							is_precompile: true,
+							..
+						}
+					)
+			};
+			for address in &accessed.accessed_addresses {
+				if !is_warm(address) {
+					recorded.entry(*address).or_default();
+				}
+			}
+			for (address, index) in &accessed.accessed_storage {
+				recorded.entry(*address).or_default().insert(*index);
+			}
+		});
 
 		// Post execution.
 		let used_gas = executor.used_gas();
-		let effective_gas = match executor.state().weight_info() {
-			Some(weight_info) => U256::from(core::cmp::max(
-				used_gas,
-				weight_info
-					.proof_size_usage
-					.unwrap_or_default()
-					.saturating_mul(T::GasLimitPovSizeRatio::get()),
-			)),
-			_ => used_gas.into(),
+		let pov_gas = match executor.state().weight_info() {
+			Some(weight_info) => weight_info
+				.proof_size_usage
+				.unwrap_or_default()
+				.saturating_mul(T::GasLimitPovSizeRatio::get()),
+			_ => 0,
 		};
+		let storage_gas = match executor.state().storage_meter() {
+			Some(storage_meter) => storage_meter.usage().saturating_mul(storage_growth_ratio),
+			_ => 0,
+		};
+		let effective_gas = U256::from(used_gas.max(pov_gas).max(storage_gas));
 		let actual_fee = effective_gas.saturating_mul(total_fee_per_gas);
 		let actual_base_fee = effective_gas.saturating_mul(base_fee);
 
 		log::debug!(
 			target: "evm",
-			"Execution {:?} [source: {:?}, value: {}, gas_limit: {}, actual_fee: {}, used_gas: {}, effective_gas: {}, base_fee: {}, total_fee_per_gas: {}, is_transactional: {}]",
+			"Execution {:?} [source: {:?}, value: {}, gas_limit: {}, actual_fee: {}, used_gas: {}, pov_gas: {}, storage_gas: {}, effective_gas: {}, base_fee: {}, total_fee_per_gas: {}, is_transactional: {}]",
 			reason,
 			source,
 			value,
 			gas_limit,
 			actual_fee,
 			used_gas,
+			pov_gas,
+			storage_gas,
 			effective_gas,
 			base_fee,
 			total_fee_per_gas,
@@ -399,7 +524,15 @@ where
 		)
 		.validate_in_block_for(&source_account)
 		.and_then(|v| v.with_base_fee())
-		.and_then(|v| v.with_balance_for(&source_account))
+		.and_then(|v| {
+			let fee = v.max_fee()?;
+			if T::OnChargeTransaction::is_fee_sponsored(&source, target, &v.transaction.input, fee)
+			{
+				v.with_sponsored_balance_for(&source_account)
+			} else {
+				v.with_balance_for(&source_account)
+			}
+		})
 		.map_err(|error| RunnerError { error, weight })?;
 		Ok(())
 	}
@@ -440,6 +573,8 @@ where
 		let precompiles = T::PrecompilesValue::get();
 		Self::execute(
 			source,
+			Some(target),
+			input,
 			value,
 			gas_limit,
 			max_fee_per_gas,
@@ -449,7 +584,9 @@ where
 			is_transactional,
 			weight_limit,
 			proof_size_base_cost,
-			|executor| executor.transact_call(source, target, value, input, gas_limit, access_list),
+			|executor, input| {
+				executor.transact_call(source, target, value, input, gas_limit, access_list)
+			},
 		)
 	}
 
@@ -486,8 +623,11 @@ where
 			)?;
 		}
 		let precompiles = T::PrecompilesValue::get();
+		let code_hash = H256::from(sp_io::hashing::keccak_256(&init));
 		Self::execute(
 			source,
+			None,
+			init,
 			value,
 			gas_limit,
 			max_fee_per_gas,
@@ -497,11 +637,17 @@ where
 			is_transactional,
 			weight_limit,
 			proof_size_base_cost,
-			|executor| {
+			|executor, init| {
 				let address = executor.create_address(evm::CreateScheme::Legacy { caller: source });
-				T::OnCreate::on_create(source, address);
-				let (reason, _) =
+				environmental::with(&ACCESS_RECORDER, |recorder| {
+					recorder.created.insert(address)
+				});
+				let (reason, output) =
 					executor.transact_create(source, value, init, gas_limit, access_list);
+				environmental::with(&CREATE_OUTPUT, |recorded| *recorded = output);
+				if reason.is_succeed() {
+					T::OnCreate::on_create(source, address, code_hash);
+				}
 				(reason, address)
 			},
 		)
@@ -544,6 +690,8 @@ where
 		let code_hash = H256::from(sp_io::hashing::keccak_256(&init));
 		Self::execute(
 			source,
+			None,
+			init,
 			value,
 			gas_limit,
 			max_fee_per_gas,
@@ -553,15 +701,21 @@ where
 			is_transactional,
 			weight_limit,
 			proof_size_base_cost,
-			|executor| {
+			|executor, init| {
 				let address = executor.create_address(evm::CreateScheme::Create2 {
 					caller: source,
 					code_hash,
 					salt,
 				});
-				T::OnCreate::on_create(source, address);
-				let (reason, _) =
+				environmental::with(&ACCESS_RECORDER, |recorder| {
+					recorder.created.insert(address)
+				});
+				let (reason, output) =
 					executor.transact_create2(source, value, init, salt, gas_limit, access_list);
+				environmental::with(&CREATE_OUTPUT, |recorded| *recorded = output);
+				if reason.is_succeed() {
+					T::OnCreate::on_create(source, address, code_hash);
+				}
 				(reason, address)
 			},
 		)
@@ -678,6 +832,8 @@ pub struct SubstrateStackState<'vicinity, 'config, T> {
 	original_storage: BTreeMap<(H160, H256), H256>,
 	recorded: Recorded,
 	weight_info: Option<WeightInfo>,
+	storage_meter: Option<StorageMeter>,
+	pending_create: bool,
 	_marker: PhantomData<T>,
 }
 
@@ -687,6 +843,7 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 		vicinity: &'vicinity Vicinity,
 		metadata: StackSubstateMetadata<'config>,
 		weight_info: Option<WeightInfo>,
+		storage_meter: Option<StorageMeter>,
 	) -> Self {
 		Self {
 			vicinity,
@@ -700,6 +857,8 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 			original_storage: BTreeMap::new(),
 			recorded: Default::default(),
 			weight_info,
+			storage_meter,
+			pending_create: false,
 		}
 	}
 
@@ -707,6 +866,10 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 		self.weight_info
 	}
 
+	pub fn storage_meter(&self) -> Option<StorageMeter> {
+		self.storage_meter
+	}
+
 	pub fn recorded(&self) -> &Recorded {
 		&self.recorded
 	}
@@ -754,7 +917,7 @@ where
 	}
 
 	fn block_randomness(&self) -> Option<H256> {
-		None
+		self.vicinity.block_randomness
 	}
 
 	fn block_gas_limit(&self) -> U256 {
@@ -839,6 +1002,12 @@ where
 	}
 
 	fn inc_nonce(&mut self, address: H160) -> Result<(), ExitError> {
+		// The first nonce increase following a CREATE or CREATE2 opcode is the one of the
+		// deploying contract, which is the last chance to reject the deployment.
+		if mem::take(&mut self.pending_create) && !T::CreateFilter::can_create(&address, None) {
+			return Err(ExitError::Other("deployment not allowed".into()));
+		}
+
 		let account_id = T::AddressMapping::into_account_id(address);
 		frame_system::Pallet::<T>::inc_account_nonce(&account_id);
 		Ok(())
@@ -912,7 +1081,23 @@ where
 				.map_err(|_| ExitError::OutOfFund)?,
 			ExistenceRequirement::AllowDeath,
 		)
-		.map_err(|_| ExitError::OutOfFund)
+		.map_err(|_| ExitError::OutOfFund)?;
+
+		if !transfer.value.is_zero() && environmental::with(&TRANSFER_LOGS, |_| ()).is_some() {
+			let mut value = [0u8; 32];
+			transfer.value.to_big_endian(&mut value);
+			self.substate.log(
+				TRANSFER_LOG_ADDRESS,
+				alloc::vec![
+					TRANSFER_LOG_TOPIC,
+					transfer.source.into(),
+					transfer.target.into(),
+				],
+				value.to_vec(),
+			);
+		}
+
+		Ok(())
 	}
 
 	fn reset_balance(&mut self, _address: H160) {
@@ -956,6 +1141,11 @@ where
 			.config()
 			.create_contract_limit
 			.unwrap_or_default() as u64;
+		// A write external operation is the code deposit of a newly created contract.
+		let code_deposit = match op {
+			ExternalOperation::Write(len) => Some(len),
+			_ => None,
+		};
 		let (weight_info, recorded) = self.info_mut();
 
 		if let Some(weight_info) = weight_info {
@@ -1003,15 +1193,30 @@ where
 				}
 			};
 		}
+
+		if let (Some(len), Some(storage_meter)) = (code_deposit, self.storage_meter.as_mut()) {
+			storage_meter
+				.record_code_deposit(len)
+				.map_err(|_| ExitError::OutOfGas)?;
+		}
 		Ok(())
 	}
 
 	fn record_external_dynamic_opcode_cost(
 		&mut self,
 		opcode: Opcode,
-		_gas_cost: GasCost,
+		gas_cost: GasCost,
 		target: evm::gasometer::StorageTarget,
 	) -> Result<(), ExitError> {
+		if let Some(storage_meter) = self.storage_meter.as_mut() {
+			storage_meter
+				.record_dynamic_opcode_cost(opcode, gas_cost, target)
+				.map_err(|_| ExitError::OutOfGas)?;
+		}
+		if matches!(opcode, Opcode::CREATE | Opcode::CREATE2) {
+			self.pending_create = true;
+		}
+
 		// If account code or storage slot is in the overlay it is already accounted for and early exit
 		let accessed_storage: Option<AccessedStorage> = match target {
 			StorageTarget::Address(address) => {
@@ -1137,8 +1342,14 @@ where
 		&mut self,
 		ref_time: Option<u64>,
 		proof_size: Option<u64>,
-		_storage_growth: Option<u64>,
+		storage_growth: Option<u64>,
 	) -> Result<(), ExitError> {
+		if let (Some(amount), Some(storage_meter)) = (storage_growth, self.storage_meter.as_mut()) {
+			storage_meter
+				.record(amount)
+				.map_err(|_| ExitError::OutOfGas)?;
+		}
+
 		let weight_info = if let (Some(weight_info), _) = self.info_mut() {
 			weight_info
 		} else {
@@ -1197,6 +1408,8 @@ mod tests {
 		// Should fail with the appropriate error if there is reentrancy
 		let res = Runner::<Test>::execute(
 			H160::default(),
+			None,
+			Vec::new(),
 			U256::default(),
 			100_000,
 			None,
@@ -1206,9 +1419,11 @@ mod tests {
 			false,
 			None,
 			None,
-			|_| {
+			|_, _| {
 				let res = Runner::<Test>::execute(
 					H160::default(),
+					None,
+					Vec::new(),
 					U256::default(),
 					100_000,
 					None,
@@ -1218,7 +1433,7 @@ mod tests {
 					false,
 					None,
 					None,
-					|_| (ExitReason::Succeed(ExitSucceed::Stopped), ()),
+					|_, _| (ExitReason::Succeed(ExitSucceed::Stopped), ()),
 				);
 				assert_matches!(
 					res,
@@ -1241,6 +1456,8 @@ mod tests {
 		// Should succeed if there is no reentrancy
 		let res = Runner::<Test>::execute(
 			H160::default(),
+			None,
+			Vec::new(),
 			U256::default(),
 			100_000,
 			None,
@@ -1250,7 +1467,7 @@ mod tests {
 			false,
 			None,
 			None,
-			|_| (ExitReason::Succeed(ExitSucceed::Stopped), ()),
+			|_, _| (ExitReason::Succeed(ExitSucceed::Stopped), ()),
 		);
 		assert!(res.is_ok());
 	}
@@ -22,7 +22,7 @@ use crate::mock::*;
 
 use frame_support::{
 	assert_ok,
-	traits::{LockIdentifier, LockableCurrency, WithdrawReasons},
+	traits::{Hooks, LockIdentifier, LockableCurrency, WithdrawReasons},
 };
 use sp_runtime::BuildStorage;
 use std::{collections::BTreeMap, str::FromStr};
@@ -622,6 +622,69 @@ mod proof_size_test {
 			assert_eq!(used_gas.effective, U256::from(actual_proof_size * ratio));
 		});
 	}
+
+	#[test]
+	fn storage_growth_create_accounting_works() {
+		new_test_ext().execute_with(|| {
+			let ratio: u64 = 600;
+			GasLimitStorageGrowthRatio::set(ratio);
+
+			let gas_limit: u64 = 1_000_000;
+			let result = create_proof_size_test_contract(gas_limit, None).expect("create succeeds");
+			assert_eq!(
+				result.exit_reason,
+				crate::ExitReason::Succeed(crate::ExitSucceed::Returned)
+			);
+
+			// The constructor stores a value in a new slot, then the code and its metadata
+			// are deposited.
+			let code_len = AccountCodes::<Test>::get(result.value).len() as u64;
+			let storage_growth =
+				ACCOUNT_STORAGE_PROOF_SIZE + ACCOUNT_CODES_METADATA_PROOF_SIZE + code_len;
+
+			let used_gas = result.used_gas;
+			assert!(used_gas.standard < U256::from(storage_growth * ratio));
+			assert_eq!(used_gas.effective, U256::from(storage_growth * ratio));
+
+			// Overwriting existing slots does not grow the storage.
+			let result = <Test as Config>::Runner::call(
+				H160::default(),
+				result.value,
+				hex::decode("4f3080a9").unwrap(),
+				U256::zero(),
+				gas_limit,
+				Some(FixedGasPrice::min_gas_price().0),
+				None,
+				None,
+				Vec::new(),
+				true, // transactional
+				true, // must be validated
+				None,
+				Some(0),
+				&<Test as Config>::config().clone(),
+			)
+			.expect("call succeeds");
+
+			assert_eq!(result.used_gas.effective, result.used_gas.standard);
+		});
+	}
+
+	#[test]
+	fn storage_growth_oog_works() {
+		new_test_ext().execute_with(|| {
+			// Only leave room for 200 bytes of storage growth, not enough for the code deposit.
+			let gas_limit: u64 = 1_000_000;
+			GasLimitStorageGrowthRatio::set(gas_limit / 200);
+
+			let result = create_proof_size_test_contract(gas_limit, None).expect("create succeeds");
+
+			assert_eq!(
+				result.exit_reason,
+				crate::ExitReason::Error(crate::ExitError::OutOfGas)
+			);
+			assert!(AccountCodes::<Test>::get(result.value).is_empty());
+		});
+	}
 }
 
 type Balances = pallet_balances::Pallet<Test>;
@@ -1260,3 +1323,235 @@ fn metadata_empty_dont_code_gets_cached() {
 		assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
 	});
 }
+
+fn insert_suicided_contract(address: H160, entries: u64) {
+	<Suicided<Test>>::insert(address, ());
+	for i in 0..entries {
+		<AccountStorages<Test>>::insert(address, H256::from_low_u64_be(i), H256::repeat_byte(0x01));
+	}
+	frame_system::Pallet::<Test>::inc_sufficients(&address);
+}
+
+#[test]
+fn on_idle_clears_suicided_storage() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		insert_suicided_contract(H160::repeat_byte(0xaa), 3);
+		insert_suicided_contract(H160::repeat_byte(0xbb), 2);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		EVM::on_idle(1, Weight::MAX);
+
+		for address in [H160::repeat_byte(0xaa), H160::repeat_byte(0xbb)] {
+			assert!(!EVM::is_account_suicided(&address));
+			assert_eq!(EVM::iter_account_storages(&address).count(), 0);
+			assert_eq!(System::sufficients(&address), 0);
+			System::assert_has_event(Event::SuicidedContractCleared { address }.into());
+		}
+		System::assert_has_event(
+			Event::SuicidedStorageRemoved {
+				address: H160::repeat_byte(0xaa),
+				entries: 3,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn on_idle_clears_suicided_storage_within_weight_limit() {
+	let address = H160::repeat_byte(0xaa);
+	let mut ext = new_test_ext();
+	ext.execute_with(|| insert_suicided_contract(address, 5));
+	ext.commit_all().unwrap();
+
+	// Enough proof size to read the contract, remove two entries and clear the contract.
+	let limit = Weight::from_parts(
+		0,
+		36 + 2 * fp_evm::ACCOUNT_STORAGE_PROOF_SIZE + fp_evm::ACCOUNT_BASIC_PROOF_SIZE,
+	);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let consumed = EVM::on_idle(1, limit);
+
+		assert_eq!(
+			consumed,
+			Weight::from_parts(0, 36 + 2 * fp_evm::ACCOUNT_STORAGE_PROOF_SIZE)
+		);
+		assert!(EVM::is_account_suicided(&address));
+		assert_eq!(EVM::iter_account_storages(&address).count(), 3);
+		System::assert_last_event(
+			Event::SuicidedStorageRemoved {
+				address,
+				entries: 2,
+			}
+			.into(),
+		);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		System::set_block_number(2);
+		EVM::on_idle(2, Weight::MAX);
+
+		assert!(!EVM::is_account_suicided(&address));
+		assert_eq!(EVM::iter_account_storages(&address).count(), 0);
+		assert_eq!(System::sufficients(&address), 0);
+		System::assert_last_event(Event::SuicidedContractCleared { address }.into());
+	});
+}
+
+#[test]
+fn on_idle_without_suicided_contracts_is_cheap() {
+	new_test_ext().execute_with(|| {
+		let consumed = EVM::on_idle(1, Weight::MAX);
+		assert_eq!(consumed, Weight::from_parts(0, 36));
+	});
+}
+
+#[test]
+fn ensure_address_mapped_accepts_derived_address() {
+	type Ensure = EnsureAddressMapped<H160, IdentityAddressMapping>;
+	let address = H160::repeat_byte(0xaa);
+
+	assert_eq!(
+		Ensure::ensure_address_origin(&address, RuntimeOrigin::signed(address)).ok(),
+		Some(address)
+	);
+	assert!(Ensure::ensure_address_origin(
+		&address,
+		RuntimeOrigin::signed(H160::repeat_byte(0xbb))
+	)
+	.is_err());
+	assert!(Ensure::ensure_address_origin(&address, RuntimeOrigin::root()).is_err());
+}
+
+#[test]
+fn hashed_account_id_mapping_works() {
+	type Mapping = HashedAddressMapping<sp_runtime::traits::BlakeTwo256>;
+	let account_id = AccountId32::new([0x01; 32]);
+
+	assert_eq!(
+		<Mapping as AccountIdMapping<AccountId32>>::into_address(&account_id),
+		H160::from(hex_literal::hex!(
+			"e35a833d3ddf155c664d4fb6440cc4e84f11a14c"
+		))
+	);
+}
+
+/// Code loading slot 1 of its own storage and the balance of `0x2222…`.
+fn access_recorder_test_code() -> Vec<u8> {
+	let mut code = hex_literal::hex!("6001545073").to_vec();
+	code.extend_from_slice(H160::repeat_byte(0x22).as_bytes());
+	code.extend_from_slice(&hex_literal::hex!("315000"));
+	code
+}
+
+#[test]
+fn access_recorder_records_call_accesses() {
+	new_test_ext().execute_with(|| {
+		let source = H160::repeat_byte(0x01);
+		let contract = H160::repeat_byte(0x11);
+		EVM::create_account(contract, access_recorder_test_code());
+
+		let (info, access_list) = runner::stack::with_access_recorder(|| {
+			<Test as Config>::Runner::call(
+				source,
+				contract,
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false, // non-transactional
+				true,  // must be validated
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+		});
+
+		assert!(info.expect("call runs").exit_reason.is_succeed());
+		// The target is warm, so it is only listed for its slot.
+		assert_eq!(
+			access_list,
+			vec![
+				(contract, vec![H256::from_low_u64_be(1)]),
+				(H160::repeat_byte(0x22), vec![]),
+			]
+		);
+	});
+}
+
+#[test]
+fn access_recorder_treats_created_contract_as_warm() {
+	new_test_ext().execute_with(|| {
+		let source = H160::repeat_byte(0x01);
+		// Same accesses from the init code, which deploys an empty contract.
+		let (info, access_list) = runner::stack::with_access_recorder(|| {
+			<Test as Config>::Runner::create(
+				source,
+				access_recorder_test_code(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false, // non-transactional
+				true,  // must be validated
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+		});
+
+		let info = info.expect("create runs");
+		assert!(info.exit_reason.is_succeed());
+		// Like a call target, the created contract is only listed for its slot.
+		assert_eq!(
+			access_list.into_iter().collect::<BTreeMap<_, _>>(),
+			BTreeMap::from([
+				(info.value, vec![H256::from_low_u64_be(1)]),
+				(H160::repeat_byte(0x22), vec![]),
+			])
+		);
+	});
+}
+
+#[test]
+fn access_recorder_does_not_list_created_contract_without_slots() {
+	new_test_ext().execute_with(|| {
+		let source = H160::repeat_byte(0x01);
+		// Loads the balance of `0x2222…`, then deploys an empty contract.
+		let mut init = vec![0x73];
+		init.extend_from_slice(H160::repeat_byte(0x22).as_bytes());
+		init.extend_from_slice(&hex_literal::hex!("315000"));
+
+		let (info, access_list) = runner::stack::with_access_recorder(|| {
+			<Test as Config>::Runner::create(
+				source,
+				init,
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false, // non-transactional
+				true,  // must be validated
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+		});
+
+		assert!(info.expect("create runs").exit_reason.is_succeed());
+		assert_eq!(access_list, vec![(H160::repeat_byte(0x22), vec![])]);
+	});
+}
@@ -0,0 +1,126 @@
+// This file is part of Frontier.
+
+// Copyright (c) Moonsong Labs.
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+pub fn main(input: TokenStream) -> TokenStream {
+	let DeriveInput {
+		ident,
+		mut generics,
+		data,
+		..
+	} = parse_macro_input!(input as DeriveInput);
+
+	let syn::Data::Enum(syn::DataEnum { variants, .. }) = data else {
+		return quote_spanned! { ident.span() =>
+			compile_error!("CustomError can only be derived for enums");
+		}
+		.into();
+	};
+
+	let mut variants_match = vec![];
+	let mut variants_abi = vec![];
+	let where_clause = generics.make_where_clause();
+
+	for variant in &variants {
+		let variant_ident = &variant.ident;
+		let name = LitStr::new(&variant_ident.to_string(), variant_ident.span());
+		let fields_ty: Vec<_> = variant.fields.iter().map(|f| &f.ty).collect();
+
+		// Bindings used to destructure the variant, and names of the error arguments.
+		// Unnamed fields are left unnamed in the Solidity interface.
+		let (bindings, names): (Vec<_>, Vec<_>) = variant
+			.fields
+			.iter()
+			.enumerate()
+			.map(|(i, f)| match &f.ident {
+				Some(ident) => (ident.clone(), ident.to_string()),
+				None => (quote::format_ident!("field{}", i), String::new()),
+			})
+			.unzip();
+
+		let pattern = match &variant.fields {
+			syn::Fields::Named(_) => quote!(Self::#variant_ident { #(#bindings),* }),
+			syn::Fields::Unnamed(_) => quote!(Self::#variant_ident ( #(#bindings),* )),
+			syn::Fields::Unit => quote!(Self::#variant_ident),
+		};
+
+		variants_match.push(quote!(
+			#pattern => ::precompile_utils::solidity::revert::RevertReason::custom_error(
+				#name,
+				(#(#bindings,)*),
+			),
+		));
+
+		variants_abi.push(quote!(
+			::precompile_utils::solidity::abi::ErrorAbi::new::<(#(#fields_ty,)*)>(
+				#name,
+				&[#(#names),*],
+			)
+		));
+
+		for ty in fields_ty {
+			where_clause
+				.predicates
+				.push(syn::parse_quote!(#ty: ::precompile_utils::solidity::Codec));
+		}
+	}
+
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	quote! {
+		impl #impl_generics ::precompile_utils::solidity::revert::CustomError for #ident #ty_generics
+		#where_clause {
+			fn into_revert_reason(self) -> ::precompile_utils::solidity::revert::RevertReason {
+				match self {
+					#(#variants_match)*
+				}
+			}
+
+			fn abi() -> ::precompile_utils::__alloc::vec::Vec<
+				::precompile_utils::solidity::abi::ErrorAbi
+			> {
+				::precompile_utils::__alloc::vec![#(#variants_abi),*]
+			}
+		}
+
+		impl #impl_generics From<#ident #ty_generics>
+			for ::precompile_utils::solidity::revert::RevertReason
+		#where_clause {
+			fn from(error: #ident #ty_generics) -> Self {
+				::precompile_utils::solidity::revert::CustomError::into_revert_reason(error)
+			}
+		}
+
+		impl #impl_generics From<#ident #ty_generics> for ::precompile_utils::solidity::revert::Revert
+		#where_clause {
+			fn from(error: #ident #ty_generics) -> Self {
+				::precompile_utils::solidity::revert::Revert::new(error.into())
+			}
+		}
+
+		impl #impl_generics From<#ident #ty_generics> for ::fp_evm::PrecompileFailure
+		#where_clause {
+			fn from(error: #ident #ty_generics) -> Self {
+				::precompile_utils::solidity::revert::RevertReason::from(error).into()
+			}
+		}
+	}
+	.into()
+}
@@ -25,6 +25,7 @@ use sp_crypto_hashing::keccak_256;
 use syn::{parse_macro_input, spanned::Spanned, Expr, Ident, ItemType, Lit, LitStr};
 
 mod derive_codec;
+mod derive_custom_error;
 mod precompile;
 mod precompile_name_from_address;
 
@@ -76,3 +77,8 @@ pub fn precompile_name_from_address(attr: TokenStream, input: TokenStream) -> To
 pub fn derive_codec(input: TokenStream) -> TokenStream {
 	derive_codec::main(input)
 }
+
+#[proc_macro_derive(CustomError)]
+pub fn derive_custom_error(input: TokenStream) -> TokenStream {
+	derive_custom_error::main(input)
+}
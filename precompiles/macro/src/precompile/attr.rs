@@ -51,6 +51,7 @@ pub mod keyword {
 	syn::custom_keyword!(precompile_set);
 	syn::custom_keyword!(test_concrete_types);
 	syn::custom_keyword!(pre_check);
+	syn::custom_keyword!(errors);
 }
 
 /// Attributes for methods.
@@ -109,6 +110,7 @@ impl syn::parse::Parse for MethodAttr {
 pub enum ImplAttr {
 	PrecompileSet(Span),
 	TestConcreteTypes(Span, Vec<syn::Type>),
+	Errors(Span, Vec<syn::Type>),
 }
 
 impl syn::parse::Parse for ImplAttr {
@@ -136,6 +138,14 @@ impl syn::parse::Parse for ImplAttr {
 				span,
 				types.into_iter().collect(),
 			))
+		} else if lookahead.peek(keyword::errors) {
+			let span = content.parse::<keyword::errors>()?.span();
+
+			let inner;
+			syn::parenthesized!(inner in content);
+			let types = inner.parse_terminated::<_, syn::Token![,]>(syn::Type::parse)?;
+
+			Ok(ImplAttr::Errors(span, types.into_iter().collect()))
 		} else {
 			Err(lookahead.error())
 		}
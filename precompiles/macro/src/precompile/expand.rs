@@ -75,21 +75,13 @@ impl Precompile {
 
 	/// Expands the parse function for each variants.
 	pub fn expand_variants_parse_fn(&self) -> impl ToTokens {
-		let span = Span::call_site();
-
 		let fn_parse = self
 			.variants_content
 			.keys()
 			.map(Self::variant_ident_to_parse_fn);
 
 		let modifier_check = self.variants_content.values().map(|variant| {
-			let modifier = match variant.modifier {
-				Modifier::NonPayable => "NonPayable",
-				Modifier::Payable => "Payable",
-				Modifier::View => "View",
-			};
-
-			let modifier = syn::Ident::new(modifier, span);
+			let modifier = Self::modifier_ident(&variant.modifier);
 
 			quote!(
 				use ::precompile_utils::solidity::modifier::FunctionModifier;
@@ -187,6 +179,7 @@ impl Precompile {
 
 		let parse_call_data_fn = self.expand_enum_parse_call_data();
 		let execute_fn = self.expand_enum_execute_fn();
+		let abi_fn = self.expand_enum_abi_fn();
 
 		quote!(
 			impl #impl_generics #enum_ident #ty_generics #where_clause {
@@ -230,6 +223,8 @@ impl Precompile {
 						Self::__phantom(_, _) => panic!("__phantom variant should not be used"),
 					}
 				}
+
+				#abi_fn
 			}
 
 			impl #impl_generics From<#enum_ident #ty_generics> for ::precompile_utils::__alloc::vec::Vec<u8>
@@ -312,6 +307,100 @@ impl Precompile {
 		)
 	}
 
+	/// Expand the function describing the Solidity interface of the precompile, built from
+	/// the public signatures, the modifiers and the `Codec` types of each method, and from the
+	/// custom errors listed with `#[precompile::errors(...)]`.
+	fn expand_enum_abi_fn(&self) -> impl ToTokens {
+		use case::CaseExt;
+
+		let name = self.impl_ident.to_string();
+
+		let functions = self.variants_content.values().flat_map(|variant| {
+			let types: Vec<_> = variant.arguments.iter().map(|arg| &arg.ty).collect();
+			let names: Vec<_> = variant
+				.arguments
+				.iter()
+				.map(|arg| arg.ident.to_string().to_camel_lowercase())
+				.collect();
+			let output = Self::evm_result_inner_type(&variant.fn_output);
+			let modifier = Self::modifier_ident(&variant.modifier);
+
+			variant.signatures.iter().map(move |signature| {
+				quote!(
+					FunctionAbi::new::<(#(#types,)*), #output>(
+						#signature,
+						&[#(#names),*],
+						FunctionModifier::#modifier,
+					)
+				)
+			})
+		});
+
+		let errors = if self.errors.is_empty() {
+			quote!(::precompile_utils::__alloc::vec::Vec::new())
+		} else {
+			let errors = &self.errors;
+			quote!({
+				let mut errors = ::precompile_utils::__alloc::vec::Vec::new();
+				#(
+					errors.extend(
+						<#errors as ::precompile_utils::solidity::revert::CustomError>::abi()
+					);
+				)*
+				errors
+			})
+		};
+
+		let fallback = match &self.fallback_to_variant {
+			Some(variant) => {
+				let modifier = Self::modifier_ident(&self.variants_content[variant].modifier);
+				quote!(Some(FunctionModifier::#modifier))
+			}
+			None => quote!(None),
+		};
+
+		quote!(
+			pub fn abi() -> ::precompile_utils::solidity::abi::PrecompileAbi {
+				use ::precompile_utils::solidity::{
+					abi::{FunctionAbi, PrecompileAbi},
+					modifier::FunctionModifier,
+				};
+
+				PrecompileAbi {
+					name: ::precompile_utils::__alloc::string::String::from(#name),
+					functions: ::precompile_utils::__alloc::vec![#(#functions),*],
+					fallback: #fallback,
+					errors: #errors,
+				}
+			}
+		)
+	}
+
+	/// Extract `T` out of `EvmResult<T>`, which defaults to `()`.
+	fn evm_result_inner_type(output: &syn::Type) -> syn::Type {
+		if let syn::Type::Path(type_path) = output {
+			if let Some(segment) = type_path.path.segments.last() {
+				if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+					if let Some(syn::GenericArgument::Type(ty)) = args.args.first() {
+						return ty.clone();
+					}
+				}
+			}
+		}
+
+		syn::parse_quote!(())
+	}
+
+	fn modifier_ident(modifier: &Modifier) -> syn::Ident {
+		let modifier = match modifier {
+			Modifier::NonPayable => "NonPayable",
+			Modifier::Payable => "Payable",
+			Modifier::View => "View",
+		};
+
+		syn::Ident::new(modifier, Span::call_site())
+	}
+
 	/// Expand how a variant can be Solidity encoded.
 	fn expand_variant_encoding(variant: &Variant) -> impl ToTokens {
 		match variant.selectors.first() {
@@ -392,8 +481,9 @@ impl Precompile {
 			let opt_pre_check = self.pre_check.as_ref().map(|ident| {
 				let span = ident.span();
 				quote_spanned!(span=>
-					let _: () = <#impl_type>::#ident(discriminant, handle)
-						.map_err(|err| Some(err))?;
+					if let Err(err) = <#impl_type>::#ident(discriminant, handle) {
+						return Some(Err(err));
+					}
 				)
 			});
 
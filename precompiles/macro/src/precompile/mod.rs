@@ -89,6 +89,9 @@ struct Precompile {
 	/// Ident of a function that performs a check before the call is dispatched to the proper
 	/// function.
 	pre_check: Option<syn::Ident>,
+
+	/// Types implementing `CustomError` whose errors are included in the Solidity interface.
+	errors: Vec<syn::Type>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -113,6 +116,10 @@ struct Variant {
 	/// > a unit test is generated to check it matches.
 	solidity_arguments_type: String,
 
+	/// Public signatures of this function, in the order of the attributes.
+	/// Used to describe the Solidity interface of the precompile.
+	signatures: Vec<String>,
+
 	/// Modifier of the function. They are all exclusive and defaults to
 	/// `NonPayable`.
 	modifier: Modifier,
@@ -39,6 +39,7 @@ impl Precompile {
 			precompile_set_discriminant_type: None,
 			test_concrete_types: None,
 			pre_check: None,
+			errors: vec![],
 		};
 
 		precompile.process_impl_attr(impl_)?;
@@ -62,7 +63,8 @@ impl Precompile {
 	}
 
 	/// Process the attributes used on the `impl` block, which allows to declare
-	/// if it is a PrecompileSet or not, and to provide concrete types for tests if necessary.
+	/// if it is a PrecompileSet or not, to provide concrete types for tests if necessary and
+	/// to list the custom errors of the precompile.
 	fn process_impl_attr(&mut self, impl_: &mut syn::ItemImpl) -> syn::Result<()> {
 		let attrs = attr::take_attributes::<attr::ImplAttr>(&mut impl_.attrs)?;
 
@@ -85,6 +87,9 @@ impl Precompile {
 
 					self.test_concrete_types = Some(types);
 				}
+				attr::ImplAttr::Errors(_, types) => {
+					self.errors.extend(types);
+				}
 			}
 		}
 
@@ -131,6 +136,7 @@ impl Precompile {
 		let mut arguments = vec![];
 		let mut is_fallback = false;
 		let mut selectors = vec![];
+		let mut signatures = vec![];
 		let initial_arguments = if self.tagged_as_precompile_set { 2 } else { 1 };
 
 		// We first look for unique attributes.
@@ -201,6 +207,7 @@ impl Precompile {
 				}
 				attr::MethodAttr::Public(_, signature_lit) => {
 					used = true;
+					signatures.push(signature_lit.value());
 
 					let selector = self.parse_public_attr(
 						signature_lit,
@@ -300,6 +307,7 @@ impl Precompile {
 				Variant {
 					arguments,
 					solidity_arguments_type: solidity_arguments_type.unwrap_or(String::from("()")),
+					signatures,
 					modifier,
 					selectors,
 					fn_output: output_type.as_ref().clone(),
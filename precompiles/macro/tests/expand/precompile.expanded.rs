@@ -282,6 +282,34 @@ where
 			}
 		}
 	}
+	pub fn abi() -> ::precompile_utils::solidity::abi::PrecompileAbi {
+		use precompile_utils::solidity::{
+			abi::{FunctionAbi, PrecompileAbi},
+			modifier::FunctionModifier,
+		};
+		PrecompileAbi {
+			name: ::precompile_utils::__alloc::string::String::from("BatchPrecompile"),
+			functions: <[_]>::into_vec(::alloc::boxed::box_new([
+				FunctionAbi::new::<(BoundedVec<Address, GetArrayLimit>, BoundedVec<U256, GetArrayLimit>, BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>, BoundedVec<u64, GetArrayLimit>), ()>(
+					"batchAll(address[],uint256[],bytes[],uint64[])",
+					&["to", "value", "callData", "gasLimit"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(BoundedVec<Address, GetArrayLimit>, BoundedVec<U256, GetArrayLimit>, BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>, BoundedVec<u64, GetArrayLimit>), ()>(
+					"batchSome(address[],uint256[],bytes[],uint64[])",
+					&["to", "value", "callData", "gasLimit"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(BoundedVec<Address, GetArrayLimit>, BoundedVec<U256, GetArrayLimit>, BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>, BoundedVec<u64, GetArrayLimit>), ()>(
+					"batchSomeUntilFailure(address[],uint256[],bytes[],uint64[])",
+					&["to", "value", "callData", "gasLimit"],
+					FunctionModifier::NonPayable,
+				),
+			])),
+			fallback: Some(FunctionModifier::NonPayable),
+			errors: ::precompile_utils::__alloc::vec::Vec::new(),
+		}
+	}
 }
 impl<Runtime> From<BatchPrecompileCall<Runtime>> for ::precompile_utils::__alloc::vec::Vec<u8>
 where
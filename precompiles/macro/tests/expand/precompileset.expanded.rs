@@ -1011,6 +1011,119 @@ where
 			}
 		}
 	}
+	pub fn abi() -> ::precompile_utils::solidity::abi::PrecompileAbi {
+		use precompile_utils::solidity::{
+			abi::{FunctionAbi, PrecompileAbi},
+			modifier::FunctionModifier,
+		};
+		PrecompileAbi {
+			name: ::precompile_utils::__alloc::string::String::from("PrecompileSet"),
+			functions: <[_]>::into_vec(::alloc::boxed::box_new([
+				FunctionAbi::new::<(Address, Address), U256>(
+					"allowance(address,address)",
+					&["owner", "spender"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address, U256), bool>(
+					"approve(address,uint256)",
+					&["spender", "value"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address,), U256>(
+					"balanceOf(address)",
+					&["who"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address, U256), bool>(
+					"burn(address,uint256)",
+					&["from", "value"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(), bool>("clearMetadata()", &[], FunctionModifier::NonPayable),
+				FunctionAbi::new::<(), bool>("clear_metadata()", &[], FunctionModifier::NonPayable),
+				FunctionAbi::new::<(), u8>("decimals()", &[], FunctionModifier::NonPayable),
+				FunctionAbi::new::<(), H256>("DOMAIN_SEPARATOR()", &[], FunctionModifier::View),
+				FunctionAbi::new::<(Address,), U256>(
+					"nonces(address)",
+					&["owner"],
+					FunctionModifier::View,
+				),
+				FunctionAbi::new::<(Address, Address, U256, U256, u8, H256, H256), ()>(
+					"permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+					&["owner", "spender", "value", "deadline", "v", "r", "s"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address,), bool>(
+					"freeze(address)",
+					&["account"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(), bool>("freezeAsset()", &[], FunctionModifier::NonPayable),
+				FunctionAbi::new::<(), bool>("freeze_asset()", &[], FunctionModifier::NonPayable),
+				FunctionAbi::new::<(Address, U256), bool>(
+					"mint(address,uint256)",
+					&["to", "value"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(), UnboundedBytes>("name()", &[], FunctionModifier::NonPayable),
+				FunctionAbi::new::<(BoundedString<GetAssetsStringLimit<Runtime>>, BoundedString<GetAssetsStringLimit<Runtime>>, u8), bool>(
+					"setMetadata(string,string,uint8)",
+					&["name", "symbol", "decimals"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(BoundedString<GetAssetsStringLimit<Runtime>>, BoundedString<GetAssetsStringLimit<Runtime>>, u8), bool>(
+					"set_metadata(string,string,uint8)",
+					&["name", "symbol", "decimals"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address, Address, Address), bool>(
+					"setTeam(address,address,address)",
+					&["issuer", "admin", "freezer"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address, Address, Address), bool>(
+					"set_team(address,address,address)",
+					&["issuer", "admin", "freezer"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(), UnboundedBytes>(
+					"symbol()",
+					&[],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address,), bool>(
+					"thaw(address)",
+					&["account"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(), bool>("thawAsset()", &[], FunctionModifier::NonPayable),
+				FunctionAbi::new::<(), bool>("thaw_asset()", &[], FunctionModifier::NonPayable),
+				FunctionAbi::new::<(), U256>("totalSupply()", &[], FunctionModifier::NonPayable),
+				FunctionAbi::new::<(Address, U256), bool>(
+					"transfer(address,uint256)",
+					&["to", "value"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address, Address, U256), bool>(
+					"transferFrom(address,address,uint256)",
+					&["from", "to", "value"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address,), bool>(
+					"transferOwnership(address)",
+					&["owner"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Address,), bool>(
+					"transfer_ownership(address)",
+					&["owner"],
+					FunctionModifier::NonPayable,
+				),
+			])),
+			fallback: None,
+			errors: ::precompile_utils::__alloc::vec::Vec::new(),
+		}
+	}
 }
 impl<Runtime> From<PrecompileSetCall<Runtime>> for ::precompile_utils::__alloc::vec::Vec<u8>
 where
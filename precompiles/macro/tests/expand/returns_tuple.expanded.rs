@@ -81,6 +81,24 @@ impl ExamplePrecompileCall {
 			}
 		}
 	}
+	pub fn abi() -> ::precompile_utils::solidity::abi::PrecompileAbi {
+		use precompile_utils::solidity::{
+			abi::{FunctionAbi, PrecompileAbi},
+			modifier::FunctionModifier,
+		};
+		PrecompileAbi {
+			name: ::precompile_utils::__alloc::string::String::from("ExamplePrecompile"),
+			functions: <[_]>::into_vec(::alloc::boxed::box_new([
+				FunctionAbi::new::<(), (Address, U256, UnboundedBytes)>(
+					"example()",
+					&[],
+					FunctionModifier::NonPayable,
+				),
+			])),
+			fallback: None,
+			errors: ::precompile_utils::__alloc::vec::Vec::new(),
+		}
+	}
 }
 impl From<ExamplePrecompileCall> for ::precompile_utils::__alloc::vec::Vec<u8> {
 	fn from(a: ExamplePrecompileCall) -> ::precompile_utils::__alloc::vec::Vec<u8> {
@@ -0,0 +1,64 @@
+// This file is part of Frontier.
+
+// Copyright (c) Moonsong Labs.
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use precompile_utils::solidity::{
+	codec::{Address, Writer},
+	revert::{CustomError, Revert, RevertReason},
+};
+use sp_core::{H160, U256};
+
+#[derive(CustomError)]
+enum Error {
+	InsufficientAllowance { have: U256, want: U256 },
+	Unauthorized(Address),
+	Paused,
+}
+
+fn main() {
+	let abi = Error::abi();
+	assert_eq!(abi.len(), 3);
+	assert_eq!(abi[0].signature(), "InsufficientAllowance(uint256,uint256)");
+	assert_eq!(abi[1].signature(), "Unauthorized(address)");
+	assert_eq!(abi[2].signature(), "Paused()");
+	assert_eq!(abi[0].inputs[1].name, "want");
+
+	let error = Error::InsufficientAllowance {
+		have: 1.into(),
+		want: 2.into(),
+	};
+	assert_eq!(
+		Revert::new(RevertReason::from(error)).to_encoded_bytes(),
+		Writer::new_with_selector(abi[0].selector())
+			.write(U256::from(1))
+			.write(U256::from(2))
+			.build()
+	);
+
+	let error = Error::Unauthorized(H160::repeat_byte(0x42).into());
+	assert_eq!(
+		Revert::new(error.into_revert_reason()).to_encoded_bytes(),
+		Writer::new_with_selector(abi[1].selector())
+			.write(Address::from(H160::repeat_byte(0x42)))
+			.build()
+	);
+
+	assert_eq!(
+		Revert::new(Error::Paused.into()).to_encoded_bytes(),
+		Writer::new_with_selector(0x9e87fac8u32).build()
+	);
+}
@@ -65,12 +65,14 @@ pub mod prelude {
 					// Allow usage of Codec methods while not exporting the name directly.
 					Codec as _,
 					Convert,
+					FixedBytes,
+					I256,
 					UnboundedBytes,
 					UnboundedString,
 				},
 				revert::{
-					revert, BacktraceExt, InjectBacktrace, MayRevert, Revert, RevertExt,
-					RevertReason,
+					revert, BacktraceExt, CustomError, InjectBacktrace, MayRevert, Revert,
+					RevertExt, RevertReason,
 				},
 			},
 			substrate::{RuntimeHelper, TryDispatchError},
@@ -0,0 +1,521 @@
+// This file is part of Frontier.
+
+// Copyright (c) Moonsong Labs.
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Describes the Solidity interface of a precompile, as generated by the `#[precompile]` macro,
+//! and renders it as a Solidity interface or as an ABI JSON.
+
+use crate::solidity::{codec::Codec, modifier::FunctionModifier};
+use alloc::{
+	boxed::Box,
+	format,
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
+
+/// Solidity interface of a precompile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrecompileAbi {
+	/// Name of the interface.
+	pub name: String,
+	/// Functions exposed by the precompile, one per public signature.
+	pub functions: Vec<FunctionAbi>,
+	/// Modifier of the fallback function, if any.
+	pub fallback: Option<FunctionModifier>,
+	/// Custom errors the precompile can revert with.
+	pub errors: Vec<ErrorAbi>,
+}
+
+/// Solidity function exposed by a precompile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionAbi {
+	/// Name of the function.
+	pub name: String,
+	/// Named parameters of the function.
+	pub inputs: Vec<Param>,
+	/// Unnamed return values of the function.
+	pub outputs: Vec<SolidityType>,
+	/// Modifier of the function.
+	pub modifier: FunctionModifier,
+}
+
+/// Solidity custom error a precompile can revert with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorAbi {
+	/// Name of the error.
+	pub name: String,
+	/// Named parameters of the error.
+	pub inputs: Vec<Param>,
+}
+
+/// Named parameter of a function or an error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Param {
+	/// Name of the parameter.
+	pub name: String,
+	/// Type of the parameter.
+	pub ty: SolidityType,
+}
+
+/// Solidity type, parsed from a canonical signature such as `(address,uint256[])[2]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolidityType {
+	/// Elementary type, such as `uint256` or `bytes`.
+	Elementary(String),
+	/// Tuple, which is represented as a struct in Solidity.
+	Tuple(Vec<SolidityType>),
+	/// Dynamic (`None`) or fixed size array.
+	Array(Box<SolidityType>, Option<usize>),
+}
+
+impl FunctionAbi {
+	/// Describe a function from its public signature, the Rust types of its arguments and
+	/// output and the names of its arguments.
+	pub fn new<Args: Codec, Output: Codec>(
+		signature: &str,
+		names: &[&str],
+		modifier: FunctionModifier,
+	) -> Self {
+		let name = signature
+			.split_once('(')
+			.map(|(name, _)| name)
+			.unwrap_or(signature)
+			.to_string();
+
+		let inputs = Param::list::<Args>(names);
+
+		// Explicit tuples are returned as multiple values, while other types (including
+		// structs) are returned as a single value.
+		let output = Output::signature();
+		let outputs = if Output::is_explicit_tuple() || output == "()" {
+			SolidityType::parse_list(&output)
+		} else {
+			vec![SolidityType::parse(&output)]
+		};
+
+		Self {
+			name,
+			inputs,
+			outputs,
+			modifier,
+		}
+	}
+
+	/// Canonical signature of the function.
+	pub fn signature(&self) -> String {
+		Param::signature(&self.name, &self.inputs)
+	}
+
+	/// 4-bytes selector of the function.
+	pub fn selector(&self) -> u32 {
+		selector(&self.signature())
+	}
+}
+
+impl ErrorAbi {
+	/// Describe an error from its name and the Rust types and names of its arguments.
+	pub fn new<Args: Codec>(name: &str, names: &[&str]) -> Self {
+		Self {
+			name: name.to_string(),
+			inputs: Param::list::<Args>(names),
+		}
+	}
+
+	/// Canonical signature of the error.
+	pub fn signature(&self) -> String {
+		Param::signature(&self.name, &self.inputs)
+	}
+
+	/// 4-bytes selector of the error.
+	pub fn selector(&self) -> u32 {
+		selector(&self.signature())
+	}
+}
+
+impl Param {
+	/// Name the members of the tuple `Args`.
+	/// Missing names are left empty.
+	fn list<Args: Codec>(names: &[&str]) -> Vec<Self> {
+		SolidityType::parse_list(&Args::signature())
+			.into_iter()
+			.enumerate()
+			.map(|(i, ty)| Param {
+				name: names.get(i).unwrap_or(&"").to_string(),
+				ty,
+			})
+			.collect()
+	}
+
+	fn signature(name: &str, params: &[Self]) -> String {
+		let types: Vec<_> = params.iter().map(|p| p.ty.signature()).collect();
+		format!("{}({})", name, types.join(","))
+	}
+
+	/// Append the ABI JSON representation of a list of parameters.
+	fn write_json_list(params: &[Self], out: &mut String) {
+		out.push('[');
+		for (i, param) in params.iter().enumerate() {
+			if i > 0 {
+				out.push(',');
+			}
+			out.push_str(&format!("{{\"name\":\"{}\",", param.name));
+			param.ty.write_json_type(out);
+			out.push('}');
+		}
+		out.push(']');
+	}
+}
+
+fn selector(signature: &str) -> u32 {
+	let digest = sp_io::hashing::keccak_256(signature.as_bytes());
+	u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+impl SolidityType {
+	/// Parse a single canonical type.
+	/// Panics if the signature is malformed, which would be a bug in a `Codec` implementation.
+	pub fn parse(signature: &str) -> Self {
+		let (ty, rest) = Self::parse_prefix(signature);
+		assert!(rest.is_empty(), "unexpected '{rest}' in type '{signature}'");
+		ty
+	}
+
+	/// Parse a canonical tuple signature such as `(uint8,bytes)` as a list of types.
+	pub fn parse_list(signature: &str) -> Vec<Self> {
+		match Self::parse(signature) {
+			Self::Tuple(types) => types,
+			ty => vec![ty],
+		}
+	}
+
+	fn parse_prefix(input: &str) -> (Self, &str) {
+		let (mut ty, mut rest) = if let Some(mut rest) = input.strip_prefix('(') {
+			let mut members = Vec::new();
+			if let Some(r) = rest.strip_prefix(')') {
+				rest = r;
+			} else {
+				loop {
+					let (member, r) = Self::parse_prefix(rest);
+					members.push(member);
+					match r.as_bytes().first() {
+						Some(b',') => rest = &r[1..],
+						Some(b')') => {
+							rest = &r[1..];
+							break;
+						}
+						_ => panic!("unterminated tuple in type '{input}'"),
+					}
+				}
+			}
+			(Self::Tuple(members), rest)
+		} else {
+			let end = input.find([',', ')', '[']).unwrap_or(input.len());
+			(Self::Elementary(input[..end].to_string()), &input[end..])
+		};
+
+		while let Some(r) = rest.strip_prefix('[') {
+			let end = r
+				.find(']')
+				.unwrap_or_else(|| panic!("unterminated array in type '{input}'"));
+			let size = match &r[..end] {
+				"" => None,
+				size => Some(
+					size.parse()
+						.unwrap_or_else(|_| panic!("invalid array size in type '{input}'")),
+				),
+			};
+			ty = Self::Array(Box::new(ty), size);
+			rest = &r[end + 1..];
+		}
+
+		(ty, rest)
+	}
+
+	/// Canonical signature of the type.
+	pub fn signature(&self) -> String {
+		match self {
+			Self::Elementary(name) => name.clone(),
+			Self::Tuple(members) => {
+				let members: Vec<_> = members.iter().map(|m| m.signature()).collect();
+				format!("({})", members.join(","))
+			}
+			Self::Array(inner, size) => format!("{}{}", inner.signature(), array_suffix(*size)),
+		}
+	}
+
+	/// Whether the type must be annotated with a data location in Solidity.
+	fn is_reference(&self) -> bool {
+		match self {
+			Self::Elementary(name) => name == "bytes" || name == "string",
+			Self::Tuple(_) | Self::Array(_, _) => true,
+		}
+	}
+
+	/// Declare the structs used by this type, inner ones first.
+	fn declare_structs(&self, structs: &mut Vec<String>) {
+		match self {
+			Self::Elementary(_) => (),
+			Self::Array(inner, _) => inner.declare_structs(structs),
+			Self::Tuple(members) => {
+				for member in members {
+					member.declare_structs(structs);
+				}
+				let signature = self.signature();
+				if !structs.contains(&signature) {
+					structs.push(signature);
+				}
+			}
+		}
+	}
+
+	/// Name of the type in a Solidity interface, using the struct names declared in `structs`.
+	fn solidity_name(&self, structs: &[String]) -> String {
+		match self {
+			Self::Elementary(name) => name.clone(),
+			Self::Array(inner, size) => {
+				format!("{}{}", inner.solidity_name(structs), array_suffix(*size))
+			}
+			Self::Tuple(_) => {
+				let signature = self.signature();
+				let index = structs
+					.iter()
+					.position(|s| s == &signature)
+					.expect("structs are declared before being used");
+				format!("Tuple{index}")
+			}
+		}
+	}
+
+	/// Append the ABI JSON fields `"type"` and `"components"` of this type.
+	fn write_json_type(&self, out: &mut String) {
+		let (base, suffix) = self.split_array_suffix();
+		match base {
+			Self::Tuple(members) => {
+				out.push_str(&format!("\"type\":\"tuple{suffix}\",\"components\":["));
+				for (i, member) in members.iter().enumerate() {
+					if i > 0 {
+						out.push(',');
+					}
+					out.push_str("{\"name\":\"\",");
+					member.write_json_type(out);
+					out.push('}');
+				}
+				out.push(']');
+			}
+			_ => out.push_str(&format!("\"type\":\"{}\"", self.signature())),
+		}
+	}
+
+	/// Split nested arrays into their innermost type and their combined suffix.
+	fn split_array_suffix(&self) -> (&Self, String) {
+		match self {
+			Self::Array(inner, size) => {
+				let (base, suffix) = inner.split_array_suffix();
+				(base, format!("{suffix}{}", array_suffix(*size)))
+			}
+			ty => (ty, String::new()),
+		}
+	}
+}
+
+fn array_suffix(size: Option<usize>) -> String {
+	match size {
+		Some(size) => format!("[{size}]"),
+		None => String::from("[]"),
+	}
+}
+
+impl PrecompileAbi {
+	/// Render the precompile as a Solidity interface.
+	/// Tuples are declared as structs named `Tuple<N>` and each function is documented with
+	/// its selector, such that the output can be checked with
+	/// `testing::check_precompile_implements_solidity_interfaces`.
+	pub fn to_solidity(&self) -> String {
+		let mut structs = Vec::new();
+		for error in &self.errors {
+			for param in &error.inputs {
+				param.ty.declare_structs(&mut structs);
+			}
+		}
+		for function in &self.functions {
+			for ty in function
+				.inputs
+				.iter()
+				.map(|p| &p.ty)
+				.chain(function.outputs.iter())
+			{
+				ty.declare_structs(&mut structs);
+			}
+		}
+
+		let mut out = String::from("// SPDX-License-Identifier: GPL-3.0-only\n");
+		out.push_str("pragma solidity >=0.8.3;\n\n");
+		out.push_str(&format!("interface {} {{\n", self.name));
+
+		for (index, signature) in structs.iter().enumerate() {
+			out.push_str(&format!("\tstruct Tuple{index} {{\n"));
+			for (i, member) in SolidityType::parse_list(signature).iter().enumerate() {
+				out.push_str(&format!(
+					"\t\t{} field{i};\n",
+					member.solidity_name(&structs)
+				));
+			}
+			out.push_str("\t}\n\n");
+		}
+
+		for error in &self.errors {
+			let inputs: Vec<_> = error
+				.inputs
+				.iter()
+				.map(|p| {
+					format!("{} {}", p.ty.solidity_name(&structs), p.name)
+						.trim_end()
+						.to_string()
+				})
+				.collect();
+
+			out.push_str(&format!(
+				"\t/// @custom:selector {:08x}\n",
+				error.selector()
+			));
+			out.push_str(&format!(
+				"\terror {}({});\n\n",
+				error.name,
+				inputs.join(", ")
+			));
+		}
+
+		for function in &self.functions {
+			let location = |ty: &SolidityType| if ty.is_reference() { " memory" } else { "" };
+
+			let inputs: Vec<_> = function
+				.inputs
+				.iter()
+				.map(|p| {
+					format!(
+						"{}{} {}",
+						p.ty.solidity_name(&structs),
+						location(&p.ty),
+						p.name
+					)
+					.trim_end()
+					.to_string()
+				})
+				.collect();
+			let outputs: Vec<_> = function
+				.outputs
+				.iter()
+				.map(|ty| format!("{}{}", ty.solidity_name(&structs), location(ty)))
+				.collect();
+
+			out.push_str(&format!(
+				"\t/// @custom:selector {:08x}\n",
+				function.selector()
+			));
+			out.push_str(&format!(
+				"\tfunction {}({}) external{}",
+				function.name,
+				inputs.join(", "),
+				match function.modifier {
+					FunctionModifier::View => " view",
+					FunctionModifier::Payable => " payable",
+					FunctionModifier::NonPayable => "",
+				}
+			));
+			if !outputs.is_empty() {
+				out.push_str(&format!(" returns ({})", outputs.join(", ")));
+			}
+			out.push_str(";\n\n");
+		}
+
+		if let Some(modifier) = self.fallback {
+			let payable = if modifier == FunctionModifier::Payable {
+				" payable"
+			} else {
+				""
+			};
+			out.push_str(&format!("\tfallback() external{payable};\n"));
+		} else if out.ends_with("\n\n") {
+			out.pop();
+		}
+
+		out.push_str("}\n");
+		out
+	}
+
+	/// Render the precompile as an ABI JSON.
+	pub fn to_abi_json(&self) -> String {
+		let mut entries = Vec::new();
+
+		for error in &self.errors {
+			let mut out = format!(
+				"{{\"type\":\"error\",\"name\":\"{}\",\"inputs\":",
+				error.name
+			);
+			Param::write_json_list(&error.inputs, &mut out);
+			out.push('}');
+			entries.push(out);
+		}
+
+		for function in &self.functions {
+			let mut out = format!("{{\"type\":\"function\",\"name\":\"{}\",", function.name);
+
+			out.push_str("\"inputs\":");
+			Param::write_json_list(&function.inputs, &mut out);
+
+			out.push_str(",\"outputs\":[");
+			for (i, ty) in function.outputs.iter().enumerate() {
+				if i > 0 {
+					out.push(',');
+				}
+				out.push_str("{\"name\":\"\",");
+				ty.write_json_type(&mut out);
+				out.push('}');
+			}
+
+			out.push_str(&format!(
+				"],\"stateMutability\":\"{}\"}}",
+				state_mutability(function.modifier)
+			));
+			entries.push(out);
+		}
+
+		if let Some(modifier) = self.fallback {
+			// A fallback function cannot be `view`.
+			let modifier = if modifier == FunctionModifier::Payable {
+				FunctionModifier::Payable
+			} else {
+				FunctionModifier::NonPayable
+			};
+			entries.push(format!(
+				"{{\"type\":\"fallback\",\"stateMutability\":\"{}\"}}",
+				state_mutability(modifier)
+			));
+		}
+
+		format!("[{}]", entries.join(","))
+	}
+}
+
+fn state_mutability(modifier: FunctionModifier) -> &'static str {
+	match modifier {
+		FunctionModifier::View => "view",
+		FunctionModifier::NonPayable => "nonpayable",
+		FunctionModifier::Payable => "payable",
+	}
+}
@@ -224,6 +224,193 @@ macro_rules! impl_evmdata_for_uints {
 
 impl_evmdata_for_uints!(u8, u16, u32, u64, u128,);
 
+/// Read a 32 bytes word, checking that the padding added on the left of a value of `size` bytes
+/// is the sign extension of this value.
+fn read_sign_extended(reader: &mut Reader, size: usize, what: String) -> MayRevert<[u8; 32]> {
+	let range = reader.move_cursor(32)?;
+
+	let data = reader
+		.input
+		.get(range)
+		.ok_or_else(|| RevertReason::read_out_of_bounds(what.clone()))?;
+
+	let padding = if data[32 - size] & 0x80 != 0 {
+		0xff
+	} else {
+		0x00
+	};
+	if data[..32 - size].iter().any(|byte| *byte != padding) {
+		return Err(RevertReason::value_is_too_large(what).into());
+	}
+
+	let mut buffer = [0u8; 32];
+	buffer.copy_from_slice(data);
+	Ok(buffer)
+}
+
+macro_rules! impl_evmdata_for_ints {
+	($($int:ty, )*) => {
+		$(
+			impl Codec for $int {
+				fn read(reader: &mut Reader) -> MayRevert<Self> {
+					const SIZE: usize = core::mem::size_of::<$int>();
+
+					let data = read_sign_extended(reader, SIZE, Self::signature())?;
+					let mut buffer = [0u8; SIZE];
+					buffer.copy_from_slice(&data[32 - SIZE..]);
+
+					Ok(Self::from_be_bytes(buffer))
+				}
+
+				fn write(writer: &mut Writer, value: Self) {
+					const SIZE: usize = core::mem::size_of::<$int>();
+
+					let mut buffer = if value < 0 { [0xffu8; 32] } else { [0u8; 32] };
+					buffer[32 - SIZE..].copy_from_slice(&value.to_be_bytes());
+					writer.data.extend_from_slice(&buffer);
+				}
+
+				fn has_static_size() -> bool {
+					true
+				}
+
+				fn signature() -> String {
+					alloc::format!("int{}", core::mem::size_of::<Self>() * 8)
+				}
+			}
+		)*
+	};
+}
+
+impl_evmdata_for_ints!(i8, i16, i32, i64, i128,);
+
+/// The `int256` type of Solidity, represented in two's complement.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct I256(pub U256);
+
+impl I256 {
+	/// Check if the value is negative.
+	pub fn is_negative(&self) -> bool {
+		self.0.bit(255)
+	}
+}
+
+impl From<i128> for I256 {
+	fn from(value: i128) -> I256 {
+		let mut buffer = if value < 0 { [0xffu8; 32] } else { [0u8; 32] };
+		buffer[16..].copy_from_slice(&value.to_be_bytes());
+		I256(U256::from_big_endian(&buffer))
+	}
+}
+
+impl TryFrom<I256> for i128 {
+	type Error = ();
+
+	fn try_from(value: I256) -> Result<i128, ()> {
+		let mut buffer = [0u8; 32];
+		value.0.to_big_endian(&mut buffer);
+
+		let padding = if value.is_negative() { 0xff } else { 0x00 };
+		if buffer[..16].iter().any(|byte| *byte != padding) || (buffer[16] & 0x80 != padding & 0x80)
+		{
+			return Err(());
+		}
+
+		let mut bytes = [0u8; 16];
+		bytes.copy_from_slice(&buffer[16..]);
+		Ok(i128::from_be_bytes(bytes))
+	}
+}
+
+impl Codec for I256 {
+	fn read(reader: &mut Reader) -> MayRevert<Self> {
+		let value: U256 = reader
+			.read()
+			.map_err(|_| RevertReason::read_out_of_bounds("int256"))?;
+
+		Ok(I256(value))
+	}
+
+	fn write(writer: &mut Writer, value: Self) {
+		U256::write(writer, value.0);
+	}
+
+	fn has_static_size() -> bool {
+		true
+	}
+
+	fn signature() -> String {
+		String::from("int256")
+	}
+}
+
+/// The `bytes1` to `bytes32` types of Solidity, which are left-aligned.
+/// A `[u8; N]` represents instead the `uint8[N]` type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedBytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Default for FixedBytes<N> {
+	fn default() -> Self {
+		FixedBytes([0u8; N])
+	}
+}
+
+impl<const N: usize> From<[u8; N]> for FixedBytes<N> {
+	fn from(value: [u8; N]) -> Self {
+		FixedBytes(value)
+	}
+}
+
+impl<const N: usize> From<FixedBytes<N>> for [u8; N] {
+	fn from(value: FixedBytes<N>) -> Self {
+		value.0
+	}
+}
+
+macro_rules! impl_evmdata_for_fixed_bytes {
+	($($size:literal, )*) => {
+		$(
+			impl Codec for FixedBytes<$size> {
+				fn read(reader: &mut Reader) -> MayRevert<Self> {
+					let range = reader.move_cursor(32)?;
+
+					let data = reader
+						.input
+						.get(range)
+						.ok_or_else(|| RevertReason::read_out_of_bounds(Self::signature()))?;
+
+					if data[$size..].iter().any(|byte| *byte != 0) {
+						return Err(RevertReason::value_is_too_large(Self::signature()).into());
+					}
+
+					let mut value = [0u8; $size];
+					value.copy_from_slice(&data[..$size]);
+					Ok(FixedBytes(value))
+				}
+
+				fn write(writer: &mut Writer, value: Self) {
+					let mut buffer = [0u8; 32];
+					buffer[..$size].copy_from_slice(&value.0);
+					writer.data.extend_from_slice(&buffer);
+				}
+
+				fn has_static_size() -> bool {
+					true
+				}
+
+				fn signature() -> String {
+					alloc::format!("bytes{}", $size)
+				}
+			}
+		)*
+	};
+}
+
+impl_evmdata_for_fixed_bytes!(
+	1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+	27, 28, 29, 30, 31, 32,
+);
+
 impl Codec for bool {
 	fn read(reader: &mut Reader) -> MayRevert<Self> {
 		let h256 = H256::read(reader).map_err(|_| RevertReason::read_out_of_bounds("bool"))?;
@@ -249,6 +436,51 @@ impl Codec for bool {
 	}
 }
 
+/// Static array `T[N]`, encoded like a tuple of `N` elements of type `T`: elements are inlined
+/// if `T` is static, otherwise the array is dynamic and contains the offsets of its elements.
+impl<T: Codec, const N: usize> Codec for [T; N] {
+	fn read(reader: &mut Reader) -> MayRevert<Self> {
+		if Self::has_static_size() {
+			read_array_items(reader)
+		} else {
+			read_array_items(&mut reader.read_pointer()?)
+		}
+	}
+
+	fn write(writer: &mut Writer, value: Self) {
+		if Self::has_static_size() {
+			for item in value {
+				T::write(writer, item);
+			}
+		} else {
+			let mut inner_writer = Writer::new();
+			for item in value {
+				T::write(&mut inner_writer, item);
+			}
+			writer.write_pointer(inner_writer.build());
+		}
+	}
+
+	fn has_static_size() -> bool {
+		N == 0 || T::has_static_size()
+	}
+
+	fn signature() -> String {
+		alloc::format!("{}[{}]", T::signature(), N)
+	}
+}
+
+fn read_array_items<T: Codec, const N: usize>(reader: &mut Reader) -> MayRevert<[T; N]> {
+	let mut array = Vec::with_capacity(N);
+	for i in 0..N {
+		array.push(reader.read().in_array(i)?);
+	}
+
+	array
+		.try_into()
+		.map_err(|_| RevertReason::read_out_of_bounds("array content").into())
+}
+
 type ConstU32Max = ConstU32<{ u32::MAX }>;
 
 impl<T: Codec> Codec for Vec<T> {
@@ -380,3 +612,208 @@ impl<T, S> From<BoundedVec<T, S>> for Vec<T> {
 		value.inner
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::solidity::codec::{UnboundedBytes, UnboundedString};
+	use hex_literal::hex;
+
+	// Test vectors from the Solidity ABI specification:
+	// https://docs.soliditylang.org/en/v0.8.19/abi-spec.html#examples
+
+	#[test]
+	fn abi_spec_static_array_of_fixed_bytes() {
+		let value = [FixedBytes(*b"abc"), FixedBytes(*b"def")];
+		let expected = hex!(
+			"fce353f6"
+			"6162630000000000000000000000000000000000000000000000000000000000"
+			"6465660000000000000000000000000000000000000000000000000000000000"
+		);
+		assert_eq!(<[FixedBytes<3>; 2]>::signature(), "bytes3[2]");
+		assert_eq!(
+			Writer::new_with_selector(0xfce353f6u32)
+				.write(value)
+				.build(),
+			expected
+		);
+
+		let mut reader = Reader::new_skip_selector(&expected).expect("selector");
+		assert_eq!(
+			reader.read::<[FixedBytes<3>; 2]>().expect("to decode"),
+			value
+		);
+	}
+
+	#[test]
+	fn abi_spec_dynamic_arguments() {
+		let expected = hex!(
+			"a5643bf2"
+			"0000000000000000000000000000000000000000000000000000000000000060" // offset of "dave"
+			"0000000000000000000000000000000000000000000000000000000000000001" // true
+			"00000000000000000000000000000000000000000000000000000000000000a0" // offset of [1, 2, 3]
+			"0000000000000000000000000000000000000000000000000000000000000004" // length of "dave"
+			"6461766500000000000000000000000000000000000000000000000000000000" // "dave"
+			"0000000000000000000000000000000000000000000000000000000000000003" // length of [1, 2, 3]
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000002"
+			"0000000000000000000000000000000000000000000000000000000000000003"
+		);
+		assert_eq!(
+			Writer::new_with_selector(0xa5643bf2u32)
+				.write(UnboundedBytes::from("dave"))
+				.write(true)
+				.write(vec![U256::from(1), U256::from(2), U256::from(3)])
+				.build(),
+			expected
+		);
+	}
+
+	#[test]
+	fn abi_spec_fixed_bytes_between_dynamic_arguments() {
+		let expected = hex!(
+			"8be65246"
+			"0000000000000000000000000000000000000000000000000000000000000123"
+			"0000000000000000000000000000000000000000000000000000000000000080" // offset of [0x456, 0x789]
+			"3132333435363738393000000000000000000000000000000000000000000000" // bytes10 "1234567890"
+			"00000000000000000000000000000000000000000000000000000000000000e0" // offset of "Hello, world!"
+			"0000000000000000000000000000000000000000000000000000000000000002" // length of [0x456, 0x789]
+			"0000000000000000000000000000000000000000000000000000000000000456"
+			"0000000000000000000000000000000000000000000000000000000000000789"
+			"000000000000000000000000000000000000000000000000000000000000000d" // length of "Hello, world!"
+			"48656c6c6f2c20776f726c642100000000000000000000000000000000000000"
+		);
+		assert_eq!(
+			Writer::new_with_selector(0x8be65246u32)
+				.write(U256::from(0x123))
+				.write(vec![0x456u32, 0x789u32])
+				.write(FixedBytes(*b"1234567890"))
+				.write(UnboundedBytes::from("Hello, world!"))
+				.build(),
+			expected
+		);
+
+		let mut reader = Reader::new_skip_selector(&expected).expect("selector");
+		assert_eq!(reader.read::<U256>().expect("to decode"), U256::from(0x123));
+		assert_eq!(
+			reader.read::<Vec<u32>>().expect("to decode"),
+			vec![0x456u32, 0x789u32]
+		);
+		assert_eq!(
+			reader.read::<FixedBytes<10>>().expect("to decode"),
+			FixedBytes(*b"1234567890")
+		);
+	}
+
+	#[test]
+	fn abi_spec_nested_dynamic_arrays() {
+		let expected = hex!(
+			"2289b18c"
+			"0000000000000000000000000000000000000000000000000000000000000040" // offset of [[1, 2], [3]]
+			"0000000000000000000000000000000000000000000000000000000000000140" // offset of ["one", "two", "three"]
+			"0000000000000000000000000000000000000000000000000000000000000002" // length of [[1, 2], [3]]
+			"0000000000000000000000000000000000000000000000000000000000000040" // offset of [1, 2]
+			"00000000000000000000000000000000000000000000000000000000000000a0" // offset of [3]
+			"0000000000000000000000000000000000000000000000000000000000000002" // length of [1, 2]
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000002"
+			"0000000000000000000000000000000000000000000000000000000000000001" // length of [3]
+			"0000000000000000000000000000000000000000000000000000000000000003"
+			"0000000000000000000000000000000000000000000000000000000000000003" // length of ["one", "two", "three"]
+			"0000000000000000000000000000000000000000000000000000000000000060" // offset of "one"
+			"00000000000000000000000000000000000000000000000000000000000000a0" // offset of "two"
+			"00000000000000000000000000000000000000000000000000000000000000e0" // offset of "three"
+			"0000000000000000000000000000000000000000000000000000000000000003" // length of "one"
+			"6f6e650000000000000000000000000000000000000000000000000000000000"
+			"0000000000000000000000000000000000000000000000000000000000000003" // length of "two"
+			"74776f0000000000000000000000000000000000000000000000000000000000"
+			"0000000000000000000000000000000000000000000000000000000000000005" // length of "three"
+			"7468726565000000000000000000000000000000000000000000000000000000"
+		);
+		assert_eq!(
+			Writer::new_with_selector(0x2289b18cu32)
+				.write(vec![
+					vec![U256::from(1), U256::from(2)],
+					vec![U256::from(3)]
+				])
+				.write(vec![
+					UnboundedString::from("one"),
+					UnboundedString::from("two"),
+					UnboundedString::from("three"),
+				])
+				.build(),
+			expected
+		);
+	}
+
+	#[test]
+	fn static_array_of_dynamic_type_uses_head_and_tail() {
+		let value = [UnboundedString::from("a"), UnboundedString::from("b")];
+		let expected = hex!(
+			"0000000000000000000000000000000000000000000000000000000000000020" // offset of ["a", "b"]
+			"0000000000000000000000000000000000000000000000000000000000000040" // offset of "a"
+			"0000000000000000000000000000000000000000000000000000000000000080" // offset of "b"
+			"0000000000000000000000000000000000000000000000000000000000000001" // length of "a"
+			"6100000000000000000000000000000000000000000000000000000000000000"
+			"0000000000000000000000000000000000000000000000000000000000000001" // length of "b"
+			"6200000000000000000000000000000000000000000000000000000000000000"
+		);
+		assert!(!<[UnboundedString; 2]>::has_static_size());
+		assert_eq!(<[UnboundedString; 2]>::signature(), "string[2]");
+		assert_eq!(Writer::new().write(value.clone()).build(), expected);
+
+		let mut reader = Reader::new(&expected);
+		let [a, b] = reader.read::<[UnboundedString; 2]>().expect("to decode");
+		assert_eq!((a.as_bytes(), b.as_bytes()), (&b"a"[..], &b"b"[..]));
+	}
+
+	#[test]
+	fn signed_integers_are_sign_extended() {
+		assert_eq!(i8::signature(), "int8");
+		assert_eq!(I256::signature(), "int256");
+
+		let expected = hex!(
+			"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff" // int256 -1
+			"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80" // int8 -128
+			"000000000000000000000000000000000000000000000000000000000000007f" // int16 127
+			"000000000000000000000000000000007fffffffffffffffffffffffffffffff" // int128 max
+		);
+		assert_eq!(
+			Writer::new()
+				.write(I256::from(-1i128))
+				.write(-128i8)
+				.write(127i16)
+				.write(i128::MAX)
+				.build(),
+			expected
+		);
+
+		let mut reader = Reader::new(&expected);
+		assert_eq!(
+			reader.read::<I256>().expect("to decode"),
+			I256::from(-1i128)
+		);
+		assert_eq!(reader.read::<i8>().expect("to decode"), -128i8);
+		assert_eq!(reader.read::<i16>().expect("to decode"), 127i16);
+		assert_eq!(reader.read::<i128>().expect("to decode"), i128::MAX);
+
+		// 128 doesn't fit in an int8, and neither does a negative value with a cleared sign bit.
+		let input = Writer::new().write(U256::from(128)).build();
+		assert!(Reader::new(&input).read::<i8>().is_err());
+		let input = Writer::new().write(I256::from(-129i128)).build();
+		assert!(Reader::new(&input).read::<i8>().is_err());
+		assert_eq!(i128::try_from(I256::from(i128::MIN)), Ok(i128::MIN));
+	}
+
+	#[test]
+	fn fixed_bytes_reject_dirty_padding() {
+		let input = hex!("6162630000000000000000000000000000000000000000000000000000000001");
+		assert!(Reader::new(&input).read::<FixedBytes<3>>().is_err());
+		assert_eq!(
+			Reader::new(&input)
+				.read::<FixedBytes<32>>()
+				.expect("to decode"),
+			FixedBytes(input)
+		);
+	}
+}
@@ -18,6 +18,7 @@
 
 //! Provides utilities for compatibility with Solidity tooling.
 
+pub mod abi;
 pub mod codec;
 pub mod modifier;
 pub mod revert;
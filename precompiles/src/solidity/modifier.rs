@@ -23,7 +23,7 @@ use fp_evm::Context;
 use sp_core::U256;
 
 /// Represents modifiers a Solidity function can be annotated with.
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FunctionModifier {
 	/// Function that doesn't modify the state.
 	View,
@@ -19,13 +19,21 @@
 //! Utilities to work with revert messages with support for backtraces and
 //! consistent formatting.
 
-use crate::solidity::{self, codec::bytes::UnboundedBytes};
+use crate::solidity::{
+	self,
+	abi::ErrorAbi,
+	codec::{bytes::UnboundedBytes, Codec},
+};
 use alloc::{
+	format,
 	string::{String, ToString},
 	vec::Vec,
 };
 use fp_evm::{ExitRevert, PrecompileFailure};
 
+// derive macro
+pub use precompile_utils_macro::CustomError;
+
 /// Represent the result of a computation that can revert.
 pub type MayRevert<T = ()> = Result<T, Revert>;
 
@@ -115,6 +123,15 @@ pub enum RevertReason {
 	/// Used by a check that the input contains at least N static arguments.
 	/// Often use to return early if the input is too short.
 	ExpectedAtLeastNArguments(usize),
+	/// A Solidity custom error such as `error InsufficientAllowance(uint256, uint256)`.
+	/// It is encoded as the selector of the error followed by its arguments, and thus
+	/// doesn't include the backtrace.
+	CustomError {
+		/// Name of the error.
+		name: String,
+		/// Encoded selector and arguments.
+		data: Vec<u8>,
+	},
 }
 
 impl RevertReason {
@@ -135,6 +152,32 @@ impl RevertReason {
 	pub fn value_is_too_large(what: impl Into<String>) -> Self {
 		RevertReason::ValueIsTooLarge { what: what.into() }
 	}
+
+	/// Create a `RevertReason::CustomError` from the name of the error and its arguments.
+	/// If `T` is a tuple each member represents an argument of the error.
+	pub fn custom_error<T: Codec>(name: impl Into<String>, arguments: T) -> Self {
+		let name = name.into();
+		let signature = format!("{name}{}", T::signature());
+		let digest = sp_io::hashing::keccak_256(signature.as_bytes());
+		let selector = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+
+		RevertReason::CustomError {
+			name,
+			data: solidity::encode_with_selector(selector, arguments),
+		}
+	}
+}
+
+/// Type representing Solidity custom errors, which can be returned by a precompile and listed in
+/// its Solidity interface with the `#[precompile::errors(...)]` attribute.
+/// Can be derived on enums, each variant being an error with the same name whose arguments are
+/// the fields of the variant.
+pub trait CustomError: Sized {
+	/// Convert the error into a `RevertReason::CustomError`.
+	fn into_revert_reason(self) -> RevertReason;
+
+	/// Describe all the errors of this type.
+	fn abi() -> Vec<ErrorAbi>;
 }
 
 impl core::fmt::Display for RevertReason {
@@ -151,6 +194,7 @@ impl core::fmt::Display for RevertReason {
 			RevertReason::ExpectedAtLeastNArguments(n) => {
 				write!(f, "Expected at least {n} arguments")
 			}
+			RevertReason::CustomError { name, .. } => write!(f, "Custom error {name}"),
 		}
 	}
 }
@@ -192,8 +236,13 @@ impl Revert {
 		self
 	}
 
-	/// Transforms the revert into its bytes representation (from a String).
+	/// Transforms the revert into its bytes representation (from a String), or into the
+	/// selector and arguments of a custom error.
 	pub fn to_encoded_bytes(self) -> Vec<u8> {
+		if let RevertReason::CustomError { data, .. } = self.reason {
+			return data;
+		}
+
 		let bytes: Vec<u8> = self.into();
 		solidity::encode_with_selector(ERROR_SELECTOR, UnboundedBytes::from(bytes))
 	}
@@ -17,7 +17,10 @@
 // limitations under the License.
 
 use crate::{
-	solidity::codec::Codec,
+	solidity::{
+		codec::Codec,
+		revert::{CustomError, Revert},
+	},
 	testing::{decode_revert_message, MockHandle, PrettyLog, SubcallHandle, SubcallTrait},
 };
 use alloc::boxed::Box;
@@ -222,6 +225,31 @@ impl<'p, P: PrecompileSet> PrecompilesTester<'p, P> {
 		self.assert_optionals();
 	}
 
+	/// Execute the precompile set and check it reverts with the provided custom error.
+	pub fn execute_reverts_with_error(mut self, error: impl CustomError) {
+		let res = self.execute();
+		let expected = Revert::new(error.into_revert_reason()).to_encoded_bytes();
+
+		match res {
+			Some(Err(PrecompileFailure::Revert { output, .. })) => {
+				if output != expected {
+					eprintln!(
+						"Revert output (bytes): {:?}",
+						sp_core::hexdisplay::HexDisplay::from(&output)
+					);
+					eprintln!(
+						"Expected error (bytes): {:?}",
+						sp_core::hexdisplay::HexDisplay::from(&expected)
+					);
+					panic!("Revert error doesn't match !");
+				}
+			}
+			other => panic!("Didn't revert, instead returned {:?}", other),
+		}
+
+		self.assert_optionals();
+	}
+
 	/// Execute the precompile set and check it returns provided output.
 	pub fn execute_error(mut self, error: ExitError) {
 		let res = self.execute();
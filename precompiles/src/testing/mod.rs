@@ -26,7 +26,9 @@ pub use account::*;
 pub use execution::*;
 pub use handle::*;
 pub use modifier::*;
-pub use solidity::{check_precompile_implements_solidity_interfaces, compute_selector};
+pub use solidity::{
+	check_precompile_implements_solidity_interfaces, compute_selector, write_precompile_abi,
+};
 
 use fp_evm::Log;
 
@@ -18,13 +18,31 @@
 
 //! Utility module to interact with solidity file.
 
+use crate::solidity::abi::PrecompileAbi;
 use sp_io::hashing::keccak_256;
 use std::{
 	collections::HashMap,
 	fs::File,
 	io::{BufRead, BufReader, Read},
+	path::Path,
 };
 
+/// Writes the Solidity interface and the ABI JSON generated by the `#[precompile]` macro
+/// (`<Precompile>Call::abi()`) in `dir`, as `<name>.sol` and `<name>.abi.json`.
+/// Meant to be called from a test or a build script to publish artifacts that are
+/// guaranteed to match the runtime.
+pub fn write_precompile_abi(abi: &PrecompileAbi, dir: impl AsRef<Path>) {
+	let dir = dir.as_ref();
+	std::fs::create_dir_all(dir)
+		.unwrap_or_else(|e| panic!("failed creating directory '{}': {}", dir.display(), e));
+
+	for (extension, content) in [("sol", abi.to_solidity()), ("abi.json", abi.to_abi_json())] {
+		let path = dir.join(format!("{}.{}", abi.name, extension));
+		std::fs::write(&path, content)
+			.unwrap_or_else(|e| panic!("failed writing file '{}': {}", path.display(), e));
+	}
+}
+
 pub fn check_precompile_implements_solidity_interfaces<F>(
 	files: &[&'static str],
 	supports_selector: F,
@@ -269,6 +287,62 @@ fn get_selectors_from_reader<R: Read>(reader: R) -> Vec<SolidityFunction> {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::solidity::{
+		abi::{ErrorAbi, FunctionAbi},
+		codec::{Address, UnboundedBytes},
+		modifier::FunctionModifier,
+	};
+	use sp_core::U256;
+
+	#[test]
+	fn test_generated_interface_is_parsed() {
+		let abi = PrecompileAbi {
+			name: String::from("Example"),
+			functions: vec![
+				FunctionAbi::new::<(Address, U256), bool>(
+					"transfer(address,uint256)",
+					&["to", "value"],
+					FunctionModifier::NonPayable,
+				),
+				FunctionAbi::new::<(Vec<(u8, UnboundedBytes)>,), (U256, Vec<Address>)>(
+					"batch((uint8,bytes)[])",
+					&["calls"],
+					FunctionModifier::View,
+				),
+			],
+			fallback: Some(FunctionModifier::Payable),
+			errors: vec![ErrorAbi::new::<(U256, U256)>(
+				"InsufficientAllowance",
+				&["have", "want"],
+			)],
+		};
+
+		let solidity = abi.to_solidity();
+		let actual = get_selectors_from_reader(solidity.as_bytes())
+			.into_iter()
+			.map(|sol_fn| (sol_fn.signature(), sol_fn.docs_selector.clone()))
+			.collect::<Vec<_>>();
+		let expected = abi
+			.functions
+			.iter()
+			.map(|f| (f.signature(), format!("{:08x}", f.selector())))
+			.collect::<Vec<_>>();
+		assert_eq!(expected, actual);
+
+		assert_eq!(
+			abi.to_abi_json(),
+			"[{\"type\":\"error\",\"name\":\"InsufficientAllowance\",\"inputs\":[\
+			{\"name\":\"have\",\"type\":\"uint256\"},{\"name\":\"want\",\"type\":\"uint256\"}]},\
+			{\"type\":\"function\",\"name\":\"transfer\",\"inputs\":[\
+			{\"name\":\"to\",\"type\":\"address\"},{\"name\":\"value\",\"type\":\"uint256\"}],\
+			\"outputs\":[{\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\"},\
+			{\"type\":\"function\",\"name\":\"batch\",\"inputs\":[{\"name\":\"calls\",\
+			\"type\":\"tuple[]\",\"components\":[{\"name\":\"\",\"type\":\"uint8\"},\
+			{\"name\":\"\",\"type\":\"bytes\"}]}],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\"},\
+			{\"name\":\"\",\"type\":\"address[]\"}],\"stateMutability\":\"view\"},\
+			{\"type\":\"fallback\",\"stateMutability\":\"payable\"}]"
+		);
+	}
 
 	#[test]
 	fn test_selectors_are_parsed() {
@@ -57,6 +57,8 @@ pub struct Vicinity {
 	pub gas_price: U256,
 	/// Origin of the transaction.
 	pub origin: H160,
+	/// Randomness of the current block, if any.
+	pub block_randomness: Option<H256>,
 }
 
 /// `System::Account` 16(hash) + 20 (key) + 60 (AccountInfo::max_encoded_len)
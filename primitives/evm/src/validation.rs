@@ -81,6 +81,8 @@ pub enum TransactionValidationError {
 	/// Unknown error
 	#[num_enum(default)]
 	UnknownError,
+	/// The sender is not allowed to deploy contracts
+	DeploymentNotAllowed,
 }
 
 impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config, E> {
@@ -160,6 +162,25 @@ impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config,
 		Ok(self)
 	}
 
+	/// Same as `with_balance_for`, for transactions whose fee is paid by a third party:
+	/// the account only needs to afford the transferred value.
+	pub fn with_sponsored_balance_for(&self, who: &Account) -> Result<&Self, E> {
+		// Fee input must still be well-formed.
+		let _ = self.transaction_fee_input()?;
+
+		if who.balance < self.transaction.value {
+			return Err(TransactionValidationError::BalanceTooLow.into());
+		}
+		Ok(self)
+	}
+
+	/// The most the transaction could possibly pay in fees, `max_fee_per_gas * gas_limit`
+	/// (or `gas_price * gas_limit` for Legacy and EIP-2930 transactions).
+	pub fn max_fee(&self) -> Result<U256, E> {
+		let (max_fee_per_gas, _) = self.transaction_fee_input()?;
+		Ok(max_fee_per_gas.saturating_mul(self.transaction.gas_limit))
+	}
+
 	// Returns the max_fee_per_gas (or gas_price for legacy txns) as well as an optional
 	// effective_gas_price for EIP-1559 transactions. effective_gas_price represents
 	// the total (fee + tip) that would be paid given the current base_fee.
@@ -261,6 +282,7 @@ mod tests {
 		InvalidChainId,
 		InvalidSignature,
 		UnknownError,
+		DeploymentNotAllowed,
 	}
 
 	static SHANGHAI_CONFIG: evm::Config = evm::Config::shanghai();
@@ -279,6 +301,7 @@ mod tests {
 				TransactionValidationError::InvalidChainId => TestError::InvalidChainId,
 				TransactionValidationError::InvalidSignature => TestError::InvalidSignature,
 				TransactionValidationError::UnknownError => TestError::UnknownError,
+				TransactionValidationError::DeploymentNotAllowed => TestError::DeploymentNotAllowed,
 			}
 		}
 	}
@@ -813,6 +836,43 @@ mod tests {
 		assert_eq!(res.unwrap_err(), TestError::BalanceTooLow);
 	}
 
+	// Sponsored transaction only requires the transferred value.
+	#[test]
+	fn validate_sponsored_balance_succeeds() {
+		let who = Account {
+			balance: U256::from(1u8),
+			nonce: U256::zero(),
+		};
+		let test = default_transaction(true);
+		assert!(test.with_balance_for(&who).is_err());
+		let res = test.with_sponsored_balance_for(&who);
+		assert!(res.is_ok());
+	}
+
+	// Sponsored transaction still fails if the value can't be transferred.
+	#[test]
+	fn validate_sponsored_insufficient_value_fails() {
+		let who = Account {
+			balance: U256::zero(),
+			nonce: U256::zero(),
+		};
+		let test = default_transaction(true);
+		let res = test.with_sponsored_balance_for(&who);
+		assert!(res.is_err());
+		assert_eq!(res.unwrap_err(), TestError::BalanceTooLow);
+	}
+
+	// Max fee is computed from max_fee_per_gas, regardless of the base fee.
+	#[test]
+	fn max_fee_uses_max_fee_per_gas() {
+		let test = transaction_max_fee_high(false);
+		let res = test.max_fee();
+		assert_eq!(
+			res.unwrap(),
+			U256::from(5_000_000_000u128).saturating_mul(U256::from(21_000u64))
+		);
+	}
+
 	// Transaction with invalid fee input - mixing gas_price and max_fee_per_gas.
 	#[test]
 	fn validate_balance_with_invalid_fee_input() {
@@ -45,6 +45,44 @@ pub struct TransactionStatus {
 	pub logs_bloom: Bloom,
 }
 
+/// Block environment overrides of a block simulated by `EthereumSimulateApi`.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct SimulateBlockOverrides {
+	/// Block number, defaults to the previous block number plus one.
+	pub number: Option<U256>,
+	/// Timestamp in seconds, defaults to the previous timestamp plus the block time.
+	pub timestamp: Option<u64>,
+	/// Base fee per gas, defaults to the current one.
+	pub base_fee_per_gas: Option<U256>,
+}
+
+/// A call executed in a block simulated by `EthereumSimulateApi`.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct SimulateCall {
+	pub from: Address,
+	/// Call target, or `None` for a contract creation.
+	pub to: Option<Address>,
+	pub data: Vec<u8>,
+	pub value: U256,
+	pub gas_limit: U256,
+	pub max_fee_per_gas: Option<U256>,
+	pub max_priority_fee_per_gas: Option<U256>,
+	pub nonce: Option<U256>,
+	pub access_list: Option<Vec<(Address, Vec<H256>)>>,
+}
+
+/// Outcome of a block simulated by `EthereumSimulateApi`.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct SimulatedBlock {
+	pub number: U256,
+	/// Timestamp in seconds.
+	pub timestamp: u64,
+	pub base_fee_per_gas: U256,
+	/// One result per call. The value of a contract creation is the deployed code, or the revert
+	/// data if it reverted.
+	pub results: Vec<Result<fp_evm::ExecutionInfoV2<Vec<u8>>, sp_runtime::DispatchError>>,
+}
+
 pub trait RuntimeStorageOverride<B: BlockT, C>: Send + Sync {
 	fn is_enabled() -> bool;
 
@@ -264,6 +302,44 @@ sp_api::decl_runtime_apis! {
 		fn initialize_pending_block(header: &<Block as BlockT>::Header);
 	}
 
+	/// EIP-2930 access list generation, used by `eth_createAccessList`.
+	pub trait EthereumAccessListApi {
+		/// Execute a call, or a contract creation if `to` is `None`, warming `access_list`
+		/// beforehand, and return the execution result together with every account and
+		/// storage slot the execution accessed. The returned `value` is empty for creations.
+		fn create_access_list(
+			from: Address,
+			to: Option<Address>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(Address, Vec<H256>)>>,
+		) -> Result<
+			(fp_evm::ExecutionInfoV2::<Vec<u8>>, Vec<(Address, Vec<H256>)>),
+			sp_runtime::DispatchError,
+		>;
+	}
+
+	/// Multi-block call simulation, used by `eth_simulateV1`.
+	pub trait EthereumSimulateApi {
+		/// Apply `overrides` to the block environment, then execute `calls` in order, each one
+		/// on top of the state left by the previous ones. The state changes are kept, so that
+		/// consecutive calls of this api on the same overlay simulate consecutive blocks.
+		///
+		/// When `validation` is set, calls are checked as transactions would be (nonce, fees and
+		/// balance), signatures excepted. When `trace_transfers` is set, every value transfer
+		/// emits a synthetic `Transfer` log.
+		fn simulate_block(
+			overrides: SimulateBlockOverrides,
+			calls: Vec<SimulateCall>,
+			validation: bool,
+			trace_transfers: bool,
+		) -> SimulatedBlock;
+	}
+
 	#[api_version(2)]
 	pub trait ConvertTransactionRuntimeApi {
 		fn convert_transaction(transaction: ethereum::TransactionV2) -> <Block as BlockT>::Extrinsic;
@@ -0,0 +1,274 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of the state proofs served by `eth_getProof`.
+//!
+//! Chains keeping the EVM state in the Substrate state trie have no Ethereum Merkle-Patricia
+//! trie, so `eth_getProof` answers with Substrate read-proofs: the `accountProof` covers the
+//! `EVM::AccountCodes` entry of the address and, when the node knows how its runtime maps
+//! addresses to accounts, the `System::Account` entry of the mapped account. Every
+//! `storageProof` covers one `EVM::AccountStorages` entry. All proofs are checked against the
+//! state root of the Substrate header the response was built for, which is returned as
+//! `storageHash`. Light clients and bridges should only trust that root once the header is
+//! finalized.
+//!
+//! The account mapping and the `frame_system::AccountInfo` type are specific to each runtime,
+//! so they are supplied by the caller. A typical verification looks like:
+//!
+//! ```ignore
+//! let account_id = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address);
+//! let account: Option<frame_system::AccountInfo<Nonce, AccountData>> =
+//!     fp_state_proof::verify_account(state_root, &account_id, &response.account_proof)?;
+//! let code = fp_state_proof::verify_code(state_root, address, &response.account_proof)?;
+//! for entry in &response.storage_proof {
+//!     let value = fp_state_proof::verify_storage(state_root, address, entry.key, &entry.proof)?;
+//!     assert_eq!(value, entry.value);
+//! }
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use sp_core::{
+	hashing::{blake2_128, twox_128},
+	Blake2Hasher, H160, H256,
+};
+use sp_trie::{LayoutV1, StorageProof};
+// Frontier
+use fp_storage::constants::{EVM_ACCOUNT_CODES, EVM_ACCOUNT_STORAGES, PALLET_EVM};
+
+/// Storage keys covered by `eth_getProof`.
+pub mod keys {
+	use super::*;
+
+	const PALLET_SYSTEM: &[u8] = b"System";
+	const SYSTEM_ACCOUNT: &[u8] = b"Account";
+
+	fn storage_prefix(pallet: &[u8], storage: &[u8]) -> Vec<u8> {
+		[twox_128(pallet), twox_128(storage)].concat()
+	}
+
+	fn blake2_128_concat(bytes: &[u8]) -> Vec<u8> {
+		[&blake2_128(bytes)[..], bytes].concat()
+	}
+
+	/// Key of the `System::Account` entry of `account_id`, the Substrate account an address is
+	/// mapped to.
+	pub fn account_info(account_id: &impl Encode) -> Vec<u8> {
+		let mut key = storage_prefix(PALLET_SYSTEM, SYSTEM_ACCOUNT);
+		key.extend(blake2_128_concat(&account_id.encode()));
+		key
+	}
+
+	/// Key of the `EVM::AccountCodes` entry of `address`.
+	pub fn account_code(address: H160) -> Vec<u8> {
+		let mut key = storage_prefix(PALLET_EVM, EVM_ACCOUNT_CODES);
+		key.extend(blake2_128_concat(address.as_bytes()));
+		key
+	}
+
+	/// Key of the `EVM::AccountStorages` entry of `address` at `index`.
+	pub fn account_storage(address: H160, index: H256) -> Vec<u8> {
+		let mut key = storage_prefix(PALLET_EVM, EVM_ACCOUNT_STORAGES);
+		key.extend(blake2_128_concat(address.as_bytes()));
+		key.extend(blake2_128_concat(index.as_bytes()));
+		key
+	}
+}
+
+/// Errors returned by the proof verification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+	/// The proof does not contain all the trie nodes needed to read the key under the
+	/// given state root.
+	IncompleteProof,
+	/// The proven value could not be decoded.
+	InvalidValue,
+}
+
+/// Reads `key` from the trie nodes of `proof`, checking them against `state_root`.
+///
+/// Returns `None` if the proof shows that the key is absent from the state.
+pub fn read_value(
+	state_root: H256,
+	proof: impl IntoIterator<Item = impl AsRef<[u8]>>,
+	key: &[u8],
+) -> Result<Option<Vec<u8>>, Error> {
+	let db = StorageProof::new(proof.into_iter().map(|node| node.as_ref().to_vec()))
+		.into_memory_db::<Blake2Hasher>();
+	sp_trie::read_trie_value::<LayoutV1<Blake2Hasher>, _>(&db, &state_root, key, None, None)
+		.map_err(|_| Error::IncompleteProof)
+}
+
+fn read_decoded<T: Decode>(
+	state_root: H256,
+	proof: impl IntoIterator<Item = impl AsRef<[u8]>>,
+	key: &[u8],
+) -> Result<Option<T>, Error> {
+	read_value(state_root, proof, key)?
+		.map(|value| T::decode(&mut &value[..]).map_err(|_| Error::InvalidValue))
+		.transpose()
+}
+
+/// Verifies the `System::Account` entry of `account_id` against `state_root`, decoding it as
+/// the `frame_system::AccountInfo` type `A` of the runtime.
+///
+/// Returns `None` if the account does not exist.
+pub fn verify_account<A: Decode>(
+	state_root: H256,
+	account_id: &impl Encode,
+	account_proof: impl IntoIterator<Item = impl AsRef<[u8]>>,
+) -> Result<Option<A>, Error> {
+	read_decoded(state_root, account_proof, &keys::account_info(account_id))
+}
+
+/// Verifies the contract code of `address` against `state_root`.
+///
+/// Returns `None` if `address` holds no code.
+pub fn verify_code(
+	state_root: H256,
+	address: H160,
+	account_proof: impl IntoIterator<Item = impl AsRef<[u8]>>,
+) -> Result<Option<Vec<u8>>, Error> {
+	read_decoded(state_root, account_proof, &keys::account_code(address))
+}
+
+/// Verifies the storage slot `index` of `address` against `state_root`.
+///
+/// Absent slots are proven as zero, like in the EVM.
+pub fn verify_storage(
+	state_root: H256,
+	address: H160,
+	index: H256,
+	storage_proof: impl IntoIterator<Item = impl AsRef<[u8]>>,
+) -> Result<H256, Error> {
+	Ok(read_decoded(
+		state_root,
+		storage_proof,
+		&keys::account_storage(address, index),
+	)?
+	.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::{traits::BlakeTwo256, StateVersion};
+	use sp_state_machine::{prove_read, InMemoryBackend};
+	use std::collections::BTreeMap;
+
+	const ALICE: H160 = H160::repeat_byte(0xaa);
+	const ALICE_ACCOUNT: [u8; 32] = [0xa1; 32];
+
+	/// Account entry of a runtime storing a nonce and a free balance.
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	struct AccountInfo {
+		nonce: u32,
+		free: u128,
+	}
+
+	fn state() -> (InMemoryBackend<BlakeTwo256>, H256) {
+		let account = AccountInfo {
+			nonce: 7,
+			free: 1_000,
+		};
+		let entries: BTreeMap<_, _> = [
+			(keys::account_info(&ALICE_ACCOUNT), account.encode()),
+			(keys::account_code(ALICE), vec![0x60u8, 0x00].encode()),
+			(
+				keys::account_storage(ALICE, H256::repeat_byte(1)),
+				H256::repeat_byte(2).encode(),
+			),
+		]
+		.into_iter()
+		.collect();
+		let backend = InMemoryBackend::<BlakeTwo256>::from((entries, StateVersion::V1));
+		let root = *backend.root();
+		(backend, root)
+	}
+
+	fn prove(backend: InMemoryBackend<BlakeTwo256>, keys: &[Vec<u8>]) -> Vec<Vec<u8>> {
+		prove_read(backend, keys.iter())
+			.expect("proof generation")
+			.into_iter_nodes()
+			.collect()
+	}
+
+	#[test]
+	fn account_proof_roundtrip() {
+		let (backend, root) = state();
+		let proof = prove(
+			backend,
+			&[
+				keys::account_info(&ALICE_ACCOUNT),
+				keys::account_code(ALICE),
+			],
+		);
+
+		assert_eq!(
+			verify_account(root, &ALICE_ACCOUNT, &proof),
+			Ok(Some(AccountInfo {
+				nonce: 7,
+				free: 1_000
+			}))
+		);
+		assert_eq!(
+			verify_code(root, ALICE, &proof).unwrap(),
+			Some(vec![0x60, 0x00])
+		);
+	}
+
+	#[test]
+	fn storage_proof_roundtrip() {
+		let (backend, root) = state();
+		let proof = prove(
+			backend,
+			&[
+				keys::account_storage(ALICE, H256::repeat_byte(1)),
+				keys::account_storage(ALICE, H256::repeat_byte(3)),
+			],
+		);
+
+		assert_eq!(
+			verify_storage(root, ALICE, H256::repeat_byte(1), &proof),
+			Ok(H256::repeat_byte(2))
+		);
+		assert_eq!(
+			verify_storage(root, ALICE, H256::repeat_byte(3), &proof),
+			Ok(H256::zero())
+		);
+	}
+
+	#[test]
+	fn proofs_only_cover_requested_keys_and_root() {
+		let (backend, root) = state();
+		let proof = prove(backend, &[keys::account_info(&ALICE_ACCOUNT)]);
+
+		assert_eq!(
+			verify_code(root, ALICE, &proof),
+			Err(Error::IncompleteProof)
+		);
+		assert_eq!(
+			verify_account::<AccountInfo>(H256::repeat_byte(9), &ALICE_ACCOUNT, &proof),
+			Err(Error::IncompleteProof)
+		);
+	}
+}
@@ -37,6 +37,27 @@ pub struct EthConfiguration {
     #[arg(long, default_value = "10000")]
     pub max_past_logs: u32,
 
+    /// Maximum number of blocks spanned by an `eth_getLogs` or filter logs query.
+    #[arg(long)]
+    pub max_block_range: Option<u32>,
+
+    /// Maximum approximate size in bytes of an `eth_getLogs` or filter logs response.
+    #[arg(long)]
+    pub max_logs_response_size: Option<usize>,
+
+    /// Maximum number of filters stored in the filter pool.
+    #[arg(long, default_value = "500")]
+    pub max_stored_filters: usize,
+
+    /// Maximum number of filters a single WebSocket RPC connection may install. Filters
+    /// installed over HTTP are only bounded by `--max-stored-filters`.
+    #[arg(long)]
+    pub max_filters_per_connection: Option<usize>,
+
+    /// Number of seconds a filter is kept in the pool without being polled.
+    #[arg(long, default_value = "300")]
+    pub filter_ttl: u64,
+
     /// Maximum fee history cache size.
     #[arg(long, default_value = "2048")]
     pub fee_history_limit: u64,
@@ -169,4 +190,7 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Maintain the Frontier mapping database.
+	FrontierDb(fc_cli::FrontierDbCmd),
 }
\ No newline at end of file
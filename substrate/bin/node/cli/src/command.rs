@@ -30,6 +30,7 @@ use node_primitives::Block;
 // use keyring::*;
 use sc_cli::{Result, SubstrateCli};
 use sc_network::{Litep2pNetworkBackend, NetworkBackend};
+use fc_storage::StorageOverrideHandler;
 use sc_service::PartialComponents;
 use sp_keyring::Sr25519Keyring;
 use sp_runtime::traits::HashingFor;
@@ -255,6 +256,24 @@ pub fn run() -> Result<()> {
             let runner = cli.create_runner(cmd)?;
             runner.sync_run(|config| cmd.run::<Block>(&config))
         }
+        Some(Subcommand::FrontierDb(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.sync_run(|config| {
+                let PartialComponents { client, .. } =
+                    new_partial::<Litep2pNetworkBackend>(&config, &cli.eth, None)?;
+                let frontier_backend = fc_db::Backend::KeyValue(Arc::new(fc_db::kv::Backend::open(
+                    client.clone(),
+                    &config.database,
+                    &service::db_config_dir(&config),
+                )?));
+                let storage_override = Arc::new(StorageOverrideHandler::<
+                    Block,
+                    FullClient,
+                    service::FullBackend,
+                >::new(client.clone()));
+                cmd.run(client, frontier_backend, storage_override)
+            })
+        }
         Some(_) => {
             unreachable!()
         }
@@ -18,7 +18,7 @@ use sp_core::H256;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
 pub use fc_consensus::FrontierBlockImport;
-use fc_rpc::EthTask;
+use fc_rpc::{EthMetrics, EthTask};
 pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
 pub use fc_storage::{StorageOverride, StorageOverrideHandler};
 
@@ -51,7 +51,9 @@ pub fn new_frontier_partial(
 pub trait EthCompatRuntimeApiCollection<Block: BlockT>:
     sp_api::ApiExt<Block>
     + fp_rpc::ConvertTransactionRuntimeApi<Block>
+    + fp_rpc::EthereumAccessListApi<Block>
     + fp_rpc::EthereumRuntimeRPCApi<Block>
+    + fp_rpc::EthereumSimulateApi<Block>
 {
 }
 
@@ -61,6 +63,7 @@ pub fn spawn_frontier_tasks<B, RA, HF>(
     backend: Arc<FullBackend<B>>,
     frontier_backend: Arc<FrontierBackend<B, FullClient<B, RA, HF>>>,
     filter_pool: Option<FilterPool>,
+    filter_ttl: Duration,
     storage_override: Arc<dyn StorageOverride<B>>,
     fee_history_cache: FeeHistoryCache,
     fee_history_cache_limit: FeeHistoryCacheLimit,
@@ -70,6 +73,8 @@ pub fn spawn_frontier_tasks<B, RA, HF>(
             fc_mapping_sync::EthereumBlockNotification<B>,
         >,
     >,
+    prometheus_registry: Option<prometheus_endpoint::Registry>,
+    eth_metrics: Option<Arc<EthMetrics>>,
 ) where
     B: BlockT<Hash = H256>,
     RA: ConstructRuntimeApi<B, FullClient<B, RA, HF>>,
@@ -95,6 +100,7 @@ pub fn spawn_frontier_tasks<B, RA, HF>(
                     fc_mapping_sync::SyncStrategy::Normal,
                     sync,
                     pubsub_notification_sinks,
+                    prometheus_registry,
                 )
                 .for_each(|()| future::ready(())),
             );
@@ -116,6 +122,7 @@ pub fn spawn_frontier_tasks<B, RA, HF>(
                     fc_mapping_sync::SyncStrategy::Parachain,
                     sync,
                     pubsub_notification_sinks,
+                    prometheus_registry,
                 ),
             );
         }
@@ -123,12 +130,15 @@ pub fn spawn_frontier_tasks<B, RA, HF>(
 
     // Spawn Frontier EthFilterApi maintenance task.
     if let Some(filter_pool) = filter_pool {
-        // Each filter is allowed to stay in the pool for 100 blocks.
-        const FILTER_RETAIN_THRESHOLD: u64 = 100;
         task_manager.spawn_essential_handle().spawn(
             "frontier-filter-pool",
             Some("frontier"),
-            EthTask::filter_pool_task(client.clone(), filter_pool, FILTER_RETAIN_THRESHOLD),
+            EthTask::filter_pool_task(
+                client.clone(),
+                filter_pool,
+                filter_ttl,
+                eth_metrics.clone(),
+            ),
         );
     }
 
@@ -141,6 +151,7 @@ pub fn spawn_frontier_tasks<B, RA, HF>(
             storage_override,
             fee_history_cache,
             fee_history_cache_limit,
+            eth_metrics,
         ),
     );
 }
@@ -150,6 +161,8 @@ where
     Block: BlockT,
     Api: sp_api::ApiExt<Block>
         + fp_rpc::ConvertTransactionRuntimeApi<Block>
-        + fp_rpc::EthereumRuntimeRPCApi<Block>,
+        + fp_rpc::EthereumAccessListApi<Block>
+        + fp_rpc::EthereumRuntimeRPCApi<Block>
+        + fp_rpc::EthereumSimulateApi<Block>,
 {
 }
\ No newline at end of file
@@ -80,7 +80,8 @@ pub type RuntimeExecutor = sc_executor::WasmExecutor<HostFunctions>;
 
 /// The full client type definition.
 pub type FullClient = sc_service::TFullClient<Block, RuntimeApi, RuntimeExecutor>;
-type FullBackend = sc_service::TFullBackend<Block>;
+/// The full backend type definition.
+pub type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 type FullGrandpaBlockImport =
 	grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
@@ -352,6 +353,8 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	  let name = config.network.node_name.clone();
 	  let enable_grandpa = !config.disable_grandpa;
 	  let prometheus_registry = config.prometheus_registry().cloned();
+	  // Shared by the Ethereum RPC and the Frontier maintenance tasks.
+	  let eth_metrics = fc_rpc::EthMetrics::new(prometheus_registry.as_ref());
 	  let enable_offchain_worker = config.offchain_worker.enabled;
   
 	  let hwbench = (!disable_hardware_benchmarks)
@@ -488,7 +491,8 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 					cache_size: 0,
 				},
 				&db_config_dir(&config),
-			)?)),
+			)?
+			.with_log_indexer(storage_override.clone()))),
 		
 			
 		  };
@@ -521,6 +525,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		  let rpc_statement_store = statement_store.clone();
   
 		  let target_gas_price = eth_config.target_gas_price;
+		  let filter_ttl = std::time::Duration::from_secs(eth_config.filter_ttl);
 		  let slot_duration = import_setup.2.config().slot_duration().clone();
 		  let pending_create_inherent_data_providers = move |_, ()| async move {
 			  let current = sp_timestamp::InherentDataProvider::from_system_time();
@@ -537,12 +542,19 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		  };
   
 		  let network = network.clone();
+		  let rpc_eth_metrics = eth_metrics.clone();
 		  let is_authority = config.role.clone().is_authority().clone();
 		  let sync_service0 = sync_service.clone();
 		  let rpc_extensions_builder =
 			  move |deny_unsafe, subscription_executor: node_rpc::SubscriptionTaskExecutor| {
 				  let enable_dev_signer = eth_config.enable_dev_signer;
-				  let max_past_logs = eth_config.max_past_logs;
+				  let filter_limits = fc_rpc::FilterLimits {
+					  max_stored_filters: eth_config.max_stored_filters,
+					  max_filters_per_connection: eth_config.max_filters_per_connection,
+					  max_past_logs: eth_config.max_past_logs,
+					  max_block_range: eth_config.max_block_range,
+					  max_response_size: eth_config.max_logs_response_size,
+				  };
 				  let execute_gas_limit_multiplier = eth_config.execute_gas_limit_multiplier;
 				  let eth_deps = node_rpc::EthDeps {
 					  client: client.clone(),
@@ -560,12 +572,13 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 					  storage_override: storage_override.clone(),
 					  block_data_cache: block_data_cache.clone(),
 					  filter_pool: filter_pool1.clone(),
-					  max_past_logs,
+					  filter_limits,
 					  fee_history_cache: fee_history_cache1.clone(),
 					  fee_history_cache_limit,
 					  execute_gas_limit_multiplier,
 					  forced_parent_hashes: None,
 					  pending_create_inherent_data_providers,
+					  metrics: rpc_eth_metrics.clone(),
 				  };
   
 				  let deps = node_rpc::FullDeps {
@@ -636,11 +649,14 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		  eth_backend.clone(),
 		  frontier_backend.clone(),
 		  filter_pool,
+		  filter_ttl,
 		  eth_storage_override.clone(),
 		  fee_history_cache,
 		  fee_history_cache_limit,
 		  sync_service.clone(),
 		  pubsub_notification_sinks,
+		  prometheus_registry.clone(),
+		  eth_metrics,
 	  );
   
 	  if let Some(hwbench) = hwbench {
@@ -5,7 +5,7 @@ use jsonrpsee::RpcModule;
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
 	client::BlockchainEvents,
-	AuxStore, UsageProvider,
+	AuxStore, ProofProvider, UsageProvider,
 };
 use sc_network::service::traits::NetworkService;
 use sc_network_sync::SyncingService;
@@ -21,10 +21,13 @@ use sp_inherents::CreateInherentDataProviders;
 use sp_runtime::traits::Block as BlockT;
 use node_primitives::Block;
 // Frontier
-pub use fc_rpc::{EthBlockDataCacheTask, EthConfig};
+pub use fc_rpc::{EthBlockDataCacheTask, EthConfig, EthMetrics, FilterLimits};
 pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
 use fc_storage::StorageOverride;
-use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
+use fp_rpc::{
+    ConvertTransaction, ConvertTransactionRuntimeApi, EthereumAccessListApi, EthereumRuntimeRPCApi,
+    EthereumSimulateApi,
+};
 
 /// Extra dependencies for Ethereum compatibility.
 pub struct EthDeps<C, P, A: ChainApi, CT, CIDP> {
@@ -52,8 +55,8 @@ pub struct EthDeps<C, P, A: ChainApi, CT, CIDP> {
     pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
     /// EthFilterApi pool.
     pub filter_pool: Option<FilterPool>,
-    /// Maximum number of logs in a query.
-    pub max_past_logs: u32,
+    /// Resource limits of filters and log queries.
+    pub filter_limits: FilterLimits,
     /// Fee history cache.
     pub fee_history_cache: FeeHistoryCache,
     /// Maximum fee history cache size.
@@ -65,6 +68,8 @@ pub struct EthDeps<C, P, A: ChainApi, CT, CIDP> {
     pub forced_parent_hashes: Option<BTreeMap<H256, H256>>,
     /// Something that can create the inherent data providers for pending state
     pub pending_create_inherent_data_providers: CIDP,
+    /// Prometheus metrics of the Ethereum RPC.
+    pub metrics: Option<Arc<EthMetrics>>,
 }
 
 /// Instantiate Ethereum-compatible RPC extensions.
@@ -81,9 +86,12 @@ pub fn create_eth<C, BE, P, A, CT, CIDP, EC>(
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
     // B: BlockT,
-    C: CallApiAt<Block> + ProvideRuntimeApi<Block>,
-    C::Api:
-        BlockBuilderApi<Block> + ConvertTransactionRuntimeApi<Block> + EthereumRuntimeRPCApi<Block>,
+    C: CallApiAt<Block> + ProofProvider<Block> + ProvideRuntimeApi<Block>,
+    C::Api: BlockBuilderApi<Block>
+        + ConvertTransactionRuntimeApi<Block>
+        + EthereumAccessListApi<Block>
+        + EthereumRuntimeRPCApi<Block>
+        + EthereumSimulateApi<Block>,
     C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
     C: BlockchainEvents<Block>
         + AuxStore
@@ -115,12 +123,13 @@ where
         storage_override,
         block_data_cache,
         filter_pool,
-        max_past_logs,
+        filter_limits,
         fee_history_cache,
         fee_history_cache_limit,
         execute_gas_limit_multiplier,
         forced_parent_hashes,
         pending_create_inherent_data_providers,
+        metrics,
     } = deps;
 
     let mut signers = Vec::new();
@@ -146,6 +155,7 @@ where
             forced_parent_hashes,
             pending_create_inherent_data_providers,
             Some(pending_consenus_data_provider),
+            metrics.clone(),
         )
         .replace_config::<EC>()
         .into_rpc(),
@@ -158,9 +168,9 @@ where
                 frontier_backend.clone(),
                 graph.clone(),
                 filter_pool,
-                500_usize, // max stored filters
-                max_past_logs,
+                filter_limits,
                 block_data_cache.clone(),
+                metrics,
             )
             .into_rpc(),
         )?;
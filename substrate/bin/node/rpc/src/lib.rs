@@ -143,6 +143,26 @@ where
     type EstimateGasAdapter = ();
     type RuntimeStorageOverride =
         fc_rpc::frontier_backend_client::SystemAccountId20StorageOverride<B, C, BE>;
+    type AccountProofKeys = AccountProofKeys;
+}
+
+/// Proves the `System::Account` entry of the account the runtime maps an address to, along
+/// with its `EVM::AccountCodes` entry.
+pub struct AccountProofKeys;
+
+impl fc_rpc::AccountProofKeys for AccountProofKeys {
+    fn account_proof_keys(address: sp_core::H160) -> Vec<Vec<u8>> {
+        use pallet_evm::AddressMapping;
+
+        let account_id =
+            <argochain_runtime::Runtime as pallet_evm::Config>::AddressMapping::into_account_id(
+                address,
+            );
+        vec![
+            fp_state_proof::keys::account_info(&account_id),
+            fp_state_proof::keys::account_code(address),
+        ]
+    }
 }
 
 /// Instantiate all Full RPC extensions.
@@ -160,6 +180,7 @@ where
     // Block: BlockT,
     C: ProvideRuntimeApi<Block>
         + sc_client_api::BlockBackend<Block>
+        + sc_client_api::ProofProvider<Block>
         + CallApiAt<Block>
         + HeaderBackend<Block>
         + AuxStore
@@ -171,12 +192,15 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash, BlockNumber>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: pallet_evm_contract_registry_rpc::EvmContractRegistryRuntimeApi<Block, BlockNumber>,
     // C::Api: sp_consensus_aura::AuraApi<Block, AuraId>,
     C::Api: sc_consensus_babe::BabeApi<Block>,
     C::Api: BlockBuilder<Block>,
     C::Api: sp_api::ApiExt<Block>,
     C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
     C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+    C::Api: fp_rpc::EthereumAccessListApi<Block>,
+    C::Api: fp_rpc::EthereumSimulateApi<Block>,
     C: BlockchainEvents<Block> + UsageProvider<Block> + StorageProvider<Block, B>,
     P: TransactionPool<Block = Block> + 'static,
     SC: SelectChain<Block> + 'static,
@@ -190,6 +214,7 @@ where
     CT: fp_rpc::ConvertTransaction<<Block as BlockT>::Extrinsic> + Send + Sync + 'static,
 {
     use mmr_rpc::{Mmr, MmrApiServer};
+    use pallet_evm_contract_registry_rpc::{ContractRegistry, ContractRegistryApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use sc_consensus_babe_rpc::{Babe, BabeApiServer};
     // use sc_consensus_beefy_rpc::{Beefy, BeefyApiServer};
@@ -251,6 +276,7 @@ where
     )?;
 
     io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    io.merge(ContractRegistry::new(client.clone()).into_rpc())?;
     let BabeDeps {
         keystore,
         babe_worker_handle,
@@ -149,8 +149,8 @@ use pallet_ethereum::{Call::transact, PostLogContent, Transaction as EthereumTra
 use fp_self_contained;
 
 use pallet_evm::{
-    Account as EVMAccount, EnsureAccountId20, EnsureAddressNever, EnsureAddressRoot, FeeCalculator,
-    GasWeightMapping, HashedAddressMapping, IdentityAddressMapping, Runner,
+    Account as EVMAccount, EnsureAccountId20, EnsureAddressMapped, FeeCalculator, GasWeightMapping,
+    HashedAddressMapping, IdentityAddressMapping, Runner,
 };
 
 use pallet_base_fee;
@@ -849,7 +849,6 @@ impl pallet_staking::Config for Runtime {
 	type HistoryDepth = HistoryDepth;
 	type EventListeners = NominationPools;
 	type WeightInfo = pallet_staking::weights::SubstrateWeight<Runtime>;
-	type MaxValidatorsCount = MaxValidatorsCount;
 	type BenchmarkingConfig = StakingBenchmarkingConfig;
 	type DisablingStrategy = pallet_staking::UpToLimitDisablingStrategy;
 }
@@ -1488,6 +1487,49 @@ parameter_types! {
 	pub const DepositPerByte: Balance = deposit(0, 1);
 	pub const DefaultDepositLimit: Balance = deposit(1024, 1024 * 1024);
 	pub CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(30);
+	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
+}
+
+impl pallet_contracts::Config for Runtime {
+	type Time = Timestamp;
+	type Randomness = RandomnessCollectiveFlip;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	/// The safest default is to allow no calls at all.
+	///
+	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
+	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
+	/// change because that would break already deployed contracts. The `Call` structure itself
+	/// is not allowed to change the indices of existing pallets, too.
+	type CallFilter = Nothing;
+	type DepositPerItem = DepositPerItem;
+	type DepositPerByte = DepositPerByte;
+	type DefaultDepositLimit = DefaultDepositLimit;
+	type CallStack = [pallet_contracts::Frame<Self>; 5];
+	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
+	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
+	/// Lets ink! contracts call EVM contracts, as the address `AddressMapping` derives for them.
+	type ChainExtension = pallet_evm_precompile_contracts::chain_extension::EvmChainExtension<Self>;
+	type Schedule = Schedule;
+	type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
+	type MaxCodeLen = ConstU32<{ 123 * 1024 }>;
+	type MaxStorageKeyLen = ConstU32<128>;
+	type UnsafeUnstableInterface = ConstBool<false>;
+	type UploadOrigin = EnsureSigned<Self::AccountId>;
+	type InstantiateOrigin = EnsureSigned<Self::AccountId>;
+	type MaxDebugBufferLen = ConstU32<{ 2 * 1024 * 1024 }>;
+	type MaxTransientStorageSize = ConstU32<{ 1 * 1024 * 1024 }>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type Migrations = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type Migrations = pallet_contracts::migration::codegen::BenchMigrations;
+	type MaxDelegateDependencies = ConstU32<32>;
+	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
+	type Debug = ();
+	type Environment = ();
+	type ApiVersion = ();
 }
 
 
@@ -2005,25 +2047,31 @@ impl pallet_ethereum::Config for Runtime {
 
 const BLOCK_GAS_LIMIT: u64 = 75_000_000;
 const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
+const MAX_STORAGE_GROWTH: u64 = 40 * 1024;
 pub const WEIGHT_MILLISECS_PER_BLOCK: u64 = 2000;
 
 parameter_types! {
 	pub const ChainId: u64 = 42;
 	pub BlockGasLimit: U256 = U256::from(BLOCK_GAS_LIMIT);
 	pub const GasLimitPovSizeRatio: u64 = BLOCK_GAS_LIMIT.saturating_div(MAX_POV_SIZE);
+	pub const GasLimitStorageGrowthRatio: u64 = BLOCK_GAS_LIMIT.saturating_div(MAX_STORAGE_GROWTH);
 	pub PrecompilesValue: FrontierPrecompiles<Runtime> = FrontierPrecompiles::<_>::new();
 	pub WeightPerGas: Weight = Weight::from_parts(weight_per_gas(BLOCK_GAS_LIMIT, NORMAL_DISPATCH_RATIO, WEIGHT_MILLISECS_PER_BLOCK), 0);
-	pub SuicideQuickClearLimit: u32 = 0;
+	pub SuicideQuickClearLimit: u32 = 64;
 }
 use pallet_evm::EVMCurrencyAdapter;
 
+/// Signed origins act as the EVM address derived from their account, so that multisig and proxied
+/// accounts can send EVM calls and deployments through `pallet_evm`.
+pub type EnsureEvmAddress = EnsureAddressMapped<AccountId, HashedAddressMapping<BlakeTwo256>>;
+
 impl pallet_evm::Config for Runtime {
     type FeeCalculator = BaseFee;
     type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
     type WeightPerGas = WeightPerGas;
     type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
-    type CallOrigin = EnsureAddressRoot<AccountId>;
-    type WithdrawOrigin = EnsureAddressNever<AccountId>;
+    type CallOrigin = EnsureEvmAddress;
+    type WithdrawOrigin = EnsureEvmAddress;
     type AddressMapping = HashedAddressMapping<BlakeTwo256>;
     type Currency = Balances;
     type RuntimeEvent = RuntimeEvent;
@@ -2032,17 +2080,64 @@ impl pallet_evm::Config for Runtime {
     type ChainId = ChainId;
     type BlockGasLimit = BlockGasLimit;
     type Runner = pallet_evm::runner::stack::Runner<Self>;
-    type OnChargeTransaction = EVMCurrencyAdapter<Balances, DealWithFees>;
-    type OnCreate = ();
+    type OnChargeTransaction = pallet_evm_fee_sponsorship::SponsoredFeeAdapter<
+        Self,
+        EVMCurrencyAdapter<Balances, DealWithFees>,
+    >;
+    type OnCreate = EvmContractRegistry;
+    type CreateFilter = EvmDeploymentAllowlist;
     type FindAuthor = FindAuthorTruncated<Babe>;
     type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+    type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
     type Timestamp = Timestamp;
-	type SuicideQuickClearLimit = SuicideQuickClearLimit;
+    type BlockRandomness = pallet_evm_randomness::PrevRandao<Self>;
+    type SuicideQuickClearLimit = SuicideQuickClearLimit;
     type WeightInfo = pallet_evm::weights::SubstrateWeight<Self>;
 }
 
 impl pallet_evm_chain_id::Config for Runtime {}
 
+parameter_types! {
+	pub const EvmFeeSponsorshipPalletId: PalletId = PalletId(*b"evm/spon");
+}
+
+impl pallet_evm_fee_sponsorship::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = EvmFeeSponsorshipPalletId;
+	type MaxTargets = ConstU32<16>;
+	type MaxSelectors = ConstU32<16>;
+	type WeightInfo = pallet_evm_fee_sponsorship::weights::SubstrateWeight<Self>;
+}
+
+parameter_types! {
+	pub const EvmRandomnessPalletId: PalletId = PalletId(*b"evm/rand");
+	pub const RandomnessRequestDeposit: Balance = 1 * ARGO;
+}
+
+impl pallet_evm_randomness::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type VrfRandomness = pallet_babe::ParentBlockRandomness<Self>;
+	type EpochRandomness = pallet_babe::RandomnessFromOneEpochAgo<Self>;
+	type PalletId = EvmRandomnessPalletId;
+	type Deposit = RandomnessRequestDeposit;
+	type MinBlockDelay = ConstU32<2>;
+	type MaxBlockDelay = ConstU32<2400>;
+	type ExpirationDelay = ConstU32<DAYS>;
+}
+
+impl pallet_evm_deployment_allowlist::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ManagerOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = pallet_evm_deployment_allowlist::weights::SubstrateWeight<Self>;
+}
+
+impl pallet_evm_contract_registry::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MetadataOrigin = EnsureEvmAddress;
+	type MaxMetadataLength = ConstU32<64>;
+	type WeightInfo = pallet_evm_contract_registry::weights::SubstrateWeight<Self>;
+}
+
 
 parameter_types! {
 	pub BoundDivision: U256 = U256::from(1024);
@@ -2132,6 +2227,7 @@ impl pallet_nfts::Config for Runtime {
 	type Helper = ();
 	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
 	type Locker = ();
+	type OnItemOwnerChange = pallet_evm_precompile_nfts_erc721::ClearTokenApprovals<Runtime>;
 }
 
 
@@ -2475,7 +2571,7 @@ mod runtime {
 	pub type AssetRate = pallet_asset_rate::Pallet<Runtime>;
 
 	#[runtime::pallet_index(21)]
-	// pub type Contracts = pallet_contracts::Pallet<Runtime>;
+	pub type Contracts = pallet_contracts::Pallet<Runtime>;
 
 	#[runtime::pallet_index(22)]
 	pub type Sudo = pallet_sudo::Pallet<Runtime>;
@@ -2664,6 +2760,18 @@ mod runtime {
 	#[runtime::pallet_index(84)]
 	pub type PalletCounter = pallet_counter;
 
+	#[runtime::pallet_index(85)]
+	pub type EvmFeeSponsorship = pallet_evm_fee_sponsorship;
+
+	#[runtime::pallet_index(86)]
+	pub type EvmRandomness = pallet_evm_randomness;
+
+	#[runtime::pallet_index(87)]
+	pub type EvmDeploymentAllowlist = pallet_evm_deployment_allowlist;
+
+	#[runtime::pallet_index(88)]
+	pub type EvmContractRegistry = pallet_evm_contract_registry;
+
 
 
 }
@@ -2796,6 +2904,9 @@ mod benches {
 		[pallet_example_mbm, PalletExampleMbms]
 		[pallet_asset_conversion_ops, AssetConversionMigration]
 		[pallet_ethereum, Ethereum]
+		[pallet_evm_fee_sponsorship, EvmFeeSponsorship]
+		[pallet_evm_deployment_allowlist, EvmDeploymentAllowlist]
+		[pallet_evm_contract_registry, EvmContractRegistry]
 
 	);
 }
@@ -3025,6 +3136,80 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_contracts::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord> for Runtime
+	{
+		fn call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+		) -> pallet_contracts::ContractExecResult<Balance, EventRecord> {
+			let gas_limit = gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block);
+			Contracts::bare_call(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				input_data,
+				pallet_contracts::DebugInfo::UnsafeDebug,
+				pallet_contracts::CollectEvents::UnsafeCollect,
+				pallet_contracts::Determinism::Enforced,
+			)
+		}
+
+		fn instantiate(
+			origin: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			code: pallet_contracts::Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> pallet_contracts::ContractInstantiateResult<AccountId, Balance, EventRecord>
+		{
+			let gas_limit = gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block);
+			Contracts::bare_instantiate(
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data,
+				salt,
+				pallet_contracts::DebugInfo::UnsafeDebug,
+				pallet_contracts::CollectEvents::UnsafeCollect,
+			)
+		}
+
+		fn upload_code(
+			origin: AccountId,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<Balance>,
+			determinism: pallet_contracts::Determinism,
+		) -> pallet_contracts::CodeUploadResult<Hash, Balance>
+		{
+			Contracts::bare_upload_code(
+				origin,
+				code,
+				storage_deposit_limit,
+				determinism,
+			)
+		}
+
+		fn get_storage(
+			address: AccountId,
+			key: Vec<u8>,
+		) -> pallet_contracts::GetStorageResult {
+			Contracts::get_storage(
+				address,
+				key
+			)
+		}
+	}
+
 
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
@@ -3196,6 +3381,36 @@ impl_runtime_apis! {
         }
     }
 
+	impl pallet_evm_fee_sponsorship_runtime_api::EvmFeeSponsorshipApi<Block, Balance> for Runtime {
+		fn policy_of(contract: H160) -> Option<u32> {
+			pallet_evm_fee_sponsorship::SponsoredContracts::<Runtime>::get(contract)
+		}
+
+		fn remaining_allowance(policy_id: u32, user: H160) -> Option<Balance> {
+			EvmFeeSponsorship::remaining_allowance(policy_id, &user)
+		}
+
+		fn pot_balance(policy_id: u32) -> Option<Balance> {
+			EvmFeeSponsorship::pot_balance(policy_id)
+		}
+	}
+
+	impl pallet_evm_contract_registry_runtime_api::EvmContractRegistryApi<Block, BlockNumber> for Runtime {
+		fn contract_info(
+			contract: H160,
+		) -> Option<pallet_evm_contract_registry_runtime_api::ContractInfo<BlockNumber>> {
+			EvmContractRegistry::contract_info(contract)
+		}
+
+		fn metadata_of(contract: H160) -> Option<Vec<u8>> {
+			EvmContractRegistry::metadata_of(contract)
+		}
+
+		fn contracts_of(deployer: H160) -> Vec<H160> {
+			EvmContractRegistry::contracts_of(deployer)
+		}
+	}
+
 
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
@@ -3547,6 +3762,212 @@ impl_runtime_apis! {
 
 	
 
+	impl fp_rpc::EthereumAccessListApi<Block> for Runtime {
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<(pallet_evm::CallInfo, Vec<(H160, Vec<H256>)>), sp_runtime::DispatchError> {
+			use pallet_evm::GasWeightMapping as _;
+
+			let evm_config = <Runtime as pallet_evm::Config>::config();
+
+			// Same estimate as `EthereumRuntimeRPCApi::call`, based on an EIP1559 transaction.
+			let mut estimated_transaction_len = data.len() + 258;
+			if access_list.is_some() {
+				estimated_transaction_len += access_list.encoded_size();
+			}
+
+			let gas_limit = if gas_limit > U256::from(BLOCK_GAS_LIMIT) {
+				BLOCK_GAS_LIMIT
+			} else {
+				gas_limit.low_u64()
+			};
+
+			let (weight_limit, proof_size_base_cost) =
+				match <Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+					gas_limit,
+					true,
+				) {
+					weight_limit if weight_limit.proof_size() > 0 => {
+						(Some(weight_limit), Some(estimated_transaction_len as u64))
+					}
+					_ => (None, None),
+				};
+
+			let (info, accessed) = pallet_evm::runner::stack::with_access_recorder(|| match to {
+				Some(to) => <Runtime as pallet_evm::Config>::Runner::call(
+					from,
+					to,
+					data,
+					value,
+					gas_limit.unique_saturated_into(),
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+					nonce,
+					access_list.unwrap_or_default(),
+					false,
+					true,
+					weight_limit,
+					proof_size_base_cost,
+					evm_config,
+				),
+				None => <Runtime as pallet_evm::Config>::Runner::create(
+					from,
+					data,
+					value,
+					gas_limit.unique_saturated_into(),
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+					nonce,
+					access_list.unwrap_or_default(),
+					false,
+					true,
+					weight_limit,
+					proof_size_base_cost,
+					evm_config,
+				)
+				.map(|info| pallet_evm::CallInfo {
+					exit_reason: info.exit_reason,
+					value: Vec::new(),
+					used_gas: info.used_gas,
+					weight_info: info.weight_info,
+					logs: info.logs,
+				}),
+			});
+
+			info.map(|info| (info, accessed)).map_err(|err| err.error.into())
+		}
+	}
+
+	impl fp_rpc::EthereumSimulateApi<Block> for Runtime {
+		fn simulate_block(
+			overrides: fp_rpc::SimulateBlockOverrides,
+			calls: Vec<fp_rpc::SimulateCall>,
+			validation: bool,
+			trace_transfers: bool,
+		) -> fp_rpc::SimulatedBlock {
+			use pallet_evm::GasWeightMapping as _;
+
+			let number = overrides
+				.number
+				.map(|number| number.unique_saturated_into())
+				.unwrap_or_else(|| System::block_number() + 1);
+			System::set_block_number(number);
+
+			let now = overrides
+				.timestamp
+				.map(|secs| secs.saturating_mul(1000))
+				.unwrap_or_else(|| pallet_timestamp::Now::<Runtime>::get() + MILLISECS_PER_BLOCK);
+			pallet_timestamp::Now::<Runtime>::put(now);
+
+			if let Some(base_fee) = overrides.base_fee_per_gas {
+				pallet_base_fee::BaseFeePerGas::<Runtime>::put(base_fee);
+			}
+
+			let evm_config = <Runtime as pallet_evm::Config>::config();
+			let execute = |call: fp_rpc::SimulateCall| {
+				// Same estimate as `EthereumRuntimeRPCApi::call`, based on an EIP1559 transaction.
+				let mut estimated_transaction_len = call.data.len() + 258;
+				if call.access_list.is_some() {
+					estimated_transaction_len += call.access_list.encoded_size();
+				}
+
+				let gas_limit = if call.gas_limit > U256::from(BLOCK_GAS_LIMIT) {
+					BLOCK_GAS_LIMIT
+				} else {
+					call.gas_limit.low_u64()
+				};
+
+				let (weight_limit, proof_size_base_cost) =
+					match <Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+						gas_limit,
+						true,
+					) {
+						weight_limit if weight_limit.proof_size() > 0 => {
+							(Some(weight_limit), Some(estimated_transaction_len as u64))
+						}
+						_ => (None, None),
+					};
+
+				let result = match call.to {
+					Some(to) => <Runtime as pallet_evm::Config>::Runner::call(
+						call.from,
+						to,
+						call.data,
+						call.value,
+						gas_limit.unique_saturated_into(),
+						call.max_fee_per_gas,
+						call.max_priority_fee_per_gas,
+						call.nonce,
+						call.access_list.unwrap_or_default(),
+						validation,
+						true,
+						weight_limit,
+						proof_size_base_cost,
+						evm_config,
+					),
+					None => {
+						let (result, output) = pallet_evm::runner::stack::with_create_output(|| {
+							<Runtime as pallet_evm::Config>::Runner::create(
+								call.from,
+								call.data,
+								call.value,
+								gas_limit.unique_saturated_into(),
+								call.max_fee_per_gas,
+								call.max_priority_fee_per_gas,
+								call.nonce,
+								call.access_list.unwrap_or_default(),
+								validation,
+								true,
+								weight_limit,
+								proof_size_base_cost,
+								evm_config,
+							)
+						});
+						// The deployed code of a successful creation, the revert data otherwise.
+						result.map(|info| pallet_evm::CallInfo {
+							value: if info.exit_reason.is_succeed() {
+								pallet_evm::AccountCodes::<Runtime>::get(info.value)
+							} else {
+								output
+							},
+							exit_reason: info.exit_reason,
+							used_gas: info.used_gas,
+							weight_info: info.weight_info,
+							logs: info.logs,
+						})
+					}
+				};
+				result.map_err(|err| err.error.into())
+			};
+
+			let results = calls
+				.into_iter()
+				.map(|call| {
+					if trace_transfers {
+						pallet_evm::runner::stack::with_transfer_logs(|| execute(call))
+					} else {
+						execute(call)
+					}
+				})
+				.collect();
+
+			fp_rpc::SimulatedBlock {
+				number: number.into(),
+				timestamp: now / 1000,
+				base_fee_per_gas: <Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price().0,
+				results,
+			}
+		}
+	}
+
 	impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
 		fn build_state(config: Vec<u8>) -> sp_genesis_builder::Result {
 			build_state::<RuntimeGenesisConfig>(config)
@@ -3662,9 +4083,93 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use fp_rpc::{
+		runtime_decl_for_ethereum_access_list_api::EthereumAccessListApiV1,
+		runtime_decl_for_ethereum_simulate_api::EthereumSimulateApiV1,
+	};
 	use frame_election_provider_support::NposSolution;
+	use frame_support::{assert_noop, assert_ok};
 	use frame_system::offchain::CreateSignedTransaction;
-	use sp_runtime::UpperOf;
+	use pallet_evm::{
+		runner::stack::{TRANSFER_LOG_ADDRESS, TRANSFER_LOG_TOPIC},
+		AddressMapping,
+	};
+	use sp_runtime::{DispatchError, UpperOf};
+
+	const ALICE: H160 = H160::repeat_byte(0xaa);
+	const BOB: H160 = H160::repeat_byte(0xbb);
+	const CONTRACT: H160 = H160::repeat_byte(0xcc);
+
+	// Stores its 32 bytes of call data in slot 0, or returns slot 0 when called without data.
+	const STORE_CODE: [u8; 23] = hex!("36600f5760005460005260206000f35b60003560005500");
+	// Returns the block number, the timestamp and the base fee.
+	const BLOCK_ENV_CODE: [u8; 17] = hex!("43600052426020524860405260606000f3");
+	// Deploys the code `0xff`.
+	const DEPLOY_CODE: [u8; 10] = hex!("60ff60005360016000f3");
+	// Reverts with 42.
+	const REVERT_CODE: [u8; 10] = hex!("602a60005260206000fd");
+
+	// Loads slot 1 of its own storage and the balance of `BOB`.
+	const ACCESS_CODE: [u8; 28] =
+		hex!("6001545073bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb315000");
+
+	/// Externalities in which `ALICE` is funded.
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(evm_account(ALICE), 1_000 * ARGO)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		sp_io::TestExternalities::new(storage)
+	}
+
+	fn evm_account(address: H160) -> AccountId {
+		<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+	}
+
+	fn simulate_call(to: Option<H160>, data: Vec<u8>, value: U256) -> fp_rpc::SimulateCall {
+		fp_rpc::SimulateCall {
+			from: ALICE,
+			to,
+			data,
+			value,
+			gas_limit: U256::from(1_000_000),
+			..Default::default()
+		}
+	}
+
+	fn simulate(
+		overrides: fp_rpc::SimulateBlockOverrides,
+		calls: Vec<fp_rpc::SimulateCall>,
+		trace_transfers: bool,
+	) -> fp_rpc::SimulatedBlock {
+		<Runtime as EthereumSimulateApiV1<Block>>::simulate_block(
+			overrides,
+			calls,
+			false,
+			trace_transfers,
+		)
+	}
+
+	fn create_access_list(to: Option<H160>, data: Vec<u8>) -> Vec<(H160, Vec<H256>)> {
+		let (info, access_list) = <Runtime as EthereumAccessListApiV1<Block>>::create_access_list(
+			ALICE,
+			to,
+			data,
+			U256::zero(),
+			U256::from(1_000_000),
+			None,
+			None,
+			None,
+			None,
+		)
+		.unwrap();
+		assert!(info.exit_reason.is_succeed());
+		access_list
+	}
 
 	#[test]
 	fn validate_transaction_submitter_bounds() {
@@ -3699,4 +4204,184 @@ mod tests {
 			size,
 		);
 	}
+
+	#[test]
+	fn simulated_blocks_carry_state_over() {
+		new_test_ext().execute_with(|| {
+			pallet_evm::Pallet::<Runtime>::create_account(CONTRACT, STORE_CODE.to_vec());
+			let stored = H256::from_low_u64_be(42);
+
+			let first = simulate(
+				Default::default(),
+				vec![simulate_call(Some(CONTRACT), stored.as_bytes().to_vec(), U256::zero())],
+				false,
+			);
+			assert!(first.results[0].as_ref().unwrap().exit_reason.is_succeed());
+
+			let second = simulate(
+				Default::default(),
+				vec![simulate_call(Some(CONTRACT), Vec::new(), U256::zero())],
+				false,
+			);
+			assert_eq!(second.number, first.number + U256::one());
+			assert_eq!(second.timestamp, first.timestamp + SECS_PER_BLOCK);
+			assert_eq!(second.results[0].as_ref().unwrap().value, stored.as_bytes());
+		});
+	}
+
+	#[test]
+	fn simulated_block_applies_overrides() {
+		new_test_ext().execute_with(|| {
+			pallet_evm::Pallet::<Runtime>::create_account(CONTRACT, BLOCK_ENV_CODE.to_vec());
+
+			let block = simulate(
+				fp_rpc::SimulateBlockOverrides {
+					number: Some(U256::from(100)),
+					timestamp: Some(5_000),
+					base_fee_per_gas: Some(U256::from(7)),
+				},
+				vec![simulate_call(Some(CONTRACT), Vec::new(), U256::zero())],
+				false,
+			);
+
+			assert_eq!(block.number, U256::from(100));
+			assert_eq!(block.timestamp, 5_000);
+			assert_eq!(block.base_fee_per_gas, U256::from(7));
+			let env: Vec<u8> = [
+				H256::from_low_u64_be(100),
+				H256::from_low_u64_be(5_000),
+				H256::from_low_u64_be(7),
+			]
+			.iter()
+			.flat_map(|word| word.as_bytes().to_vec())
+			.collect();
+			assert_eq!(block.results[0].as_ref().unwrap().value, env);
+		});
+	}
+
+	#[test]
+	fn simulated_transfers_are_logged_when_traced() {
+		new_test_ext().execute_with(|| {
+			let value = U256::from(10 * ARGO);
+			let transfer = || vec![simulate_call(Some(BOB), Vec::new(), value)];
+
+			let untraced = simulate(Default::default(), transfer(), false);
+			assert!(untraced.results[0].as_ref().unwrap().logs.is_empty());
+
+			let traced = simulate(Default::default(), transfer(), true);
+			let mut data = [0u8; 32];
+			value.to_big_endian(&mut data);
+			assert_eq!(
+				traced.results[0].as_ref().unwrap().logs,
+				vec![fp_evm::Log {
+					address: TRANSFER_LOG_ADDRESS,
+					topics: vec![TRANSFER_LOG_TOPIC, ALICE.into(), BOB.into()],
+					data: data.to_vec(),
+				}]
+			);
+			assert_eq!(
+				Balances::free_balance(evm_account(BOB)),
+				20 * ARGO,
+				"both transfers are kept"
+			);
+		});
+	}
+
+	#[test]
+	fn simulated_creation_returns_code_or_revert_data() {
+		new_test_ext().execute_with(|| {
+			let block = simulate(
+				Default::default(),
+				vec![
+					simulate_call(None, DEPLOY_CODE.to_vec(), U256::zero()),
+					simulate_call(None, REVERT_CODE.to_vec(), U256::zero()),
+				],
+				false,
+			);
+
+			let deployed = block.results[0].as_ref().unwrap();
+			assert!(deployed.exit_reason.is_succeed());
+			assert_eq!(deployed.value, vec![0xff]);
+
+			let reverted = block.results[1].as_ref().unwrap();
+			assert!(matches!(reverted.exit_reason, fp_evm::ExitReason::Revert(_)));
+			assert_eq!(reverted.value, H256::from_low_u64_be(42).as_bytes());
+		});
+	}
+
+	#[test]
+	fn call_access_list_lists_target_only_for_its_slots() {
+		new_test_ext().execute_with(|| {
+			pallet_evm::Pallet::<Runtime>::create_account(CONTRACT, ACCESS_CODE.to_vec());
+
+			assert_eq!(
+				create_access_list(Some(CONTRACT), Vec::new()),
+				vec![
+					(BOB, Vec::new()),
+					(CONTRACT, vec![H256::from_low_u64_be(1)]),
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn create_access_list_does_not_list_created_contract() {
+		new_test_ext().execute_with(|| {
+			// `DEPLOY_CODE` only touches the memory, `ACCESS_CODE` loads a slot of the created
+			// contract.
+			assert_eq!(create_access_list(None, DEPLOY_CODE.to_vec()), Vec::new());
+			assert_eq!(
+				create_access_list(None, ACCESS_CODE.to_vec()).len(),
+				2,
+				"the created contract is listed for its slot"
+			);
+			assert_eq!(
+				create_access_list(None, ACCESS_CODE[4..].to_vec()),
+				vec![(BOB, Vec::new())]
+			);
+		});
+	}
+
+	#[test]
+	fn signed_call_is_paid_by_the_derived_address() {
+		new_test_ext().execute_with(|| {
+			let signer = AccountId::from([1u8; 32]);
+			let source = <HashedAddressMapping<BlakeTwo256> as pallet_evm::AccountIdMapping<
+				AccountId,
+			>>::into_address(&signer);
+			let payer = evm_account(source);
+			assert_ne!(payer, signer);
+			assert_ok!(Balances::force_set_balance(
+				RuntimeOrigin::root(),
+				payer.clone().into(),
+				1_000 * ARGO
+			));
+			let base_fee = pallet_base_fee::BaseFeePerGas::<Runtime>::get();
+			let call = |origin| {
+				pallet_evm::Pallet::<Runtime>::call(
+					origin,
+					source,
+					BOB,
+					Vec::new(),
+					U256::zero(),
+					1_000_000,
+					base_fee,
+					None,
+					None,
+					Vec::new(),
+				)
+			};
+
+			assert_noop!(call(RuntimeOrigin::root()), DispatchError::BadOrigin);
+			assert_noop!(
+				call(RuntimeOrigin::signed(evm_account(ALICE))),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(call(RuntimeOrigin::signed(signer.clone())));
+
+			let fee = (base_fee * U256::from(21_000)).low_u128();
+			assert_eq!(Balances::free_balance(&payer), 1_000 * ARGO - fee);
+			assert_eq!(Balances::free_balance(&signer), 0);
+		});
+	}
 }
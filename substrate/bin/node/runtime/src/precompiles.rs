@@ -1,57 +1,90 @@
-use core::marker::PhantomData;
-use pallet_evm::{
-	IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult, PrecompileSet,
-};
+use crate::frame_support::parameter_types;
 use crate::sp_core::H160;
-
+use pallet_evm_precompile_batch::BatchPrecompile;
+use pallet_evm_precompile_call_permit::CallPermitPrecompile;
+use pallet_evm_precompile_contract_registry::ContractRegistryPrecompile;
+use pallet_evm_precompile_contracts::ContractsPrecompile;
+use pallet_evm_precompile_identity::IdentityPrecompile;
 use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_nfts_erc721::{Erc721NftsPrecompileSet, COLLECTION_ADDRESS_PREFIX};
+use pallet_evm_precompile_proxy::{EvmProxyCallFilter, EvmSubCall, ProxyPrecompile};
+use pallet_evm_precompile_randomness::RandomnessPrecompile;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+use pallet_evm_precompile_storage_cleaner::StorageCleanerPrecompile;
+use precompile_utils::precompile_set::*;
+use crate::sp_runtime::traits::BlakeTwo256;
 
-pub struct FrontierPrecompiles<R>(PhantomData<R>);
+/// Address reported by the ERC-721 precompiles for item owners unknown to the EVM, the same an
+/// account acts as through `EnsureEvmAddress`.
+type NftsAccountMapping = pallet_evm::HashedAddressMapping<BlakeTwo256>;
 
-impl<R> FrontierPrecompiles<R>
-where
-	R: pallet_evm::Config,
-{
-	pub fn new() -> Self {
-		Self(Default::default())
-	}
-	pub fn used_addresses() -> [H160; 7] {
-		[
-			hash(1),
-			hash(2),
-			hash(3),
-			hash(4),
-			hash(5),
-			hash(1024),
-			hash(1025),
-		]
-	}
+/// The Ethereum precompiles behave as on Ethereum, DELEGATECALL included.
+type EthereumPrecompilesChecks = (AcceptDelegateCall, CallableByContract, CallableByPrecompile);
+
+parameter_types! {
+	pub const Erc721NftsPrefix: &'static [u8] = &COLLECTION_ADDRESS_PREFIX;
 }
-impl<R> PrecompileSet for FrontierPrecompiles<R>
-where
-	R: pallet_evm::Config,
-{
-	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
-		match handle.code_address() {
-			// Ethereum precompiles :
-			a if a == hash(1) => Some(ECRecover::execute(handle)),
-			a if a == hash(2) => Some(Sha256::execute(handle)),
-			a if a == hash(3) => Some(Ripemd160::execute(handle)),
-			a if a == hash(4) => Some(Identity::execute(handle)),
-			a if a == hash(5) => Some(Modexp::execute(handle)),
-			// Non-Frontier specific nor Ethereum precompiles :
-			a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
-			a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
-			_ => None,
-		}
-	}
 
-	fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
-		IsPrecompileResult::Answer {
-			is_precompile: Self::used_addresses().contains(&address),
-			extra_cost: 0,
+type FrontierPrecompilesAt<R> = (
+	// Ethereum precompiles :
+	PrecompileAt<AddressU64<1>, ECRecover, EthereumPrecompilesChecks>,
+	PrecompileAt<AddressU64<2>, Sha256, EthereumPrecompilesChecks>,
+	PrecompileAt<AddressU64<3>, Ripemd160, EthereumPrecompilesChecks>,
+	PrecompileAt<AddressU64<4>, Identity, EthereumPrecompilesChecks>,
+	PrecompileAt<AddressU64<5>, Modexp, EthereumPrecompilesChecks>,
+	// Non-Frontier specific nor Ethereum precompiles :
+	PrecompileAt<AddressU64<1024>, Sha3FIPS256, (CallableByContract, CallableByPrecompile)>,
+	PrecompileAt<AddressU64<1025>, ECRecoverPublicKey, (CallableByContract, CallableByPrecompile)>,
+	// Argochain specific precompiles :
+	PrecompileAt<
+		AddressU64<2056>,
+		BatchPrecompile<R>,
+		(SubcallWithMaxNesting<1>, CallableByContract),
+	>,
+	PrecompileAt<
+		AddressU64<2057>,
+		RandomnessPrecompile<R>,
+		(CallableByContract, CallableByPrecompile),
+	>,
+	PrecompileAt<
+		AddressU64<2058>,
+		CallPermitPrecompile<R>,
+		(SubcallWithMaxNesting<0>, CallableByContract),
+	>,
+	PrecompileAt<AddressU64<2059>, StorageCleanerPrecompile<R>>,
+	PrecompileAt<
+		AddressU64<2060>,
+		IdentityPrecompile<R, crate::MaxAdditionalFields>,
+		CallableByContract,
+	>,
+	PrecompileAt<
+		AddressU64<2061>,
+		ProxyPrecompile<R>,
+		(SubcallWithMaxNesting<1>, CallableByContract),
+	>,
+	PrecompileAt<AddressU64<2062>, ContractsPrecompile<R>, CallableByContract>,
+	PrecompileAt<AddressU64<2063>, ContractRegistryPrecompile<R>, CallableByContract>,
+	// ERC-721 facades of the NFT collections :
+	PrecompileSetStartingWith<
+		Erc721NftsPrefix,
+		Erc721NftsPrecompileSet<R, NftsAccountMapping>,
+		(SubcallWithMaxNesting<1>, CallableByContract),
+	>,
+);
+
+/// The precompiles of the runtime, with the checks of `precompile_utils` (DELEGATECALL, calls
+/// from contracts and precompiles, subcall nesting) applied to each of them.
+pub type FrontierPrecompiles<R> = PrecompileSetBuilder<R, FrontierPrecompilesAt<R>>;
+
+/// EVM calls a proxy can make through the proxy precompile. Zero-value calls may still move
+/// tokens, so `NonTransfer` proxies can only manage the identity of the account they proxy.
+impl EvmProxyCallFilter for crate::ProxyType {
+	fn is_evm_proxy_call_allowed(&self, call: &EvmSubCall) -> bool {
+		match self {
+			crate::ProxyType::Any => true,
+			crate::ProxyType::NonTransfer => call.value.is_zero() && call.to == hash(2060),
+			crate::ProxyType::Governance | crate::ProxyType::Staking => false,
 		}
 	}
 }
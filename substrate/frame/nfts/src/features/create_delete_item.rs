@@ -262,6 +262,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		ItemPriceOf::<T, I>::remove(&collection, &item);
 		PendingSwapOf::<T, I>::remove(&collection, &item);
 		ItemAttributesApprovalsOf::<T, I>::remove(&collection, &item);
+		T::OnItemOwnerChange::on_item_owner_change(&collection, &item);
 
 		if remove_config {
 			ItemConfigOf::<T, I>::remove(&collection, &item);
@@ -163,6 +163,9 @@ pub mod pallet {
 		/// Locker trait to enable Locking mechanism downstream.
 		type Locker: Locker<Self::CollectionId, Self::ItemId>;
 
+		/// Called when an item changes owner or is burned.
+		type OnItemOwnerChange: OnItemOwnerChange<Self::CollectionId, Self::ItemId>;
+
 		/// The basic amount of funds that must be reserved for collection.
 		#[pallet::constant]
 		type CollectionDeposit: Get<DepositBalanceOf<Self, I>>;
@@ -546,3 +546,17 @@ pub struct PreSignedAttributes<CollectionId, ItemId, AccountId, Deadline> {
 	/// A deadline for the signature.
 	pub(super) deadline: Deadline,
 }
+
+/// Hook for the items of a collection changing owner, through a transfer, or being burned.
+///
+/// Lets state bound to the current owner of an item and kept outside of this pallet, such as the
+/// approvals of an ERC-721 facade, be cleared like the pallet clears the delegates of the item.
+/// Its weight is not accounted for by the pallet, so it must be small.
+pub trait OnItemOwnerChange<CollectionId, ItemId> {
+	/// The owner of `item` of `collection` changed, or the item was burned.
+	fn on_item_owner_change(collection: &CollectionId, item: &ItemId);
+}
+
+impl<CollectionId, ItemId> OnItemOwnerChange<CollectionId, ItemId> for () {
+	fn on_item_owner_change(_collection: &CollectionId, _item: &ItemId) {}
+}